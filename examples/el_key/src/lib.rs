@@ -305,6 +305,7 @@ fn card_table(model: &Model) -> Node<Msg> {
         id!["card-table"],
         div![
             id!["card-table__grid--enabled"],
+            animate_moves(),
             model.cards.iter().filter_map(|card| {
                 if card.enabled {
                     Some(enabled_card(card, model.el_key_enabled))
@@ -1,19 +1,16 @@
-use fluent::fluent_args;
 use seed::{prelude::*, *};
-use strum::IntoEnumIterator;
 
-mod i18n;
-use i18n::{I18n, Lang};
-
-const DEFAULT_LANG: Lang = Lang::EnUS;
+// Generates `FTL_RESOURCES` and the macro `t!` from the files in `ftl_messages`.
+seed::i18n_messages!("ftl_messages", fallback = "en-US");
 
 // ------ ------
 //     Init
 // ------ ------
 
-fn init(_: Url, _: &mut impl Orders<Msg>) -> Model {
+fn init(_: Url, orders: &mut impl Orders<Msg>) -> Model {
+    orders.subscribe(Msg::LanguageChanged);
     Model {
-        i18n: I18n::new(DEFAULT_LANG),
+        i18n: I18n::new(FTL_RESOURCES),
     }
 }
 
@@ -30,15 +27,19 @@ pub struct Model {
 // ------ ------
 
 enum Msg {
-    LangChanged(String),
+    LangSelected(String),
+    LanguageChanged(subs::LanguageChanged),
 }
 
-fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
-        Msg::LangChanged(lang) => {
+        Msg::LangSelected(lang) => {
             model
                 .i18n
-                .set_lang(lang.parse().expect("supported language"));
+                .set_locale(lang.parse().expect("supported language"), orders);
+        }
+        Msg::LanguageChanged(subs::LanguageChanged(locale)) => {
+            log!("Language changed:", locale.to_string());
         }
     }
 }
@@ -48,52 +49,80 @@ fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
 // ------ ------
 
 fn view(model: &Model) -> impl IntoNodes<Msg> {
-    let args_male_sg = fluent_args![
-      "userName" => "Stephan",
-      "photoCount" => 1,
-      "userGender" => "male",
-      "tabCount" => 1,
-      "formal" => "true"
-    ];
-
-    let args_female_pl = fluent_args![
-      "userName" => "Anna",
-      "photoCount" => 5,
-      "userGender" => "female",
-      "tabCount" => 7,
-      "formal" => "false"
-    ];
-
-    // Macro from the module `i18n`. It allows us to call `t!(..)` - see the code below.
-    create_t!(model.i18n);
-
+    let i18n = &model.i18n;
     div![
         div![select![
             attrs! {At::Name => "lang"},
-            Lang::iter().map(|lang| option![attrs! {At::Value => lang}, lang.label()]),
-            input_ev(Ev::Change, Msg::LangChanged),
+            i18n.available_locales().iter().map(|locale| {
+                let locale = locale.to_string();
+                option![
+                    attrs! {
+                        At::Value => locale,
+                        At::Selected => (i18n.locale().to_string() == locale).as_at_value(),
+                    },
+                    lang_label(&locale),
+                ]
+            }),
+            input_ev(Ev::Change, Msg::LangSelected),
         ],],
-        div![p!["Language in Model: ", model.i18n.lang().label()]],
+        div![p![
+            "Language in Model: ",
+            lang_label(&i18n.locale().to_string())
+        ]],
         div![],
         div![
-            p![t!("hello-world")],
-            p![t!("hello-user", args_male_sg)],
-            p![t!("shared-photos", args_male_sg)],
-            p![t!("tabs-close-button")],
-            p![t!("tabs-close-tooltip", args_male_sg)],
-            p![t!("tabs-close-warning", args_male_sg)],
-            p![t!("hello-user", args_female_pl)],
-            p![t!("shared-photos", args_female_pl)],
-            p![t!("tabs-close-button")],
-            p![t!("tabs-close-tooltip", args_female_pl)],
-            p![t!("tabs-close-warning", args_female_pl)],
-            p![t!("sync-dialog-title")],
-            p![t!("sync-headline-title")],
-            p![t!("sync-signedout-title")],
+            p![t!(i18n, "hello-world")],
+            p![t!(
+                i18n,
+                "hello-user",
+                userName = "Stephan",
+                formal = "true"
+            )],
+            p![t!(
+                i18n,
+                "shared-photos",
+                userName = "Stephan",
+                photoCount = 1,
+                userGender = "male",
+            )],
+            p![t!(i18n, "tabs-close-button")],
+            p![t!(i18n, "tabs-close-tooltip", tabCount = 1)],
+            p![t!(
+                i18n,
+                "tabs-close-warning",
+                tabCount = 1,
+                formal = "true"
+            )],
+            p![t!(i18n, "hello-user", userName = "Anna", formal = "false")],
+            p![t!(
+                i18n,
+                "shared-photos",
+                userName = "Anna",
+                photoCount = 5,
+                userGender = "female",
+            )],
+            p![t!(i18n, "tabs-close-tooltip", tabCount = 7)],
+            p![t!(
+                i18n,
+                "tabs-close-warning",
+                tabCount = 7,
+                formal = "false"
+            )],
+            p![t!(i18n, "sync-dialog-title")],
+            p![t!(i18n, "sync-headline-title")],
+            p![t!(i18n, "sync-signedout-title")],
         ],
     ]
 }
 
+fn lang_label(locale: &str) -> &'static str {
+    match locale {
+        "en-US" => "English (US)",
+        "de-DE" => "Deutsch (Deutschland)",
+        _ => "Unknown",
+    }
+}
+
 // ------ ------
 //     Start
 // ------ ------
@@ -10,17 +10,13 @@ const STORAGE_KEY: &str = "seed_unsaved_changes_text";
 // ------ ------
 
 #[allow(clippy::needless_pass_by_value)]
-fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
-    // https://developer.mozilla.org/en-US/docs/Web/API/WindowEventHandlers/onbeforeunload
-    orders
-        .stream(streams::window_event(Ev::BeforeUnload, Msg::BeforeUnload))
-        .subscribe(Msg::UrlRequested);
-
+fn init(url: Url, _: &mut impl Orders<Msg>) -> Model {
     let text = LocalStorage::get(STORAGE_KEY).unwrap_or_default();
     Model {
         base_url: url.to_base_url(),
         saved_text_hash: calculate_hash(&text),
         text,
+        navigation_guard: None,
     }
 }
 
@@ -32,6 +28,8 @@ struct Model {
     base_url: Url,
     saved_text_hash: u64,
     text: String,
+    // Navigation is guarded while the handle exists.
+    navigation_guard: Option<NavigationGuardHandle>,
 }
 
 // ------ ------
@@ -55,37 +53,34 @@ impl<'a> Urls<'a> {
 enum Msg {
     TextChanged(String),
     Save,
-    UrlRequested(subs::UrlRequested),
-    BeforeUnload(web_sys::Event),
 }
 
-fn update(msg: Msg, model: &mut Model, _: &mut impl Orders<Msg>) {
+fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
     match msg {
         Msg::TextChanged(text) => model.text = text,
         Msg::Save => {
             LocalStorage::insert(STORAGE_KEY, &model.text).expect("save text");
             model.saved_text_hash = calculate_hash(&model.text);
         }
-        Msg::UrlRequested(subs::UrlRequested(_, url_request)) => {
-            if calculate_hash(&model.text) == model.saved_text_hash {
-                return;
-            }
-            if let Ok(true) =
-                window().confirm_with_message("Do you want to leave? Data won't be saved.")
-            {
-                return;
-            }
-            url_request.handled_and_prevent_refresh();
-        }
-        Msg::BeforeUnload(event) => {
-            if calculate_hash(&model.text) != model.saved_text_hash {
-                log!("attempt to prevent navigation");
-                let event = event.unchecked_into::<web_sys::BeforeUnloadEvent>();
-                event.prevent_default();
-                // Because of Chrome
-                event.set_return_value("");
-            }
-        }
+    }
+
+    let unsaved_changes = calculate_hash(&model.text) != model.saved_text_hash;
+    if !unsaved_changes {
+        model.navigation_guard = None;
+    } else if model.navigation_guard.is_none() {
+        model.navigation_guard = Some(orders.guard_navigation(confirm_navigation));
+    }
+}
+
+fn confirm_navigation(request: &NavigationRequest) -> NavigationDecision {
+    // Browsers show their own dialog on tab close.
+    if request.kind == NavigationKind::Unload {
+        log!("attempt to prevent navigation");
+        return NavigationDecision::Cancel;
+    }
+    match window().confirm_with_message("Do you want to leave? Data won't be saved.") {
+        Ok(true) => NavigationDecision::Allow,
+        _ => NavigationDecision::Cancel,
     }
 }
 
@@ -0,0 +1,49 @@
+//! Tag and attribute names - copies of the lists used by `seed::virtual_dom::{Tag, At}`
+//! (`src/dom_entity_names`) so the crate can be published on its own.
+//! Update both copies together - the test below checks they are the same.
+
+macro_rules! make_tags {
+    { $($tag_camel:ident => $tag:expr),+ $(,)? } => {
+        pub const TAGS: &[&str] = &[$($tag),+];
+    }
+}
+
+macro_rules! make_attrs {
+    { $($attr_camel:ident => $attr:expr),+ $(,)? } => {
+        pub const ATTRIBUTES: &[&str] = &[$($attr),+];
+    }
+}
+
+include!("entity_names/tag_names.rs");
+include!("entity_names/attribute_names.rs");
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    /// Compare the copy with the list in the `seed` crate - skipped outside of the repository.
+    fn assert_same_as_seed_list(copy: &str, seed_path: &str) {
+        let seed_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../src/dom_entity_names")
+            .join(seed_path);
+        if let Ok(seed_list) = fs::read_to_string(&seed_path) {
+            assert!(
+                copy == seed_list,
+                "`{}` differs from its copy in `seed-macros`",
+                seed_path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn entity_names_match_seed() {
+        assert_same_as_seed_list(
+            include_str!("entity_names/tag_names.rs"),
+            "tags/tag_names.rs",
+        );
+        assert_same_as_seed_list(
+            include_str!("entity_names/attribute_names.rs"),
+            "attributes/attribute_names.rs",
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use crate::entity_names::{ATTRIBUTES, TAGS};
+use crate::markup::VOID_TAGS;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{braced, token, Expr, Ident, Lit, LitStr, Pat, Token};
+
+/// Valid attributes missing in the `At` list.
+const EXTRA_ATTRIBUTES: &[&str] = &["role"];
+
+// ------ Nodes ------
+
+pub struct Nodes(Vec<Node>);
+
+impl Parse for Nodes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let nodes = parse_children(input)?;
+        if !input.is_empty() {
+            return Err(input.error("unexpected closing tag"));
+        }
+        Ok(Self(nodes))
+    }
+}
+
+impl Nodes {
+    pub fn expand(&self) -> TokenStream {
+        match self.0.as_slice() {
+            [Node::Element(element)] => element.expand(false),
+            nodes => {
+                // Root nodes are added to a temporary element like element children,
+                // so `{expression}`s accept the same values as in element macros.
+                let children = nodes.iter().map(|node| node.expand_into(false));
+                quote! {
+                    {
+                        let mut el = ::seed::virtual_dom::El::<_>::empty(
+                            ::seed::virtual_dom::Tag::Div
+                        );
+                        #(#children)*
+                        el.children
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A statement that adds `value` to the element `el` through `UpdateEl`.
+fn update_el(value: TokenStream) -> TokenStream {
+    quote! { ::seed::virtual_dom::UpdateEl::update_el(#value, &mut el); }
+}
+
+// ------ Node ------
+
+enum Node {
+    Element(Element),
+    Text(LitStr),
+    Expr(Expr),
+    If(If),
+    For(For),
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            input.parse().map(Node::Element)
+        } else if input.peek(LitStr) {
+            input.parse().map(Node::Text)
+        } else if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            content.parse().map(Node::Expr)
+        } else if input.peek(Token![if]) {
+            input.parse().map(Node::If)
+        } else if input.peek(Token![for]) {
+            input.parse().map(Node::For)
+        } else {
+            Err(input.error("expected element, text, `{expression}`, `if` or `for`"))
+        }
+    }
+}
+
+impl Node {
+    /// Expand into statements that add the node to the element `el`.
+    fn expand_into(&self, in_svg: bool) -> TokenStream {
+        match self {
+            Node::Element(element) => update_el(element.expand(in_svg)),
+            Node::Text(text) => {
+                update_el(quote! { ::seed::virtual_dom::Node::<_>::new_text(#text) })
+            }
+            Node::Expr(expr) => update_el(quote! { #expr }),
+            Node::If(if_) => if_.expand_into(in_svg),
+            Node::For(for_) => {
+                let For { pat, expr, body } = for_;
+                let body = body.iter().map(|node| node.expand_into(in_svg));
+                quote! {
+                    for #pat in #expr {
+                        #(#body)*
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn parse_children(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() && !is_closing_tag(input) {
+        nodes.push(input.parse()?);
+    }
+    Ok(nodes)
+}
+
+fn is_closing_tag(input: ParseStream) -> bool {
+    input.peek(Token![<]) && input.peek2(Token![/])
+}
+
+fn parse_braced_children(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let nodes = parse_children(&content)?;
+    if !content.is_empty() {
+        return Err(content.error("unexpected closing tag"));
+    }
+    Ok(nodes)
+}
+
+// ------ Element ------
+
+struct Element {
+    name: Name,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Name = input.parse()?;
+        let is_custom = name.value.contains('-');
+        if !is_custom && !TAGS.contains(&name.value.as_str()) {
+            return Err(syn::Error::new(
+                name.span,
+                format!(
+                    "unknown tag `{}` - custom elements have to contain a hyphen",
+                    name.value
+                ),
+            ));
+        }
+
+        let mut attributes = Vec::new();
+        while !input.peek(Token![>]) && !input.peek(Token![/]) {
+            let attribute: Attribute = input.parse()?;
+            if let AttributeKind::Attribute = attribute.kind {
+                attribute.validate(is_custom)?;
+            }
+            attributes.push(attribute);
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+        input.parse::<Token![>]>()?;
+        if VOID_TAGS.contains(&name.value.as_str()) {
+            return Ok(Self {
+                name,
+                attributes,
+                children: Vec::new(),
+            });
+        }
+
+        let children = parse_children(input)?;
+        if input.is_empty() {
+            return Err(syn::Error::new(
+                name.span,
+                format!("missing closing tag `</{}>`", name.value),
+            ));
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let closing_name: Name = input.parse()?;
+        if closing_name.value != name.value {
+            return Err(syn::Error::new(
+                closing_name.span,
+                format!("expected closing tag `</{}>`", name.value),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            name,
+            attributes,
+            children,
+        })
+    }
+}
+
+impl Element {
+    fn expand(&self, in_svg: bool) -> TokenStream {
+        let tag = &self.name.value;
+        let is_svg = in_svg || tag == "svg";
+        let constructor = if is_svg {
+            quote!(empty_svg)
+        } else {
+            quote!(empty)
+        };
+        let attributes = self.attributes.iter().map(Attribute::expand);
+        // Children of `foreignObject` are HTML elements again.
+        let children_in_svg = is_svg && tag != "foreignObject";
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.expand_into(children_in_svg));
+        quote! {
+            {
+                #[allow(unused_mut)]
+                let mut el = ::seed::virtual_dom::El::#constructor(
+                    ::seed::virtual_dom::Tag::from(#tag)
+                );
+                #(#attributes)*
+                #(#children)*
+                ::seed::virtual_dom::Node::Element(el)
+            }
+        }
+    }
+}
+
+// ------ Attribute ------
+
+enum AttributeKind {
+    Attribute,
+    Event,
+}
+
+enum AttributeValue {
+    Lit(Lit),
+    Expr(Box<Expr>),
+    None,
+}
+
+struct Attribute {
+    kind: AttributeKind,
+    name: Name,
+    value: AttributeValue,
+}
+
+impl Parse for Attribute {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let kind = if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            AttributeKind::Event
+        } else {
+            AttributeKind::Attribute
+        };
+        let name: Name = input.parse()?;
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(token::Brace) {
+                let content;
+                braced!(content in input);
+                AttributeValue::Expr(Box::new(content.parse()?))
+            } else {
+                AttributeValue::Lit(input.parse()?)
+            }
+        } else {
+            AttributeValue::None
+        };
+
+        match (&kind, &value) {
+            (AttributeKind::Event, AttributeValue::Expr(_)) | (AttributeKind::Attribute, _) => {
+                Ok(Self { kind, name, value })
+            }
+            (AttributeKind::Event, _) => Err(syn::Error::new(
+                name.span,
+                format!("expected handler - e.g. `@{}={{|_| Msg::..}}`", name.value),
+            )),
+        }
+    }
+}
+
+impl Attribute {
+    fn validate(&self, in_custom_element: bool) -> syn::Result<()> {
+        let name = self.name.value.as_str();
+        let valid = in_custom_element
+            || name.starts_with("data-")
+            || name.starts_with("aria-")
+            || matches!(name, "key" | "ref")
+            || EXTRA_ATTRIBUTES.contains(&name)
+            || ATTRIBUTES.contains(&name);
+        if valid {
+            Ok(())
+        } else {
+            Err(syn::Error::new(
+                self.name.span,
+                format!("unknown attribute `{}`", name),
+            ))
+        }
+    }
+
+    fn expand(&self) -> TokenStream {
+        let name = &self.name.value;
+        let value = match &self.value {
+            AttributeValue::Lit(lit) => quote!(#lit),
+            AttributeValue::Expr(expr) => quote!(#expr),
+            AttributeValue::None => {
+                return quote! {
+                    el.attrs.vals.insert(
+                        ::seed::virtual_dom::At::from(#name),
+                        ::seed::virtual_dom::AtValue::None,
+                    );
+                };
+            }
+        };
+        match (&self.kind, name.as_str()) {
+            (AttributeKind::Event, _) => quote! {
+                el.add_event_handler(::seed::browser::dom::event_handler::ev(
+                    ::seed::virtual_dom::Ev::from(#name),
+                    #value,
+                ));
+            },
+            (AttributeKind::Attribute, "key") => quote! {
+                ::seed::virtual_dom::UpdateEl::update_el(
+                    ::seed::virtual_dom::el_key(&(#value)),
+                    &mut el,
+                );
+            },
+            (AttributeKind::Attribute, "ref") => quote! {
+                ::seed::virtual_dom::UpdateEl::update_el(
+                    ::seed::virtual_dom::el_ref(&(#value)),
+                    &mut el,
+                );
+            },
+            (AttributeKind::Attribute, _) => quote! {
+                el.attrs.vals.insert(
+                    ::seed::virtual_dom::At::from(#name),
+                    ::std::convert::Into::<::seed::virtual_dom::AtValue>::into(&(#value)),
+                );
+            },
+        }
+    }
+}
+
+// ------ If ------
+
+struct If {
+    condition: Expr,
+    then_branch: Vec<Node>,
+    else_branch: Option<Else>,
+}
+
+enum Else {
+    If(Box<If>),
+    Nodes(Vec<Node>),
+}
+
+impl Parse for If {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![if]>()?;
+        let condition = Expr::parse_without_eager_brace(input)?;
+        let then_branch = parse_braced_children(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(Else::If(Box::new(input.parse()?)))
+            } else {
+                Some(Else::Nodes(parse_braced_children(input)?))
+            }
+        } else {
+            None
+        };
+        Ok(Self {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl If {
+    fn expand_into(&self, in_svg: bool) -> TokenStream {
+        let condition = &self.condition;
+        let then_branch = self.then_branch.iter().map(|node| node.expand_into(in_svg));
+        let else_branch = match &self.else_branch {
+            Some(Else::If(if_)) => {
+                let if_ = if_.expand_into(in_svg);
+                quote!(else { #if_ })
+            }
+            Some(Else::Nodes(nodes)) => {
+                let nodes = nodes.iter().map(|node| node.expand_into(in_svg));
+                quote!(else { #(#nodes)* })
+            }
+            None => quote!(),
+        };
+        quote! {
+            if #condition {
+                #(#then_branch)*
+            } #else_branch
+        }
+    }
+}
+
+// ------ For ------
+
+struct For {
+    pat: Pat,
+    expr: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for For {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+        let body = parse_braced_children(input)?;
+        Ok(Self { pat, expr, body })
+    }
+}
+
+// ------ Name ------
+
+/// Tag or attribute name - identifiers (including keywords) joined by hyphens.
+struct Name {
+    value: String,
+    span: Span,
+}
+
+impl Parse for Name {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let first = Ident::parse_any(input)?;
+        let span = first.span();
+        let mut value = first.to_string();
+        while input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            value.push('-');
+            value.push_str(&Ident::parse_any(input)?.to_string());
+        }
+        Ok(Self { value, span })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse `html!` input and return the error message with the line and column of its span.
+    fn error(input: &str) -> (String, usize, usize) {
+        match syn::parse_str::<Nodes>(input) {
+            Ok(_) => panic!("`{}` is valid", input),
+            Err(error) => {
+                let start = error.span().start();
+                (error.to_string(), start.line, start.column)
+            }
+        }
+    }
+
+    #[test]
+    fn valid_names() {
+        assert!(syn::parse_str::<Nodes>(
+            r#"<div data-id="1" aria-label="a" role="list"><code-block foo="b" /></div>"#
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn unknown_tag() {
+        assert_eq!(
+            error("<div>\n    <foo></foo>\n</div>"),
+            (
+                "unknown tag `foo` - custom elements have to contain a hyphen".to_owned(),
+                2,
+                5
+            )
+        );
+    }
+
+    #[test]
+    fn unknown_attribute() {
+        assert_eq!(
+            error(r#"<p clas="a"></p>"#),
+            ("unknown attribute `clas`".to_owned(), 1, 3)
+        );
+    }
+
+    #[test]
+    fn mismatched_closing_tag() {
+        assert_eq!(
+            error("<p>\n    <b>\"text\"</i>\n</p>"),
+            ("expected closing tag `</b>`".to_owned(), 2, 15)
+        );
+    }
+}
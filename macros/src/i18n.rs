@@ -0,0 +1,271 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+/// Message id -> variable names.
+type Messages = BTreeMap<String, BTreeSet<String>>;
+
+// ------ Args ------
+
+pub struct Args {
+    directory: LitStr,
+    fallback: Option<LitStr>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let directory = input.parse()?;
+        let mut fallback = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let name: Ident = input.parse()?;
+            if name != "fallback" {
+                return Err(syn::Error::new(name.span(), "expected `fallback`"));
+            }
+            input.parse::<Token![=]>()?;
+            fallback = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self {
+            directory,
+            fallback,
+        })
+    }
+}
+
+impl Args {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let files = self.read_files()?;
+        let fallback = self.fallback_locale(&files)?;
+        let fallback_messages = &files[&fallback].1;
+
+        // All messages have to be in the fallback locale - the last one in the fallback chain.
+        let mut messages = Messages::new();
+        for (locale, (_, file_messages)) in &files {
+            for (id, variables) in file_messages {
+                if !fallback_messages.contains_key(id) {
+                    return Err(syn::Error::new(
+                        self.directory.span(),
+                        format!(
+                            "message `{}` from `{}` is missing in the fallback locale `{}`",
+                            id, locale, fallback
+                        ),
+                    ));
+                }
+                messages
+                    .entry(id.clone())
+                    .or_default()
+                    .extend(variables.iter().cloned());
+            }
+        }
+
+        let resources = files.iter().map(|(locale, (path, _))| {
+            let path = path.to_string_lossy().into_owned();
+            quote! { (#locale, include_str!(#path)) }
+        });
+
+        let t_arms = messages.iter().map(|(id, variables)| {
+            if variables.is_empty() {
+                quote! {
+                    ($i18n:expr, #id $(,)?) => {
+                        $i18n.translate(#id, None)
+                    };
+                }
+            } else {
+                quote! {
+                    ($i18n:expr, #id $(, $name:ident = $value:expr)* $(,)?) => {{
+                        #[allow(unused_mut)]
+                        let mut args = ::seed::i18n::FluentArgs::new();
+                        $(
+                            __seed_i18n_arg!(#id, $name);
+                            args.add(stringify!($name), ::seed::i18n::FluentValue::from($value));
+                        )*
+                        $i18n.translate(#id, Some(&args))
+                    }};
+                }
+            }
+        });
+        let arg_arms = messages.iter().flat_map(|(id, variables)| {
+            variables.iter().map(move |variable| {
+                let variable = Ident::new(variable, Span::call_site());
+                quote! { (#id, #variable) => {}; }
+            })
+        });
+
+        Ok(quote! {
+            /// FTL files loaded by `seed::i18n_messages!`.
+            pub const FTL_RESOURCES: ::seed::i18n::Resources = ::seed::i18n::Resources {
+                fallback_locale: #fallback,
+                files: &[#(#resources),*],
+            };
+
+            #[doc(hidden)]
+            #[allow(unused_macros)]
+            macro_rules! __seed_i18n_arg {
+                #(#arg_arms)*
+                ($id:literal, $name:ident) => {
+                    compile_error!(concat!(
+                        "unknown argument `", stringify!($name), "` of the message `", $id, "`"
+                    ))
+                };
+            }
+
+            /// Translate the message - e.g. `t!(model.i18n, "hello-user", userName = "Anna")`.
+            /// Messages and argument names are checked against FTL files.
+            #[allow(unused_macros)]
+            macro_rules! t {
+                #(#t_arms)*
+                ($i18n:expr, $id:literal $($rest:tt)*) => {
+                    compile_error!(concat!("unknown message `", $id, "`"))
+                };
+            }
+        })
+    }
+
+    /// Locale -> (path, messages).
+    fn read_files(&self) -> syn::Result<BTreeMap<String, (PathBuf, Messages)>> {
+        let error = |message: String| syn::Error::new(self.directory.span(), message);
+
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| error("`CARGO_MANIFEST_DIR` is not set".to_owned()))?;
+        let directory = PathBuf::from(manifest_dir).join(self.directory.value());
+        let entries = std::fs::read_dir(&directory)
+            .map_err(|err| error(format!("cannot read `{}`: {}", directory.display(), err)))?;
+
+        let mut files = BTreeMap::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|err| error(format!("cannot read `{}`: {}", directory.display(), err)))?
+                .path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("ftl") {
+                continue;
+            }
+            let locale = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(locale) => locale.to_owned(),
+                None => continue,
+            };
+            let source = std::fs::read_to_string(&path)
+                .map_err(|err| error(format!("cannot read `{}`: {}", path.display(), err)))?;
+            let messages = scan_messages(&source);
+            files.insert(locale, (path, messages));
+        }
+        if files.is_empty() {
+            return Err(error(format!(
+                "no `.ftl` files in `{}`",
+                directory.display()
+            )));
+        }
+        Ok(files)
+    }
+
+    fn fallback_locale(
+        &self,
+        files: &BTreeMap<String, (PathBuf, Messages)>,
+    ) -> syn::Result<String> {
+        match &self.fallback {
+            Some(fallback) if files.contains_key(&fallback.value()) => Ok(fallback.value()),
+            Some(fallback) => Err(syn::Error::new(
+                fallback.span(),
+                format!("missing FTL file `{}.ftl`", fallback.value()),
+            )),
+            None if files.len() == 1 => Ok(files.keys().next().cloned().unwrap_or_default()),
+            None => Err(syn::Error::new(
+                self.directory.span(),
+                "set the fallback locale - e.g. `fallback = \"en-US\"`",
+            )),
+        }
+    }
+}
+
+// ------ FTL scanning ------
+
+/// Find message ids and their variables. Terms (`-brand-name`), comments and attributes
+/// are handled only as parts of messages.
+fn scan_messages(source: &str) -> Messages {
+    let mut messages = Messages::new();
+    let mut current: Option<String> = None;
+    for line in source.lines() {
+        // Closing braces of multiline placeables may be unindented.
+        let starts_entry = line.starts_with(|c: char| !c.is_whitespace() && c != '}');
+        if starts_entry {
+            current = message_id(line).map(ToOwned::to_owned);
+            if let Some(id) = &current {
+                messages.entry(id.clone()).or_default();
+            }
+        }
+        if let Some(id) = &current {
+            if let Some(variables) = messages.get_mut(id) {
+                variables.extend(scan_variables(line));
+            }
+        }
+    }
+    messages
+}
+
+/// `hello-user = Hello` => `hello-user`.
+fn message_id(line: &str) -> Option<&str> {
+    if !line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let end = line
+        .find(|c: char| !is_identifier_char(c))
+        .unwrap_or(line.len());
+    if line[end..].trim_start().starts_with('=') {
+        Some(&line[..end])
+    } else {
+        None
+    }
+}
+
+fn scan_variables(line: &str) -> impl Iterator<Item = String> + '_ {
+    line.split('$').skip(1).filter_map(|rest| {
+        let end = rest
+            .find(|c: char| !is_identifier_char(c))
+            .unwrap_or(rest.len());
+        let name = &rest[..end];
+        // Only variables that are valid Rust identifiers can be passed through `t!`.
+        if name.starts_with(|c: char| c.is_ascii_alphabetic()) && !name.contains('-') {
+            Some(name.to_owned())
+        } else {
+            None
+        }
+    })
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_ftl_messages() {
+        let source = r#"
+# Comment with $notAVariable
+hello-world = Hello World.
+hello-user = Hello, {$userName}!
+-brand-name = Seed { $termVariable }
+tabs-close-tooltip = {$tabCount ->
+  [one] Close {$tabCount} tab
+  *[other] Close {$tabCount} tabs in { -brand-name }
+}
+    .title = { $title }
+"#;
+        let messages = scan_messages(source);
+        let ids = messages.keys().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(ids, ["hello-user", "hello-world", "tabs-close-tooltip"]);
+        assert!(messages["hello-world"].is_empty());
+        assert_eq!(
+            messages["hello-user"].iter().collect::<Vec<_>>(),
+            ["userName"]
+        );
+        assert_eq!(
+            messages["tabs-close-tooltip"].iter().collect::<Vec<_>>(),
+            ["tabCount", "title"]
+        );
+    }
+}
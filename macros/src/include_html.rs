@@ -0,0 +1,411 @@
+use crate::markup::{self, Element, Node};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// Elements whose whitespace-only text nodes are preserved.
+const PRESERVE_WHITESPACE_TAGS: &[&str] = &["pre", "textarea"];
+
+// ------ Args ------
+
+pub struct Args {
+    path: LitStr,
+    values: Vec<(Ident, Expr)>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+        let mut values = Vec::new();
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            let pairs =
+                Punctuated::<(Ident, Expr), Token![,]>::parse_terminated_with(input, |input| {
+                    let name = input.parse()?;
+                    input.parse::<Token![=]>()?;
+                    Ok((name, input.parse()?))
+                })?;
+            values.extend(pairs);
+        }
+        Ok(Self { path, values })
+    }
+}
+
+impl Args {
+    pub fn expand(&self) -> syn::Result<TokenStream> {
+        let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+            .map_err(|_| syn::Error::new(self.path.span(), "`CARGO_MANIFEST_DIR` is not set"))?;
+        let path = PathBuf::from(manifest_dir).join(self.path.value());
+        let source = std::fs::read_to_string(&path).map_err(|error| {
+            syn::Error::new(
+                self.path.span(),
+                format!("cannot read `{}`: {}", path.display(), error),
+            )
+        })?;
+        let nodes = markup::parse(&source).map_err(|error| {
+            syn::Error::new(
+                self.path.span(),
+                format!("cannot parse `{}`: {}", path.display(), error),
+            )
+        })?;
+        let nodes = without_whitespace(nodes);
+
+        let mut uses = BTreeMap::new();
+        count_placeholders(&nodes, &mut uses);
+        for (name, _) in &self.values {
+            if uses.remove(&name.to_string()).is_none() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("placeholder `{{{{{}}}}}` not found in the file", name),
+                ));
+            }
+        }
+        if let Some(name) = uses.keys().next() {
+            return Err(syn::Error::new(
+                self.path.span(),
+                format!("missing value for placeholder `{{{{{}}}}}`", name),
+            ));
+        }
+
+        let mut generator = Generator::default();
+        let bindings = self.values.iter().map(|(name, value)| {
+            let binding = generator.binding(&name.to_string());
+            quote! { let #binding = #value; }
+        });
+        let bindings = bindings.collect::<Vec<_>>();
+        count_placeholders(&nodes, &mut generator.uses);
+
+        let path = path.to_string_lossy().into_owned();
+        let output = match nodes.as_slice() {
+            [Node::Element(element)] => generator.element(element, None),
+            nodes => {
+                let pushes = nodes.iter().map(|node| match node {
+                    Node::Element(element) => {
+                        let element = generator.element(element, None);
+                        quote! { nodes.push(#element); }
+                    }
+                    Node::Text(text) => generator.text(text, |value| {
+                        quote! { nodes.extend(::seed::virtual_dom::IntoNodes::into_nodes(#value)); }
+                    }),
+                });
+                let pushes = pushes.collect::<Vec<_>>();
+                quote! {
+                    {
+                        let mut nodes = ::std::vec::Vec::new();
+                        #(#pushes)*
+                        nodes
+                    }
+                }
+            }
+        };
+        Ok(quote! {
+            {
+                // Rebuild when the file changes.
+                const _: &str = include_str!(#path);
+                #(#bindings)*
+                #output
+            }
+        })
+    }
+}
+
+// ------ Generator ------
+
+/// Uses of a placeholder in the file.
+#[derive(Default)]
+struct Uses {
+    /// The number of uses as an element part or child - the value is moved.
+    by_value: usize,
+    /// Is it used in an attribute value? - the value is borrowed.
+    in_attribute_value: bool,
+}
+
+#[derive(Default)]
+struct Generator {
+    /// Placeholder name -> its uses.
+    uses: BTreeMap<String, Uses>,
+}
+
+impl Generator {
+    fn binding(&self, name: &str) -> Ident {
+        Ident::new(&format!("placeholder_{}", name), Span::mixed_site())
+    }
+
+    /// Placeholder value - borrowed when the placeholder is used more than once
+    /// or in an attribute value.
+    fn value(&self, name: &str) -> TokenStream {
+        let binding = self.binding(name);
+        let borrowed = self
+            .uses
+            .get(name)
+            .iter()
+            .any(|uses| uses.by_value > 1 || uses.in_attribute_value);
+        if borrowed {
+            quote!(&#binding)
+        } else {
+            quote!(#binding)
+        }
+    }
+
+    fn element(&self, element: &Element, parent_namespace: Option<&str>) -> TokenStream {
+        let tag = &element.name;
+        let namespace = match (tag.as_str(), parent_namespace) {
+            ("svg", _) => Some("Svg"),
+            ("math", _) => Some("MathMl"),
+            (_, Some("Svg")) if tag != "foreignObject" => Some("Svg"),
+            (_, namespace) => namespace,
+        };
+        let set_namespace = namespace.map(|namespace| {
+            let namespace = Ident::new(namespace, Span::call_site());
+            quote! { el.namespace = Some(::seed::browser::dom::Namespace::#namespace); }
+        });
+        // Children of `foreignObject` are HTML elements again.
+        let children_namespace = if tag == "foreignObject" {
+            None
+        } else {
+            namespace
+        };
+
+        let attributes = element
+            .attributes
+            .iter()
+            .map(|(name, value)| self.attribute(name, value.as_deref()));
+        let children = element.children.iter().map(|child| match child {
+            Node::Element(child) => {
+                let child = self.element(child, children_namespace);
+                quote! { el.children.push(#child); }
+            }
+            Node::Text(text) => self.text(text, |value| {
+                quote! { ::seed::virtual_dom::UpdateEl::update_el(#value, &mut el); }
+            }),
+        });
+
+        quote! {
+            {
+                #[allow(unused_mut)]
+                let mut el = ::seed::virtual_dom::El::empty(::seed::virtual_dom::Tag::from(#tag));
+                #set_namespace
+                #(#attributes)*
+                #(#children)*
+                ::seed::virtual_dom::Node::Element(el)
+            }
+        }
+    }
+
+    fn attribute(&self, name: &str, value: Option<&str>) -> TokenStream {
+        if let Some(placeholder) = whole_placeholder(name) {
+            let value = self.value(placeholder);
+            return quote! { ::seed::virtual_dom::UpdateEl::update_el(#value, &mut el); };
+        }
+        let value = match value {
+            None => quote!(::seed::virtual_dom::AtValue::None),
+            Some(value) => match whole_placeholder(value) {
+                Some(placeholder) => {
+                    let binding = self.binding(placeholder);
+                    quote!(::std::convert::Into::<::seed::virtual_dom::AtValue>::into(&#binding))
+                }
+                None => {
+                    let parts = split_placeholders(value).into_iter().map(|part| match part {
+                        Part::Text(text) => quote! { value.push_str(#text); },
+                        Part::Placeholder(placeholder) => {
+                            let binding = self.binding(placeholder);
+                            quote! { value.push_str(&::std::string::ToString::to_string(&#binding)); }
+                        }
+                    });
+                    quote! {
+                        {
+                            let mut value = ::std::string::String::new();
+                            #(#parts)*
+                            ::seed::virtual_dom::AtValue::Some(value)
+                        }
+                    }
+                }
+            },
+        };
+        quote! {
+            el.attrs.vals.insert(::seed::virtual_dom::At::from(#name), #value);
+        }
+    }
+
+    /// `add` creates a statement that adds a placeholder value.
+    fn text(&self, text: &str, add: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
+        let parts = split_placeholders(text).into_iter().map(|part| match part {
+            Part::Text(text) => add(quote! { ::seed::virtual_dom::Node::<_>::new_text(#text) }),
+            Part::Placeholder(placeholder) => add(self.value(placeholder)),
+        });
+        quote! { #(#parts)* }
+    }
+}
+
+// ------ Placeholders ------
+
+enum Part<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split `text` into text parts and `{{name}}` placeholders.
+fn split_placeholders(text: &str) -> Vec<Part<'_>> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let placeholder = rest[start + 2..]
+            .find("}}")
+            .map(|end| &rest[start + 2..start + 2 + end])
+            .filter(|name| is_placeholder_name(name.trim()));
+        match placeholder {
+            Some(placeholder) => {
+                if start > 0 {
+                    parts.push(Part::Text(&rest[..start]));
+                }
+                parts.push(Part::Placeholder(placeholder.trim()));
+                rest = &rest[start + placeholder.len() + 4..];
+            }
+            None => {
+                parts.push(Part::Text(&rest[..start + 2]));
+                rest = &rest[start + 2..];
+            }
+        }
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    parts
+}
+
+fn whole_placeholder(text: &str) -> Option<&str> {
+    match split_placeholders(text).as_slice() {
+        [Part::Placeholder(name)] => Some(name),
+        _ => None,
+    }
+}
+
+fn is_placeholder_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Count uses of placeholders as element parts and children
+/// and mark placeholders used in attribute values.
+fn count_placeholders(nodes: &[Node], uses: &mut BTreeMap<String, Uses>) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => count_placeholders_in(text, true, uses),
+            Node::Element(element) => {
+                for (name, value) in &element.attributes {
+                    count_placeholders_in(name, true, uses);
+                    if let Some(value) = value {
+                        count_placeholders_in(value, false, uses);
+                    }
+                }
+                count_placeholders(&element.children, uses);
+            }
+        }
+    }
+}
+
+fn count_placeholders_in(text: &str, by_value: bool, uses: &mut BTreeMap<String, Uses>) {
+    for part in split_placeholders(text) {
+        if let Part::Placeholder(name) = part {
+            let uses = uses.entry(name.to_owned()).or_default();
+            if by_value {
+                uses.by_value += 1;
+            } else {
+                uses.in_attribute_value = true;
+            }
+        }
+    }
+}
+
+/// Remove whitespace-only text nodes - e.g. indentation between SVG elements.
+fn without_whitespace(nodes: Vec<Node>) -> Vec<Node> {
+    nodes
+        .into_iter()
+        .filter_map(|node| match node {
+            Node::Text(text) if text.trim().is_empty() => None,
+            Node::Element(mut element) => {
+                if !PRESERVE_WHITESPACE_TAGS.contains(&element.name.as_str()) {
+                    element.children = without_whitespace(element.children);
+                }
+                Some(Node::Element(element))
+            }
+            node => Some(node),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Placeholders are written as `{name}` to make the expected parts readable.
+    fn parts(text: &str) -> Vec<String> {
+        split_placeholders(text)
+            .into_iter()
+            .map(|part| match part {
+                Part::Text(text) => text.to_owned(),
+                Part::Placeholder(name) => format!("{{{}}}", name),
+            })
+            .collect()
+    }
+
+    fn uses(source: &str) -> BTreeMap<String, Uses> {
+        let nodes = markup::parse(source).ok().unwrap();
+        let mut uses = BTreeMap::new();
+        count_placeholders(&without_whitespace(nodes), &mut uses);
+        uses
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(parts("a {{b}} c"), ["a ", "{b}", " c"]);
+        assert_eq!(parts("{{ b }}{{c}}"), ["{b}", "{c}"]);
+        // Invalid names are kept as text.
+        assert_eq!(parts("{{1}} {{a b}} {{"), ["{{", "1}} {{", "a b}} {{"]);
+        assert!(parts("").is_empty());
+    }
+
+    #[test]
+    fn whole() {
+        assert_eq!(whole_placeholder("{{ attrs }}"), Some("attrs"));
+        assert_eq!(whole_placeholder("{{a}} "), None);
+        assert_eq!(whole_placeholder("a"), None);
+    }
+
+    #[test]
+    fn count() {
+        let uses = uses(
+            r#"<svg {{ attrs }} class="{{class}}">
+                <text x="{{x}}">{{label}} {{label}}</text>
+                {{ child }}
+                <circle r="{{x}}" />
+            </svg>"#,
+        );
+        let counts = uses
+            .iter()
+            .map(|(name, uses)| (name.as_str(), uses.by_value, uses.in_attribute_value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            [
+                ("attrs", 1, false),
+                ("child", 1, false),
+                ("class", 0, true),
+                ("label", 2, false),
+                ("x", 0, true),
+            ]
+        );
+
+        let generator = Generator { uses };
+        let moved = |name| !generator.value(name).to_string().starts_with('&');
+        assert!(moved("attrs"));
+        assert!(moved("child"));
+        assert!(!moved("class"));
+        assert!(!moved("label"));
+        assert!(!moved("x"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Procedural macros for [Seed](https://seed-rs.org) - re-exported by the crate `seed`.
+
+#![forbid(unsafe_code)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+mod entity_names;
+mod html;
+mod i18n;
+mod include_html;
+mod markup;
+
+/// HTML-like templates.
+///
+/// - Elements: `<div class="x">..</div>`, `<input type="text" />` or void elements without `/`.
+///   Custom elements have to contain a hyphen - e.g. `<code-block>`.
+/// - Attributes: `name="value"`, `name={expression}` or just `name` (e.g. `disabled`).
+///   Values are converted like in `attrs!` - use `{cond.as_at_value()}` for conditional boolean attributes.
+///   Special attributes: `key={..}` (see `el_key`) and `ref={..}` (see `el_ref`).
+/// - Events: `@click={|_| Msg::Clicked}` - see `ev`.
+/// - Children: `"text"`, `{expression}` (anything that can be passed into element macros),
+///   `if cond { .. } else { .. }` and `for item in items { .. }`.
+///
+/// One root element is expanded into `Node<Ms>`; other root nodes into `Vec<Node<Ms>>`
+/// (attributes, styles or event handlers in root `{expression}`s are ignored).
+/// Unknown tags and attributes are compile errors pointing to their names.
+///
+/// # Example
+///
+/// ```rust,ignore
+///html! {
+///    <div class="counter">
+///        <button @click={|_| Msg::Increment} disabled={model.busy.as_at_value()}>"+"</button>
+///        <span>{model.count}</span>
+///        if model.count > 10 {
+///            <p>"That's a lot!"</p>
+///        }
+///        <ul>
+///            for item in &model.items {
+///                <li key={item.id}>{&item.title}</li>
+///            }
+///        </ul>
+///    </div>
+///}
+/// ```
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    match syn::parse::<html::Nodes>(input) {
+        Ok(nodes) => nodes.expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Include an HTML or SVG file as vdom nodes - the file is parsed at compile time.
+///
+/// The path is relative to the crate root (the directory with `Cargo.toml`).
+/// SVG and MathML elements get the correct `Namespace`.
+/// Whitespace-only text nodes (except in `pre` and `textarea`) and comments are removed.
+///
+/// Placeholders `{{name}}` are replaced by values passed into the macro:
+/// - Attribute value: `width="{{size}}"` or `class="icon {{extra}}"` - values have to implement `ToString`.
+/// - Attribute: `<svg {{attrs}}>` - anything that can be passed into element macros
+///   (e.g. `C!`, `attrs!`, `ev`).
+/// - Text: `<p>Hello {{name}}!</p>` - anything that can be passed into element macros
+///   (e.g. text, nodes, `Vec<Node<Ms>>`).
+///
+/// One root node is expanded into `Node<Ms>`; more root nodes into `Vec<Node<Ms>>`.
+///
+/// # Example
+///
+/// ```rust,ignore
+///// icons/close.svg:
+///// <svg viewBox="0 0 24 24" width="{{size}}" {{parts}}><path d="M6 6L18 18M6 18L18 6"/></svg>
+///
+///fn close_button() -> Node<Msg> {
+///    button![include_html!(
+///        "icons/close.svg",
+///        size = 24,
+///        parts = ev(Ev::Click, |_| Msg::Close),
+///    )]
+///}
+/// ```
+#[proc_macro]
+pub fn include_html(input: TokenStream) -> TokenStream {
+    match syn::parse::<include_html::Args>(input).and_then(|args| args.expand()) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Load Fluent (`.ftl`) translations and generate the checked `t!` macro.
+///
+/// Each `<locale>.ftl` file in the directory (relative to the crate root) is one locale -
+/// e.g. `ftl_messages/en-US.ftl` and `ftl_messages/de-DE.ftl`.
+/// The macro generates:
+/// - `FTL_RESOURCES` - embedded files for `seed::i18n::I18n::new`.
+/// - `t!(i18n, "message-id", arg = value, ..)` - unknown message ids and argument names
+///   are compile errors.
+///
+/// All messages have to be defined in the `fallback` locale (optional with only one file).
+///
+/// # Example
+///
+/// ```rust,ignore
+///seed::i18n_messages!("ftl_messages", fallback = "en-US");
+///
+///fn view(model: &Model) -> Node<Msg> {
+///    div![t!(model.i18n, "hello-user", userName = &model.user_name)]
+///}
+/// ```
+#[proc_macro]
+pub fn i18n_messages(input: TokenStream) -> TokenStream {
+    match syn::parse::<i18n::Args>(input).and_then(|args| args.expand()) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
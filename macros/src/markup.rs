@@ -0,0 +1,390 @@
+//! Minimal parser for well-formed HTML / SVG files used by `include_html!`.
+//!
+//! Supports elements, attributes (quoted, unquoted or without value), void HTML elements,
+//! raw text elements (`script`, `style`), comments, the XML declaration, `DOCTYPE`
+//! and basic character references. Optional closing tags are not inferred.
+
+use std::fmt;
+
+/// Elements without children and closing tags.
+pub const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is not parsed as markup.
+const RAW_TEXT_TAGS: &[&str] = &["script", "style"];
+
+// ------ Node ------
+
+pub enum Node {
+    Element(Element),
+    Text(String),
+}
+
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, Option<String>)>,
+    pub children: Vec<Node>,
+}
+
+// ------ Error ------
+
+pub struct Error {
+    message: String,
+    line: usize,
+    column: usize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (line {}, column {})",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+// ------ parse ------
+
+pub fn parse(source: &str) -> Result<Vec<Node>, Error> {
+    let mut parser = Parser {
+        source,
+        position: 0,
+    };
+    let nodes = parser.parse_nodes(None)?;
+    Ok(nodes)
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> Error {
+        let consumed = &self.source[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count())
+            + 1;
+        Error {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    fn skip_past(&mut self, end: &str) -> Result<(), Error> {
+        match self.rest().find(end) {
+            Some(index) => {
+                self.position += index + end.len();
+                Ok(())
+            }
+            None => Err(self.error(format!("missing `{}`", end))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let end = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.position += end;
+        &rest[..end]
+    }
+
+    /// Parse nodes until the closing tag of `parent` or the end of the source.
+    fn parse_nodes(&mut self, parent: Option<&str>) -> Result<Vec<Node>, Error> {
+        let mut nodes = Vec::new();
+        loop {
+            let rest = self.rest();
+            if rest.is_empty() {
+                return match parent {
+                    Some(parent) => Err(self.error(format!("missing closing tag `</{}>`", parent))),
+                    None => Ok(nodes),
+                };
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if rest.starts_with("</") {
+                let start = self.position;
+                self.position += 2;
+                let name = self.take_while(is_name_char);
+                self.skip_whitespace();
+                if !self.rest().starts_with('>') {
+                    return Err(self.error("expected `>`"));
+                }
+                self.position += 1;
+                if Some(name) == parent {
+                    return Ok(nodes);
+                }
+                self.position = start;
+                return Err(match parent {
+                    Some(parent) => self.error(format!(
+                        "expected closing tag `</{}>`, found `</{}>`",
+                        parent, name
+                    )),
+                    None => self.error(format!("unexpected closing tag `</{}>`", name)),
+                });
+            } else if rest.starts_with('<') {
+                nodes.push(Node::Element(self.parse_element()?));
+            } else {
+                let text = self.take_while(|c| c != '<');
+                nodes.push(Node::Text(decode_entities(text)));
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<Element, Error> {
+        self.position += 1;
+        let name = self.take_while(is_name_char);
+        if name.is_empty() {
+            return Err(self.error("expected tag name"));
+        }
+
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.position += 2;
+                return Ok(Element {
+                    name: name.to_owned(),
+                    attributes,
+                    children: Vec::new(),
+                });
+            } else if rest.starts_with('>') {
+                self.position += 1;
+                break;
+            } else if rest.is_empty() {
+                return Err(self.error(format!("unclosed tag `<{}`", name)));
+            }
+            attributes.push(self.parse_attribute()?);
+        }
+
+        let children = if VOID_TAGS.contains(&name) {
+            Vec::new()
+        } else if RAW_TEXT_TAGS.contains(&name) {
+            let end = format!("</{}", name);
+            let content = match self.rest().find(&end) {
+                Some(index) => &self.rest()[..index],
+                None => return Err(self.error(format!("missing closing tag `</{}>`", name))),
+            };
+            self.position += content.len();
+            self.skip_past(">")?;
+            vec![Node::Text(content.to_owned())]
+        } else {
+            self.parse_nodes(Some(name))?
+        };
+
+        Ok(Element {
+            name: name.to_owned(),
+            attributes,
+            children,
+        })
+    }
+
+    fn parse_attribute(&mut self) -> Result<(String, Option<String>), Error> {
+        // `include_html!` placeholders used as whole attributes may contain whitespace
+        // - e.g. `<svg {{ attrs }}>`.
+        let name = if self.rest().starts_with("{{") {
+            match self.rest().find("}}") {
+                Some(end) => {
+                    let name = &self.rest()[..end + 2];
+                    self.position += name.len();
+                    name
+                }
+                None => return Err(self.error("missing `}}`")),
+            }
+        } else {
+            self.take_while(|c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/'))
+        };
+        if name.is_empty() {
+            return Err(self.error("expected attribute name"));
+        }
+        self.skip_whitespace();
+        if !self.rest().starts_with('=') {
+            return Ok((name.to_owned(), None));
+        }
+        self.position += 1;
+        self.skip_whitespace();
+
+        let value = match self.rest().chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                self.position += 1;
+                let value = self.take_while(|c| c != quote);
+                if self.rest().is_empty() {
+                    return Err(self.error("unclosed attribute value"));
+                }
+                self.position += 1;
+                value
+            }
+            _ => self.take_while(|c| !c.is_whitespace() && c != '>'),
+        };
+        Ok((name.to_owned(), Some(decode_entities(value))))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+/// Decode named XML entities, `&nbsp;` and numeric character references.
+/// Unknown references are kept as they are.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let reference = rest
+            .find(';')
+            .map(|end| &rest[1..end])
+            .and_then(|reference| decode_reference(reference).map(|c| (reference.len(), c)));
+        match reference {
+            Some((length, c)) => {
+                decoded.push(c);
+                rest = &rest[length + 2..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_reference(reference: &str) -> Option<char> {
+    match reference {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ if reference.starts_with("#x") || reference.starts_with("#X") => {
+            u32::from_str_radix(&reference[2..], 16)
+                .ok()
+                .and_then(std::char::from_u32)
+        }
+        _ if reference.starts_with('#') => {
+            reference[1..].parse().ok().and_then(std::char::from_u32)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(node: &Node) -> &Element {
+        match node {
+            Node::Element(element) => element,
+            Node::Text(text) => panic!("expected element, found text `{}`", text),
+        }
+    }
+
+    fn text(node: &Node) -> &str {
+        match node {
+            Node::Text(text) => text,
+            Node::Element(element) => panic!("expected text, found element `{}`", element.name),
+        }
+    }
+
+    fn error(source: &str) -> String {
+        match parse(source) {
+            Ok(_) => panic!("`{}` is valid", source),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_elements() {
+        let nodes = parse(concat!(
+            r#"<?xml version="1.0"?><!DOCTYPE svg><!-- comment -->"#,
+            r#"<svg viewBox='0 0 10 10' hidden data-x=1><br><path d="M0 0" /></svg>"#,
+            "<style>a > b { color: red }</style>text"
+        ))
+        .ok()
+        .unwrap();
+        assert_eq!(nodes.len(), 3);
+
+        let svg = element(&nodes[0]);
+        assert_eq!(svg.name, "svg");
+        assert_eq!(
+            svg.attributes,
+            vec![
+                ("viewBox".to_owned(), Some("0 0 10 10".to_owned())),
+                ("hidden".to_owned(), None),
+                ("data-x".to_owned(), Some("1".to_owned())),
+            ]
+        );
+        // `br` is void and `path` is self-closing.
+        assert_eq!(svg.children.len(), 2);
+        assert_eq!(element(&svg.children[0]).name, "br");
+        assert!(element(&svg.children[1]).children.is_empty());
+
+        let style = element(&nodes[1]);
+        assert_eq!(text(&style.children[0]), "a > b { color: red }");
+        assert_eq!(text(&nodes[2]), "text");
+    }
+
+    #[test]
+    fn parse_placeholder_attributes() {
+        let nodes = parse(r#"<svg {{ attrs }} {{parts}} title="{{ title }}"></svg>"#)
+            .ok()
+            .unwrap();
+        let names = element(&nodes[0])
+            .attributes
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["{{ attrs }}", "{{parts}}", "title"]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            error("<div>\n  <p></div>"),
+            "expected closing tag `</p>`, found `</div>` (line 2, column 6)"
+        );
+        assert_eq!(
+            error("<div>"),
+            "missing closing tag `</div>` (line 1, column 6)"
+        );
+        assert_eq!(
+            error("</div>"),
+            "unexpected closing tag `</div>` (line 1, column 1)"
+        );
+        assert_eq!(
+            error(r#"<p title="a></p>"#),
+            "unclosed attribute value (line 1, column 17)"
+        );
+        assert_eq!(error("<svg {{ attrs"), "missing `}}` (line 1, column 6)");
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            decode_entities("&lt;a&gt; &amp;&quot;&apos;&nbsp;&#65;&#x42;&#X43;"),
+            "<a> &\"'\u{a0}ABC"
+        );
+        // Unknown and invalid references are kept.
+        assert_eq!(decode_entities("&copy; & &#xZZ; &"), "&copy; & &#xZZ; &");
+    }
+}
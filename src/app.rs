@@ -1,10 +1,13 @@
-use crate::browser::dom::virtual_dom_bridge;
+use crate::browser::dom::{composition, virtual_dom_bridge};
 use crate::browser::{
     service::routing,
     util::{self, window, ClosureNew},
     Url, DUMMY_BASE_URL,
 };
-use crate::virtual_dom::{patch, El, EventHandlerManager, IntoNodes, Mailbox, Node, Tag};
+use crate::virtual_dom::{
+    event_handler_manager::event_delegator::EventDelegator, patch, A11yRules, El,
+    EventHandlerManager, IntoNodes, Mailbox, Node, Tag,
+};
 use enclose::{enc, enclose};
 use std::{
     any::Any,
@@ -23,8 +26,10 @@ pub mod data;
 mod effect;
 pub mod get_element;
 pub mod message_mapper;
+pub mod navigation_guard;
 pub mod orders;
 pub mod render_info;
+pub mod scroll_restoration;
 pub mod stream_manager;
 pub mod streams;
 pub mod sub_manager;
@@ -36,8 +41,12 @@ pub(crate) use data::AppData;
 use effect::Effect;
 pub use get_element::GetElement;
 pub use message_mapper::MessageMapper;
+pub use navigation_guard::{
+    NavigationDecision, NavigationGuardHandle, NavigationKind, NavigationRequest,
+};
 pub use orders::{Orders, OrdersContainer, OrdersProxy};
-pub use render_info::RenderInfo;
+pub use render_info::{PatchStats, RenderInfo};
+pub use scroll_restoration::ScrollRestoration;
 pub use stream_manager::StreamHandle;
 pub use sub_manager::{Notification, SubHandle};
 
@@ -148,6 +157,9 @@ where
         #[cfg(feature = "panic-hook")]
         console_error_panic_hook::set_once();
 
+        // Value patching skips elements with an active IME composition.
+        composition::track();
+
         let base_path: Rc<[String]> = Rc::from(
             util::document()
                 .query_selector("base")
@@ -184,6 +196,12 @@ where
                 scheduled_render_handle: RefCell::new(None),
                 after_next_render_callbacks: RefCell::new(Vec::new()),
                 render_info: Cell::new(None),
+                render_callbacks: RefCell::new(Vec::new()),
+                event_delegator: RefCell::new(None),
+                navigation_guards: Rc::default(),
+                scroll_restoration: Cell::new(ScrollRestoration::disabled()),
+                #[cfg(debug_assertions)]
+                a11y_rules: RefCell::new(None),
             }),
         };
 
@@ -197,22 +215,28 @@ where
         );
         app.data.model.replace(Some(new_model));
 
+        routing::init_history();
         routing::setup_popstate_listener(
             enc!((app => s) move |closure| {
                 s.data.popstate_closure.replace(Some(closure));
             }),
             enc!((app => s) move |notification| s.notify_with_notification(notification)),
+            enc!((app => s) move |scroll| s.scroll_after_next_render(scroll)),
             Rc::clone(&app.cfg.base_path),
+            Rc::clone(&app.data.navigation_guards),
         );
         routing::setup_link_listener(
             enc!((app => s) move |notification| s.notify_with_notification(notification)),
         );
 
         orders.subscribe(enc!((app => s) move |url_requested| {
+            let navigation_guards = Rc::clone(&s.data.navigation_guards);
             routing::url_request_handler(
                 url_requested,
                 Rc::clone(&s.cfg.base_path),
-                move |notification| s.notify_with_notification(notification),
+                &navigation_guards,
+                |scroll| s.scroll_after_next_render(scroll),
+                enc!((s) move |notification| s.notify_with_notification(notification)),
             )
         }));
 
@@ -248,6 +272,130 @@ where
         self.process_effect_queue(queue);
     }
 
+    /// Register the `callback` invoked after each render.
+    ///
+    /// It's useful for monitoring - e.g. to catch rendering regressions or to diagnose slow screens.
+    /// Use `Orders::after_next_render` when you need `RenderInfo` only once.
+    ///
+    /// _Note:_ The first render is done in `App::start`, so the callback isn't invoked for it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///App::start("app", init, update, view).on_render(|info| {
+    ///    if info.patch_duration > 16. {
+    ///        log!("Slow render", info.patch_duration, info.patch_stats);
+    ///    }
+    ///});
+    /// ```
+    pub fn on_render(&self, callback: impl Fn(RenderInfo) + 'static) -> &Self {
+        self.data
+            .render_callbacks
+            .borrow_mut()
+            .push(Box::new(callback));
+        self
+    }
+
+    /// Handle DOM events by one listener per event type attached to the mount point
+    /// instead of one listener per element and event type.
+    ///
+    /// It reduces the number of listeners and makes (re)rendering of big lists with event handlers
+    /// faster, because new elements only register their handlers in the app.
+    /// When an event is fired, the mount point's listener attaches a listener to the event target
+    /// and its ancestors with handlers before the event reaches them, so the handlers are called
+    /// in the same order, with the same `Event::current_target` and `stop_propagation` behavior
+    /// as listeners attached directly to elements.
+    ///
+    /// _Note:_ Only bubbling events with default `ListenerOptions` (e.g. `click`, `input` or `keydown`)
+    /// are delegated. Other handlers (e.g. `focus`, `scroll` or `passive` ones) are still attached
+    /// directly to elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///App::start("app", init, update, view).enable_event_delegation();
+    /// ```
+    pub fn enable_event_delegation(&self) -> &Self {
+        if self.data.event_delegator.borrow().is_some() {
+            return self;
+        }
+        let event_delegator = EventDelegator::new(self.cfg.mount_point.clone(), self.mailbox());
+        self.data.event_delegator.replace(Some(event_delegator));
+
+        // Move handlers of already rendered elements to the delegator.
+        let mailbox = self.mailbox();
+        if let Some(root_el) = self.data.root_el.borrow_mut().as_mut() {
+            for child in &mut root_el.children {
+                reattach_listeners(child, &mailbox);
+            }
+        }
+        self
+    }
+
+    /// Check the rendered virtual DOM for common accessibility problems after each render
+    /// and report them through `error!` - e.g. `img` without `alt` or duplicate `id`s.
+    ///
+    /// It works only in debug builds; it's a no-op in release builds.
+    /// See `A11yRule` for all rules.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///App::start("app", init, update, view)
+    ///    .enable_a11y_lint(A11yRules::default().disable(A11yRule::DuplicateId));
+    /// ```
+    #[allow(unused_variables)]
+    pub fn enable_a11y_lint(&self, rules: A11yRules) -> &Self {
+        #[cfg(debug_assertions)]
+        {
+            self.data.a11y_rules.replace(Some(rules));
+            // Check the already rendered view.
+            if let Some(root_el) = self.data.root_el.borrow().as_ref() {
+                self.lint_a11y(root_el);
+            }
+        }
+        self
+    }
+
+    /// Enable scrolling after navigation - scroll positions are restored on back / forward
+    /// and new navigations scroll to the url hash target or to the top.
+    /// It's disabled by default (like `ScrollRestoration::disabled()`) - the browser
+    /// scrolls by itself.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///App::start("app", init, update, view)
+    ///    .scroll_restoration(ScrollRestoration::default().scroll_to_top(false));
+    /// ```
+    pub fn scroll_restoration(&self, scroll_restoration: ScrollRestoration) -> &Self {
+        self.data.scroll_restoration.set(scroll_restoration);
+        routing::set_browser_scroll_restoration(scroll_restoration);
+        self
+    }
+
+    /// Scroll once the view reflects the new `Url`.
+    fn scroll_after_next_render(&self, scroll: routing::Scroll) {
+        let scroll_restoration = self.data.scroll_restoration.get();
+        self.data
+            .after_next_render_callbacks
+            .borrow_mut()
+            .push(Box::new(move |_| {
+                scroll.apply(scroll_restoration);
+                None
+            }));
+        self.schedule_render();
+    }
+
+    #[cfg(debug_assertions)]
+    fn lint_a11y(&self, root_el: &El<Ms>) {
+        if let Some(rules) = self.data.a11y_rules.borrow().as_ref() {
+            for issue in crate::virtual_dom::a11y::check(&root_el.children, rules) {
+                error!(issue.to_string());
+            }
+        }
+    }
+
     pub(crate) fn process_effect_queue(&self, mut queue: VecDeque<Effect<Ms>>) {
         if std::thread::panicking() {
             return;
@@ -336,12 +484,14 @@ where
             return;
         }
 
-        let new_render_timestamp = window().performance().expect("get `Performance`").now();
+        let performance = window().performance().expect("get `Performance`");
+        let new_render_timestamp = performance.now();
 
         // Create a new vdom: The top element, and all its children. Does not yet
         // have associated web_sys elements.
         let mut new = El::empty(Tag::Placeholder);
         new.children = (self.cfg.view)(self.data.model.borrow().as_ref().unwrap()).into_nodes();
+        let view_duration = performance.now() - new_render_timestamp;
 
         let old = self
             .data
@@ -350,6 +500,9 @@ where
             .take()
             .expect("missing root element");
 
+        // Discard stats recorded outside of the patch.
+        patch::stats::take();
+        let patch_start = performance.now();
         patch::patch_els(
             &self.cfg.document,
             &self.mailbox(),
@@ -358,25 +511,35 @@ where
             old.children.into_iter(),
             new.children.iter_mut(),
         );
+        let patch_duration = performance.now() - patch_start;
+        let patch_stats = patch::stats::take();
+
+        #[cfg(debug_assertions)]
+        self.lint_a11y(&new);
 
         // Now that we've re-rendered, replace our stored El with the new one;
         // it will be used as the old El next time.
         self.data.root_el.borrow_mut().replace(new);
 
-        // Execute `after_next_render_callbacks`.
-
-        let render_info = match self.data.render_info.take() {
-            Some(old_render_info) => RenderInfo {
-                timestamp: new_render_timestamp,
-                timestamp_delta: Some(new_render_timestamp - old_render_info.timestamp),
-            },
-            None => RenderInfo {
-                timestamp: new_render_timestamp,
-                timestamp_delta: None,
-            },
+        let render_info = RenderInfo {
+            timestamp: new_render_timestamp,
+            timestamp_delta: self
+                .data
+                .render_info
+                .take()
+                .map(|old_render_info| new_render_timestamp - old_render_info.timestamp),
+            view_duration,
+            patch_duration,
+            patch_stats,
         };
         self.data.render_info.set(Some(render_info));
 
+        for callback in self.data.render_callbacks.borrow().iter() {
+            callback(render_info);
+        }
+
+        // Execute `after_next_render_callbacks`.
+
         self.process_effect_queue(
             self.data
                 .after_next_render_callbacks
@@ -442,8 +605,27 @@ where
     }
 
     pub fn mailbox(&self) -> Mailbox<Ms> {
-        Mailbox::new(enclose!((self => s) move |option_message| {
+        let mailbox = Mailbox::new(enclose!((self => s) move |option_message| {
             s.update_with_option(option_message);
-        }))
+        }));
+        match self.data.event_delegator.borrow().as_ref() {
+            Some(event_delegator) => mailbox.with_event_delegator(event_delegator.clone()),
+            None => mailbox,
+        }
+    }
+}
+
+/// Recreate listeners of the `node` and its descendants with the given `mailbox`.
+fn reattach_listeners<Ms>(node: &mut Node<Ms>, mailbox: &Mailbox<Ms>) {
+    if let Node::Element(el) = node {
+        if let Some(node_ws) = el.node_ws.clone() {
+            // Cloned managers don't contain listeners - old listeners are detached on drop.
+            let mut event_handler_manager = el.event_handler_manager.clone();
+            event_handler_manager.attach_listeners(node_ws, None, mailbox);
+            el.event_handler_manager = event_handler_manager;
+        }
+        for child in &mut el.children {
+            reattach_listeners(child, mailbox);
+        }
     }
 }
@@ -1,7 +1,12 @@
-use super::{RenderInfo, SubManager};
+use super::{navigation_guard::NavigationGuards, RenderInfo, ScrollRestoration, SubManager};
 use crate::browser::util;
-use crate::virtual_dom::{El, EventHandlerManager};
-use std::cell::{Cell, RefCell};
+use crate::virtual_dom::{
+    event_handler_manager::event_delegator::EventDelegator, El, EventHandlerManager,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 use wasm_bindgen::closure::Closure;
 
 type StoredPopstate = RefCell<Option<Closure<dyn FnMut(web_sys::Event)>>>;
@@ -18,4 +23,10 @@ pub(crate) struct AppData<Ms: 'static, Mdl> {
     pub scheduled_render_handle: RefCell<Option<util::RequestAnimationFrameHandle>>,
     pub after_next_render_callbacks: RefCell<Vec<Box<dyn FnOnce(RenderInfo) -> Option<Ms>>>>,
     pub render_info: Cell<Option<RenderInfo>>,
+    pub render_callbacks: RefCell<Vec<Box<dyn Fn(RenderInfo)>>>,
+    pub event_delegator: RefCell<Option<EventDelegator<Ms>>>,
+    pub navigation_guards: Rc<NavigationGuards>,
+    pub scroll_restoration: Cell<ScrollRestoration>,
+    #[cfg(debug_assertions)]
+    pub a11y_rules: RefCell<Option<crate::virtual_dom::A11yRules>>,
 }
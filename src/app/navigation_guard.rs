@@ -0,0 +1,192 @@
+use crate::browser::{
+    util::{self, ClosureNew},
+    Url,
+};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+
+type Guard = dyn Fn(&NavigationRequest) -> NavigationDecision;
+type UnloadListener = Closure<dyn FnMut(web_sys::Event)>;
+
+// ------ NavigationKind ------
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NavigationKind {
+    /// In-app link click or `orders.request_url(url)`.
+    Link,
+    /// Browser back / forward buttons or `Url::go_back` / `Url::go_forward`.
+    PopState,
+    /// Tab close, page reload or navigation to another site.
+    Unload,
+}
+
+// ------ NavigationRequest ------
+
+#[derive(Debug, Clone)]
+pub struct NavigationRequest {
+    pub kind: NavigationKind,
+    /// The target `Url`. It's `None` for `NavigationKind::Unload`.
+    pub url: Option<Url>,
+}
+
+// ------ NavigationDecision ------
+
+#[derive(Debug, Clone)]
+pub enum NavigationDecision {
+    Allow,
+    /// Stay on the current page. The previous history entry is restored on back / forward.
+    Cancel,
+    /// Navigate to another `Url` instead.
+    /// Browsers don't allow redirects on `NavigationKind::Unload` - it's handled like `Cancel`.
+    Redirect(Url),
+}
+
+// ------ NavigationGuards ------
+
+/// Guards registered by `Orders::guard_navigation`.
+#[derive(Default)]
+pub(crate) struct NavigationGuards {
+    guards: RefCell<Vec<(usize, Rc<Guard>)>>,
+    next_id: Cell<usize>,
+    // The `beforeunload` listener is attached only when there is a guard,
+    // because it disables the back-forward cache in some browsers.
+    unload_listener: RefCell<Option<UnloadListener>>,
+}
+
+impl NavigationGuards {
+    pub(crate) fn add(guards: &Rc<Self>, guard: Rc<Guard>) -> NavigationGuardHandle {
+        let id = guards.next_id.get();
+        guards.next_id.set(id + 1);
+        guards.guards.borrow_mut().push((id, guard));
+
+        if guards.unload_listener.borrow().is_none() {
+            let weak_guards = Rc::downgrade(guards);
+            let listener: UnloadListener = Closure::new(move |event: web_sys::Event| {
+                let guards = match weak_guards.upgrade() {
+                    Some(guards) => guards,
+                    None => return,
+                };
+                let request = NavigationRequest {
+                    kind: NavigationKind::Unload,
+                    url: None,
+                };
+                if !matches!(guards.check(&request), NavigationDecision::Allow) {
+                    event.prevent_default();
+                    // Because of Chrome.
+                    event
+                        .unchecked_into::<web_sys::BeforeUnloadEvent>()
+                        .set_return_value("");
+                }
+            });
+            util::window()
+                .add_event_listener_with_callback("beforeunload", listener.as_ref().unchecked_ref())
+                .expect("add `beforeunload` listener");
+            guards.unload_listener.replace(Some(listener));
+        }
+
+        NavigationGuardHandle {
+            id,
+            guards: Rc::downgrade(guards),
+        }
+    }
+
+    fn remove(&self, id: usize) {
+        let mut guards = self.guards.borrow_mut();
+        guards.retain(|(guard_id, _)| *guard_id != id);
+        if guards.is_empty() {
+            if let Some(listener) = self.unload_listener.borrow_mut().take() {
+                util::window()
+                    .remove_event_listener_with_callback(
+                        "beforeunload",
+                        listener.as_ref().unchecked_ref(),
+                    )
+                    .expect("remove `beforeunload` listener");
+            }
+        }
+    }
+
+    /// Ask guards in the order of registration; the first decision other than `Allow` wins.
+    pub(crate) fn check(&self, request: &NavigationRequest) -> NavigationDecision {
+        // Guards are cloned so they can register or remove other guards.
+        let guards = self
+            .guards
+            .borrow()
+            .iter()
+            .map(|(_, guard)| Rc::clone(guard))
+            .collect::<Vec<_>>();
+        for guard in guards {
+            match guard(request) {
+                NavigationDecision::Allow => (),
+                decision => return decision,
+            }
+        }
+        NavigationDecision::Allow
+    }
+}
+
+// ------ NavigationGuardHandle ------
+
+/// The guard is removed on the handle drop.
+pub struct NavigationGuardHandle {
+    id: usize,
+    guards: Weak<NavigationGuards>,
+}
+
+impl fmt::Debug for NavigationGuardHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NavigationGuardHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl Drop for NavigationGuardHandle {
+    fn drop(&mut self) {
+        if let Some(guards) = self.guards.upgrade() {
+            guards.remove(self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn first_guard_decision_wins_until_handle_drop() {
+        let guards = Rc::new(NavigationGuards::default());
+        let request = NavigationRequest {
+            kind: NavigationKind::Link,
+            url: Some(Url::new().add_path_part("page")),
+        };
+
+        let allow = NavigationGuards::add(&guards, Rc::new(|_| NavigationDecision::Allow));
+        let redirect = NavigationGuards::add(
+            &guards,
+            Rc::new(|request| match request.kind {
+                NavigationKind::Link => NavigationDecision::Redirect(Url::new()),
+                _ => NavigationDecision::Allow,
+            }),
+        );
+        let cancel = NavigationGuards::add(&guards, Rc::new(|_| NavigationDecision::Cancel));
+        assert!(matches!(
+            guards.check(&request),
+            NavigationDecision::Redirect(_)
+        ));
+
+        drop(redirect);
+        assert!(matches!(guards.check(&request), NavigationDecision::Cancel));
+
+        drop(cancel);
+        drop(allow);
+        assert!(matches!(guards.check(&request), NavigationDecision::Allow));
+        assert!(guards.unload_listener.borrow().is_none());
+    }
+}
@@ -1,8 +1,18 @@
-use super::{subs, App, CmdHandle, RenderInfo, StreamHandle, SubHandle};
-use crate::browser::Url;
-use crate::virtual_dom::IntoNodes;
+use super::{
+    navigation_guard::NavigationGuards, subs, App, CmdHandle, NavigationDecision,
+    NavigationGuardHandle, NavigationRequest, RenderInfo, StreamHandle, SubHandle,
+};
+use crate::browser::{
+    dom::{
+        focus::{self, SavedFocus},
+        live_region,
+    },
+    Url,
+};
+use crate::virtual_dom::{ElRef, IntoNodes, Politeness};
 use futures::stream::Stream;
 use std::{any::Any, future::Future, rc::Rc};
+use wasm_bindgen::JsCast;
 
 // @TODO: Add links to doc comment once https://github.com/rust-lang/rust/issues/43466 is resolved
 // or use nightly rustdoc. Applicable to the entire code base.
@@ -174,8 +184,8 @@ pub trait Orders<Ms: 'static> {
 
     /// Register the callback that will be executed after the next render.
     ///
-    /// Callback's only parameter is `RenderInfo` - it has fields `timestamp`
-    /// and `timestamp_delta`.
+    /// Callback's only parameter is `RenderInfo` - it has fields `timestamp`,
+    /// `timestamp_delta`, `view_duration`, `patch_duration` and `patch_stats`.
     /// `timestamp_delta` is the difference between the old render timestamp and the new one
     /// and it has value `None` if it's the first rendering.
     ///
@@ -320,4 +330,109 @@ pub trait Orders<Ms: 'static> {
     fn request_url(&mut self, url: Url) -> &mut Self {
         self.notify(subs::UrlRequested::new(url))
     }
+
+    /// Hold navigation while the returned handle exists - e.g. to protect unsaved changes.
+    ///
+    /// The `guard` is called on in-app link navigation (`subs::UrlRequested`),
+    /// back / forward (`popstate`) and tab close or reload (`beforeunload`)
+    /// and decides whether to allow, cancel or redirect the navigation.
+    /// Canceled back / forward navigation restores the previous history entry.
+    ///
+    /// _Notes:_
+    /// - Browsers show their own confirmation dialog on `NavigationKind::Unload` when it's canceled.
+    /// - Direct `Url::go_and_push` / `Url::go_and_replace` calls aren't guarded.
+    /// - History entries not created by Seed (e.g. by `<a href="#section">`) can't be restored
+    ///   on canceled back / forward navigation - the app stays on the current page,
+    ///   but the browser keeps the entry and shows its URL.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///Msg::TextChanged(text) => {
+    ///    model.text = text;
+    ///    model.unsaved_changes_guard = Some(orders.guard_navigation(|request| {
+    ///        if request.kind == NavigationKind::Unload {
+    ///            return NavigationDecision::Cancel;
+    ///        }
+    ///        match window().confirm_with_message("Do you want to leave? Data won't be saved.") {
+    ///            Ok(true) => NavigationDecision::Allow,
+    ///            _ => NavigationDecision::Cancel,
+    ///        }
+    ///    }));
+    ///}
+    ///Msg::Save => {
+    ///    save(&model.text);
+    ///    model.unsaved_changes_guard = None;
+    ///}
+    /// ```
+    #[must_use = "navigation guard is removed on its handle drop"]
+    fn guard_navigation(
+        &mut self,
+        guard: impl Fn(&NavigationRequest) -> NavigationDecision + 'static,
+    ) -> NavigationGuardHandle {
+        NavigationGuards::add(&self.clone_app().data.navigation_guards, Rc::new(guard))
+    }
+
+    /// Focus the referenced element after the next render.
+    ///
+    /// Nothing happens when the element isn't rendered or it can't be focused.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///Msg::OpenDialog => {
+    ///    model.dialog_opened = true;
+    ///    model.saved_focus = orders.save_focus();
+    ///    orders.focus(&model.refs.dialog_input);
+    ///}
+    /// ```
+    fn focus<E: Clone + JsCast + 'static>(&mut self, el_ref: &ElRef<E>) -> &mut Self {
+        let el_ref = el_ref.map_type::<web_sys::HtmlElement>();
+        self.after_next_render(move |_| {
+            if let Some(element) = el_ref.get() {
+                focus::focus(&element);
+            }
+        })
+    }
+
+    /// Remember the currently focused element - e.g. before opening a modal dialog.
+    ///
+    /// See `Orders::restore_focus`.
+    fn save_focus(&self) -> SavedFocus {
+        SavedFocus::current()
+    }
+
+    /// Focus the element saved by `Orders::save_focus` after the next render.
+    ///
+    /// Nothing happens when the element has been removed in the meantime.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///Msg::CloseDialog => {
+    ///    model.dialog_opened = false;
+    ///    orders.restore_focus(mem::take(&mut model.saved_focus));
+    ///}
+    /// ```
+    fn restore_focus(&mut self, saved_focus: SavedFocus) -> &mut Self {
+        self.after_next_render(move |_| {
+            saved_focus.restore();
+        })
+    }
+
+    /// Announce the `text` to screen reader users - e.g. "Saved" or "3 results found".
+    ///
+    /// The text is rendered into a visually hidden live region
+    /// managed by Seed inside the mount point.
+    /// `Politeness::Assertive` interrupts the current speech - use it only for urgent messages.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///orders.announce("Item added to cart", Politeness::Polite);
+    /// ```
+    fn announce(&mut self, text: impl AsRef<str>, politeness: Politeness) -> &mut Self {
+        live_region::announce(&self.clone_app().cfg.mount_point, text.as_ref(), politeness);
+        self
+    }
 }
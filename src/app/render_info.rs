@@ -2,4 +2,29 @@
 pub struct RenderInfo {
     pub timestamp: f64,
     pub timestamp_delta: Option<f64>,
+    /// Time spent in the `view` function in milliseconds.
+    pub view_duration: f64,
+    /// Time spent in patching the DOM in milliseconds.
+    pub patch_duration: f64,
+    /// DOM changes made by the patch.
+    pub patch_stats: PatchStats,
+}
+
+/// DOM changes made by the VDOM patch.
+///
+/// _Note:_ A removed or a replaced element is counted once - its descendants are not counted.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PatchStats {
+    /// Created elements, including descendants of the created elements.
+    pub created: usize,
+    /// Removed elements, including replaced ones.
+    pub removed: usize,
+    /// Keyed elements moved to another position.
+    pub moved: usize,
+    /// Patched elements whose attributes or style have been changed.
+    pub updated: usize,
+    /// Set, changed or removed attributes of patched elements (`style` counts as one attribute).
+    pub attrs_changed: usize,
+    /// Attached DOM event listeners.
+    pub listeners_attached: usize,
 }
@@ -0,0 +1,59 @@
+// ------ ScrollRestoration ------
+
+/// Scrolling after navigation - see `App::scroll_restoration`.
+///
+/// `ScrollRestoration::default()` enables everything;
+/// apps without `App::scroll_restoration` use `ScrollRestoration::disabled()`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ScrollRestoration {
+    /// Save scroll positions per history entry and restore them after the render
+    /// following back / forward navigation. Default is `true`.
+    pub restore_on_back_forward: bool,
+    /// Scroll to the element with the id from the url hash (`/page#section`) after the render
+    /// following a new navigation (link click, `orders.request_url`). Default is `true`.
+    pub scroll_to_hash: bool,
+    /// Scroll to the top after the render following a new navigation
+    /// when there is no hash target. Default is `true`.
+    pub scroll_to_top: bool,
+}
+
+impl Default for ScrollRestoration {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollRestoration {
+    pub const fn new() -> Self {
+        Self {
+            restore_on_back_forward: true,
+            scroll_to_hash: true,
+            scroll_to_top: true,
+        }
+    }
+
+    /// Seed doesn't scroll; the browser restores scroll positions on back / forward by itself.
+    pub const fn disabled() -> Self {
+        Self {
+            restore_on_back_forward: false,
+            scroll_to_hash: false,
+            scroll_to_top: false,
+        }
+    }
+
+    pub const fn restore_on_back_forward(mut self, enabled: bool) -> Self {
+        self.restore_on_back_forward = enabled;
+        self
+    }
+
+    pub const fn scroll_to_hash(mut self, enabled: bool) -> Self {
+        self.scroll_to_hash = enabled;
+        self
+    }
+
+    pub const fn scroll_to_top(mut self, enabled: bool) -> Self {
+        self.scroll_to_top = enabled;
+        self
+    }
+}
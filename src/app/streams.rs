@@ -1,5 +1,7 @@
+use crate::browser::dom::hotkey::{Hotkey, HotkeyMatcher, HotkeyOptions};
 use crate::browser::util::{document, window};
-use crate::virtual_dom::Ev;
+use crate::virtual_dom::{Ev, ListenerOptions};
+use futures::future;
 use futures::stream::{Stream, StreamExt};
 use gloo_timers::future::IntervalStream;
 use web_sys::Event;
@@ -10,6 +12,9 @@ use event_stream::EventStream;
 mod backoff_stream;
 use backoff_stream::BackoffStream;
 
+mod rate_limited_stream;
+pub use rate_limited_stream::{RateLimitStreamExt, RateLimitedStream};
+
 // ------ Interval stream ------
 
 /// Stream no values on predefined time interval in milliseconds.
@@ -87,6 +92,33 @@ pub fn window_event<MsU>(
     EventStream::new(&window(), trigger.into()).map(move |event| handler.clone()(event))
 }
 
+/// Stream `Window` `web_sys::Event`s with custom listener options.
+///
+/// Handler has to return `Msg`, `Option<Msg>` or `()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///orders.stream(streams::window_event_with_options(
+///    Ev::Scroll,
+///    ListenerOptions::new().passive(),
+///    |_| Msg::OnScroll,
+///));
+/// ```
+///
+/// # Panics
+///
+/// Panics when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+pub fn window_event_with_options<MsU>(
+    trigger: impl Into<Ev>,
+    options: ListenerOptions,
+    handler: impl FnOnce(Event) -> MsU + Clone + 'static,
+) -> impl Stream<Item = MsU> {
+    EventStream::with_options(&window(), trigger.into(), options)
+        .map(move |event| handler.clone()(event))
+}
+
 // ------ Document Event stream ------
 
 /// Stream `Document` `web_sys::Event`s.
@@ -110,3 +142,93 @@ pub fn document_event<MsU>(
 ) -> impl Stream<Item = MsU> {
     EventStream::new(&document(), trigger.into()).map(move |event| handler.clone()(event))
 }
+
+/// Stream `Document` `web_sys::Event`s with custom listener options.
+///
+/// Handler has to return `Msg`, `Option<Msg>` or `()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///orders.stream(streams::document_event_with_options(
+///    Ev::Click,
+///    ListenerOptions::new().capture(),
+///    |_| Msg::OnClickAnywhere,
+///));
+/// ```
+///
+/// # Panics
+///
+/// Panics when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+pub fn document_event_with_options<MsU>(
+    trigger: impl Into<Ev>,
+    options: ListenerOptions,
+    handler: impl FnOnce(Event) -> MsU + Clone + 'static,
+) -> impl Stream<Item = MsU> {
+    EventStream::with_options(&document(), trigger.into(), options)
+        .map(move |event| handler.clone()(event))
+}
+
+// ------ Hotkey stream ------
+
+/// Stream `Window` `keydown` events matching the `hotkey` - e.g. `"mod+k"` or `"g i"`.
+///
+/// See `Hotkey` for the syntax. Events from text inputs are ignored
+/// and the default action of matched events is prevented - see `hotkey_with_options`.
+///
+/// Handler has to return `Msg`, `Option<Msg>` or `()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///orders.stream(streams::hotkey("mod+shift+k", || Msg::OpenPalette));
+///let go_to_inbox = orders.stream_with_handle(streams::hotkey("g i", || Msg::GoToInbox));
+/// ```
+///
+/// # Panics
+///
+/// Panics when the `hotkey` is invalid or when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+pub fn hotkey<MsU>(
+    hotkey: &str,
+    handler: impl FnOnce() -> MsU + Clone + 'static,
+) -> impl Stream<Item = MsU> {
+    hotkey_with_options(hotkey, HotkeyOptions::default(), handler)
+}
+
+/// Stream `Window` `keydown` events matching the `hotkey` with custom options.
+///
+/// Handler has to return `Msg`, `Option<Msg>` or `()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///orders.stream(streams::hotkey_with_options(
+///    "mod+s",
+///    HotkeyOptions::new().in_text_inputs(),
+///    || Msg::Save,
+///));
+/// ```
+///
+/// # Panics
+///
+/// Panics when the `hotkey` is invalid or when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+pub fn hotkey_with_options<MsU>(
+    hotkey: &str,
+    options: HotkeyOptions,
+    handler: impl FnOnce() -> MsU + Clone + 'static,
+) -> impl Stream<Item = MsU> {
+    let parsed_hotkey = hotkey
+        .parse::<Hotkey>()
+        .unwrap_or_else(|error| panic!("invalid hotkey '{}': {}", hotkey, error));
+    let mut matcher = HotkeyMatcher::new(parsed_hotkey, options);
+    EventStream::new(&window(), Ev::KeyDown).filter_map(move |event: Event| {
+        future::ready(if matcher.matches(&event) {
+            Some(handler.clone()())
+        } else {
+            None
+        })
+    })
+}
@@ -1,3 +1,4 @@
+use crate::virtual_dom::event_handler_manager::listener_options::{self, ListenerOptions};
 use crate::virtual_dom::Ev;
 use futures::channel::mpsc::{unbounded, UnboundedReceiver};
 use futures::stream::Stream;
@@ -19,6 +20,7 @@ use web_sys::EventTarget;
 pub struct EventStream<E> {
     node: EventTarget,
     trigger: Ev,
+    options: ListenerOptions,
     callback: Closure<dyn Fn(JsValue)>,
     receiver: UnboundedReceiver<E>,
 }
@@ -28,6 +30,14 @@ where
     E: JsCast + 'static,
 {
     pub fn new(node: &EventTarget, trigger: impl Into<Ev>) -> Self {
+        Self::with_options(node, trigger, ListenerOptions::default())
+    }
+
+    pub fn with_options(
+        node: &EventTarget,
+        trigger: impl Into<Ev>,
+        options: ListenerOptions,
+    ) -> Self {
         let trigger = trigger.into();
 
         let (sender, receiver) = unbounded();
@@ -37,12 +47,12 @@ where
             sender.unbounded_send(event.dyn_into().unwrap()).unwrap();
         }) as Box<dyn Fn(JsValue)>);
 
-        node.add_event_listener_with_callback(trigger.as_str(), callback.as_ref().unchecked_ref())
-            .unwrap();
+        listener_options::add_event_listener(node, &trigger, callback.as_ref(), options);
 
         Self {
             node: node.clone(),
             trigger,
+            options,
             callback,
             receiver,
         }
@@ -59,11 +69,11 @@ impl<E> Stream for EventStream<E> {
 
 impl<E> Drop for EventStream<E> {
     fn drop(&mut self) {
-        self.node
-            .remove_event_listener_with_callback(
-                self.trigger.as_str(),
-                self.callback.as_ref().unchecked_ref(),
-            )
-            .unwrap();
+        listener_options::remove_event_listener(
+            &self.node,
+            &self.trigger,
+            self.callback.as_ref(),
+            self.options,
+        );
     }
 }
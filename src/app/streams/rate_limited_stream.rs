@@ -0,0 +1,113 @@
+use crate::virtual_dom::event_handler_manager::rate_limit::{Limiter, RateLimit};
+use futures::future::FutureExt;
+use futures::stream::Stream;
+use gloo_timers::future::TimeoutFuture;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// ------ RateLimitStreamExt ------
+
+/// Rate limiting of streams - e.g. `streams::window_event(Ev::Resize, ..).debounce(200)`.
+///
+/// The scheduled item is dropped with the stream - i.e. when its `StreamHandle` is dropped.
+/// See `RateLimit` for more info.
+pub trait RateLimitStreamExt: Stream + Sized {
+    /// Set the rate limiting of the stream.
+    fn rate_limit(self, rate_limit: RateLimit) -> RateLimitedStream<Self> {
+        RateLimitedStream::new(self, rate_limit)
+    }
+
+    /// Pass the item once there are no new items for `ms` milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///orders.stream(streams::window_event(Ev::Resize, |_| Msg::Resized).debounce(200));
+    /// ```
+    fn debounce(self, ms: u32) -> RateLimitedStream<Self> {
+        self.rate_limit(RateLimit::debounce(ms))
+    }
+
+    /// Pass at most one item per `ms` milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///orders.stream(streams::document_event(Ev::SelectionChange, |_| Msg::SelectionChanged).throttle(100));
+    /// ```
+    fn throttle(self, ms: u32) -> RateLimitedStream<Self> {
+        self.rate_limit(RateLimit::throttle(ms))
+    }
+}
+
+impl<S: Stream> RateLimitStreamExt for S {}
+
+// ------ RateLimitedStream ------
+
+#[derive(Debug)]
+pub struct RateLimitedStream<S: Stream> {
+    stream: Option<S>,
+    limiter: Limiter<S::Item>,
+    timeout: Option<TimeoutFuture>,
+}
+
+impl<S: Stream> RateLimitedStream<S> {
+    fn new(stream: S, rate_limit: RateLimit) -> Self {
+        Self {
+            stream: Some(stream),
+            limiter: Limiter::new(rate_limit),
+            timeout: None,
+        }
+    }
+}
+
+// Items are never pinned.
+impl<S: Stream + Unpin> Unpin for RateLimitedStream<S> {}
+
+impl<S: Stream + Unpin> Stream for RateLimitedStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        while let Some(stream) = this.stream.as_mut() {
+            match Pin::new(stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let (item, start_timer) = this.limiter.push(item);
+                    if start_timer {
+                        this.timeout = Some(TimeoutFuture::new(this.limiter.ms()));
+                    }
+                    if item.is_some() {
+                        return Poll::Ready(item);
+                    }
+                }
+                Poll::Ready(None) => this.stream = None,
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(timeout) = this.timeout.as_mut() {
+            if timeout.poll_unpin(cx).is_pending() {
+                return Poll::Pending;
+            }
+            let (item, restart_timer) = this.limiter.timeout();
+            this.timeout = if restart_timer {
+                let mut timeout = TimeoutFuture::new(this.limiter.ms());
+                // Register the waker.
+                let _ = timeout.poll_unpin(cx);
+                Some(timeout)
+            } else {
+                None
+            };
+            if item.is_some() {
+                return Poll::Ready(item);
+            }
+        }
+
+        if this.stream.is_none() && this.timeout.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::browser::Url;
+use serde::de::DeserializeOwned;
 
 // ------ UrlRequested sub ------
 
@@ -18,3 +19,61 @@ pub use url_requested::UrlRequested;
 /// ```
 #[derive(Debug, Clone)]
 pub struct UrlChanged(pub Url);
+
+// ------ HistoryStateChanged sub ------
+
+/// Subscribe to back / forward navigation to history entries - it's notified after
+/// `subs::UrlChanged` with the state attached by `Url::go_and_push_with_state`
+/// or `Url::go_and_replace_with_state`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///Url::new().add_path_part("wizard").go_and_push_with_state(&model.step);
+///...
+///orders.subscribe(Msg::HistoryStateChanged);
+///...
+///update(... Msg::HistoryStateChanged(changed) =>
+///    if let Ok(Some(step)) = changed.deserialize::<Option<Step>>() {
+///        model.step = step;
+///    }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HistoryStateChanged {
+    pub url: Url,
+    /// `None` when the entry has no state - e.g. it was created by `Url::go_and_push`.
+    pub state: Option<serde_json::Value>,
+}
+
+impl HistoryStateChanged {
+    pub(crate) const fn new(url: Url, state: Option<serde_json::Value>) -> Self {
+        Self { url, state }
+    }
+
+    /// Deserialize the state - a missing state is deserialized from `null`,
+    /// so use `Option<T>` to handle entries without state.
+    ///
+    /// # Errors
+    ///
+    /// Returns error when the state doesn't match `T`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> serde_json::Result<T> {
+        let null = serde_json::Value::Null;
+        T::deserialize(self.state.as_ref().unwrap_or(&null))
+    }
+}
+
+// ------ LanguageChanged sub ------
+
+/// Subscribe to locale changes made by `I18n::set_locale`.
+/// _Note:_ Requires the `i18n` feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+///orders.subscribe(Msg::LanguageChanged);
+///...
+///update(... Msg::LanguageChanged(subs::LanguageChanged(locale)) =>
+/// ```
+#[cfg(feature = "i18n")]
+#[derive(Debug, Clone)]
+pub struct LanguageChanged(pub crate::i18n::LanguageIdentifier);
@@ -2,11 +2,19 @@
 //! These are the types used internally by our virtual dom.
 
 pub mod cast;
+pub(crate) mod composition;
 pub mod css_units;
 pub mod event_handler;
+pub mod focus;
+pub mod form_data;
+pub mod hotkey;
+pub(crate) mod live_region;
 pub mod namespace;
 pub mod virtual_dom_bridge;
 
+pub use focus::{focus_trap, SavedFocus};
+pub use form_data::{fill_form, FormValues};
+pub use hotkey::{Hotkey, HotkeyOptions, ParseHotkeyError};
 pub use namespace::Namespace;
 
 #[cfg(test)]
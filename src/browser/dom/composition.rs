@@ -0,0 +1,183 @@
+//! Tracking of IME composition (e.g. Chinese or Japanese input).
+//!
+//! Patching the value of an element with an active composition would interrupt the user -
+//! so `virtual_dom_bridge` skips such updates and the last skipped value is applied
+//! when the composition ends.
+
+use crate::browser::util::{self, ClosureNew};
+use gloo_timers::callback::Timeout;
+use std::cell::RefCell;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+struct Tracker {
+    composing_element: Option<web_sys::EventTarget>,
+    /// The element and its value from the vdom skipped during the composition.
+    skipped_value: Option<(web_sys::EventTarget, String)>,
+    _listeners: Vec<Closure<dyn FnMut(web_sys::Event)>>,
+}
+
+thread_local! {
+    static TRACKER: RefCell<Option<Tracker>> = RefCell::default();
+}
+
+/// Start tracking compositions in the document. Subsequent calls are no-ops.
+///
+/// Listeners are attached to `document` in the capture phase,
+/// so the handlers of elements can't hide compositions by stopping propagation.
+pub(crate) fn track() {
+    TRACKER.with(|tracker| {
+        let mut tracker = tracker.borrow_mut();
+        if tracker.is_some() {
+            return;
+        }
+        let document = util::document();
+        let listeners = vec![("compositionstart", true), ("compositionend", false)]
+            .into_iter()
+            .map(|(trigger, composing)| {
+                let closure = Closure::new(move |event: web_sys::Event| {
+                    if composing {
+                        set_composing_element(event.target());
+                    } else {
+                        set_composing_element(None);
+                        // Some browsers fire `input` with the composed text after
+                        // `compositionend` - let the app handle it first.
+                        Timeout::new(0, apply_skipped_value).forget();
+                    }
+                });
+                document
+                    .add_event_listener_with_callback_and_bool(
+                        trigger,
+                        closure.as_ref().unchecked_ref(),
+                        true,
+                    )
+                    .expect("add composition listener");
+                closure
+            })
+            .collect();
+
+        *tracker = Some(Tracker {
+            composing_element: None,
+            skipped_value: None,
+            _listeners: listeners,
+        });
+    });
+}
+
+/// Is there an active composition in the `target`?
+pub(crate) fn is_composing(target: &web_sys::EventTarget) -> bool {
+    TRACKER.with(|tracker| {
+        tracker
+            .borrow()
+            .iter()
+            .any(|tracker| tracker.composing_element.as_ref() == Some(target))
+    })
+}
+
+/// Remember the `value` not patched because of the composition in the `target`.
+pub(crate) fn skip_value(target: &web_sys::EventTarget, value: &str) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = tracker.borrow_mut().as_mut() {
+            tracker.skipped_value = Some((target.clone(), value.to_owned()));
+        }
+    });
+}
+
+/// Forget the skipped value of the `target` - a newer value has been patched.
+pub(crate) fn forget_skipped_value(target: &web_sys::EventTarget) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = tracker.borrow_mut().as_mut() {
+            if matches!(&tracker.skipped_value, Some((element, _)) if element == target) {
+                tracker.skipped_value = None;
+            }
+        }
+    });
+}
+
+/// Set the value skipped during the last composition unless another composition has started.
+fn apply_skipped_value() {
+    let skipped_value = TRACKER.with(|tracker| {
+        tracker.borrow_mut().as_mut().and_then(|tracker| {
+            if tracker.composing_element.is_some() {
+                None
+            } else {
+                tracker.skipped_value.take()
+            }
+        })
+    });
+    if let Some((element, value)) = skipped_value {
+        util::set_value(&element, &value).unwrap_or_else(|err| {
+            crate::error(err);
+        });
+    }
+}
+
+fn set_composing_element(element: Option<web_sys::EventTarget>) {
+    TRACKER.with(|tracker| {
+        if let Some(tracker) = tracker.borrow_mut().as_mut() {
+            tracker.composing_element = element;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gloo_timers::future::TimeoutFuture;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn track_composition() {
+        track();
+        let input: web_sys::EventTarget = util::document().create_element("input").unwrap().into();
+        util::body().append_child(input.unchecked_ref()).unwrap();
+
+        let dispatch = |trigger| {
+            let event = web_sys::Event::new(trigger).unwrap();
+            input.dispatch_event(&event).unwrap();
+        };
+
+        assert!(!is_composing(&input));
+        dispatch("compositionstart");
+        assert!(is_composing(&input));
+        dispatch("compositionend");
+        assert!(!is_composing(&input));
+
+        util::body().remove_child(input.unchecked_ref()).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    async fn apply_skipped_value_after_composition() {
+        track();
+        let input = util::document()
+            .create_element("input")
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlInputElement>();
+        util::body().append_child(&input).unwrap();
+        let dispatch = |trigger| {
+            let event = web_sys::Event::new(trigger).unwrap();
+            input.dispatch_event(&event).unwrap();
+        };
+
+        dispatch("compositionstart");
+        input.set_value("ab");
+        // E.g. the app uppercases the input.
+        skip_value(&input, "AB");
+        dispatch("compositionend");
+        assert_eq!(input.value(), "ab");
+        TimeoutFuture::new(10).await;
+        assert_eq!(input.value(), "AB");
+
+        // A newer value has been patched after the composition.
+        dispatch("compositionstart");
+        skip_value(&input, "ABC");
+        dispatch("compositionend");
+        forget_skipped_value(&input);
+        input.set_value("ABCD");
+        TimeoutFuture::new(10).await;
+        assert_eq!(input.value(), "ABCD");
+
+        util::body().remove_child(&input).unwrap();
+    }
+}
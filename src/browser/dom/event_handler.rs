@@ -2,6 +2,7 @@
 //! `web_sys::Event`
 
 use super::super::util;
+use super::hotkey::{self, Hotkey, HotkeyOptions};
 use crate::virtual_dom::{Ev, EventHandler};
 use std::rc::Rc;
 use wasm_bindgen::JsCast;
@@ -128,6 +129,66 @@ pub fn pointer_ev<Ms: 'static, MsU: 'static>(
     EventHandler::new(trigger, handler)
 }
 
+/// Create a `keydown` event handler invoked when the `hotkey` is pressed inside the element.
+///
+/// See `Hotkey` for the syntax and `streams::hotkey` for global hotkeys.
+/// Events from text inputs are ignored and the default action of matched events is prevented
+/// - see `hotkey_ev_with_options`.
+///
+/// Handler has to return `Msg`, `Option<Msg>` or `()`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///div![
+///    attrs!{At::TabIndex => 0},
+///    hotkey_ev("shift+enter", || Msg::Submit),
+///]
+/// ```
+///
+/// # Panics
+///
+/// Panics when the `hotkey` is invalid or when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+pub fn hotkey_ev<Ms: 'static, MsU: 'static>(
+    hotkey: &str,
+    handler: impl FnOnce() -> MsU + 'static + Clone,
+) -> EventHandler<Ms> {
+    hotkey_ev_with_options(hotkey, HotkeyOptions::default(), handler)
+}
+
+/// See `hotkey_ev`
+///
+/// # Panics
+///
+/// Panics when the `hotkey` is invalid or when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+/// (It will be changed to a compile-time error).
+#[allow(clippy::shadow_unrelated)]
+pub fn hotkey_ev_with_options<Ms: 'static, MsU: 'static>(
+    hotkey: &str,
+    options: HotkeyOptions,
+    handler: impl FnOnce() -> MsU + 'static + Clone,
+) -> EventHandler<Ms> {
+    let parsed_hotkey = hotkey
+        .parse::<Hotkey>()
+        .unwrap_or_else(|error| panic!("invalid hotkey '{}': {}", hotkey, error));
+    let handler = move |_: web_sys::Event| handler.clone()();
+    let handler = map_callback_return_to_option_ms!(
+        dyn Fn(web_sys::Event) -> Option<Ms>,
+        handler.clone(),
+        "Handler can return only Msg, Option<Msg> or ()!",
+        Rc
+    );
+    let hotkey = hotkey.to_owned();
+    EventHandler::new(Ev::KeyDown, move |event| {
+        if hotkey::matches_element_hotkey(&hotkey, &parsed_hotkey, options, &event) {
+            handler(event)
+        } else {
+            None
+        }
+    })
+}
+
 /// Create an event that accepts a closure, and passes a `web_sys::Event`, allowing full control of
 /// event-handling.
 #[deprecated(since = "0.6.0", note = "Use `ev` instead.")]
@@ -0,0 +1,159 @@
+//! Focus management - saving and restoring focus and focus traps for modal dialogs.
+//!
+//! See also `Orders::focus`, `Orders::save_focus` and `Orders::restore_focus`.
+
+use crate::browser::dom::event_handler::keyboard_ev;
+use crate::browser::util::document;
+use crate::virtual_dom::{AtValue, El, Ev, IntoNodes, Node, Tag};
+use wasm_bindgen::JsCast;
+
+/// Elements that can receive focus by Tab.
+const FOCUSABLE_SELECTOR: &str = "a[href], area[href], button:not([disabled]), \
+    input:not([disabled]):not([type=hidden]), select:not([disabled]), \
+    textarea:not([disabled]), iframe, [contenteditable], [tabindex]";
+
+// ------ SavedFocus ------
+
+/// The element that was focused when `Orders::save_focus` was called.
+///
+/// Pass it to `Orders::restore_focus` - e.g. when a modal dialog is closed,
+/// so the keyboard users continue from the button that opened it.
+#[derive(Debug, Clone, Default)]
+pub struct SavedFocus(Option<web_sys::HtmlElement>);
+
+impl SavedFocus {
+    /// Save the currently focused element.
+    pub fn current() -> Self {
+        Self(
+            document()
+                .active_element()
+                .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok()),
+        )
+    }
+
+    /// Focus the saved element if it's still a part of the document.
+    ///
+    /// Returns `false` when there is no element to focus.
+    pub fn restore(&self) -> bool {
+        match &self.0 {
+            Some(element) if document().contains(Some(element)) => {
+                focus(element);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+// ------ focus_trap ------
+
+/// Wrap `content` into a `div` that keeps Tab and Shift+Tab cycling inside it.
+///
+/// The trap is released once the wrapper is removed from the DOM.
+/// Combine it with `Orders::focus` to move focus into the trap when it's rendered.
+///
+/// # Example
+///
+/// ```rust,no_run
+///IF!(model.dialog_opened => focus_trap(div![
+///    C!["dialog"],
+///    attrs!{"role" => "dialog", "aria-modal" => "true"},
+///    input![el_ref(&model.dialog_input)],
+///    button!["Close", ev(Ev::Click, |_| Msg::CloseDialog)],
+///]))
+/// ```
+pub fn focus_trap<Ms: 'static>(content: impl IntoNodes<Ms>) -> Node<Ms> {
+    let mut el = El::empty(Tag::Div);
+    el.add_attr("data-focus-trap", AtValue::None);
+    for child in content.into_nodes() {
+        el.add_child(child);
+    }
+    el.add_event_handler(keyboard_ev(Ev::KeyDown, |event| {
+        if event.key() != "Tab" {
+            return;
+        }
+        let container = match event
+            .current_target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        {
+            Some(container) => container,
+            None => return,
+        };
+        let focusable_elements = focusable_elements(&container);
+        let (first, last) = match (focusable_elements.first(), focusable_elements.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => {
+                // There is nothing to cycle through - keep focus where it is.
+                event.prevent_default();
+                return;
+            }
+        };
+        let active_element = document().active_element();
+        let is_active = |element: &web_sys::HtmlElement| {
+            active_element.as_ref() == Some(element.unchecked_ref::<web_sys::Element>())
+        };
+        let is_inside = focusable_elements.iter().any(is_active);
+
+        if event.shift_key() && (!is_inside || is_active(first)) {
+            event.prevent_default();
+            focus(last);
+        } else if !event.shift_key() && (!is_inside || is_active(last)) {
+            event.prevent_default();
+            focus(first);
+        }
+    }));
+    Node::Element(el)
+}
+
+/// Visible descendants of the `container` that can receive focus by Tab, in the DOM order.
+fn focusable_elements(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    let nodes = container
+        .query_selector_all(FOCUSABLE_SELECTOR)
+        .expect("query focusable elements");
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index)?.dyn_into::<web_sys::HtmlElement>().ok())
+        .filter(|element| {
+            element.tab_index() >= 0
+                // Hidden elements (e.g. `display: none`) don't have `offsetParent`.
+                && element.offset_parent().is_some()
+        })
+        .collect()
+}
+
+pub(crate) fn focus(element: &web_sys::HtmlElement) {
+    element.focus().expect("focus element");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::util;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn saved_focus() {
+        let container = util::document().create_element("div").unwrap();
+        container.set_inner_html(r#"<button>A</button><button disabled>B</button><span>C</span>"#);
+        util::body().append_child(&container).unwrap();
+
+        let button = container
+            .first_element_child()
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlElement>();
+        focus(&button);
+        let saved_focus = SavedFocus::current();
+        button.blur().unwrap();
+
+        assert_eq!(focusable_elements(&container), vec![button.clone()]);
+        assert!(saved_focus.restore());
+        assert_eq!(
+            util::document().active_element(),
+            Some(button.unchecked_into())
+        );
+
+        util::body().remove_child(&container).unwrap();
+        assert!(!saved_focus.restore());
+    }
+}
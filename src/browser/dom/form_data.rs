@@ -0,0 +1,226 @@
+//! Reading values from form elements and filling form elements with values.
+
+use crate::browser::UrlSearch;
+use crate::form::{self, DeError, FieldValues, SerError};
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
+use wasm_bindgen::JsCast;
+
+// ------ FormValues ------
+
+/// Values of form controls read by the browser's
+/// [`FormData`](https://developer.mozilla.org/en-US/docs/Web/API/FormData) -
+/// i.e. only checked checkboxes and radios, all selected options of multi-selects, etc.
+///
+/// # Example
+///
+/// ```rust,no_run
+///let form = model.form.get().expect("form element");
+///let signup: Signup = FormValues::read(&form).deserialize()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FormValues {
+    values: FieldValues,
+    files: BTreeMap<String, Vec<web_sys::File>>,
+}
+
+impl FormValues {
+    /// Read values of the `form` controls.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the browser can't create `FormData` from the `form`.
+    pub fn read(form: &web_sys::HtmlFormElement) -> Self {
+        let form_data = web_sys::FormData::new_with_form(form).expect("create FormData from form");
+        let mut form_values = Self::default();
+
+        let entries = js_sys::try_iter(&form_data)
+            .expect("iterate FormData")
+            .expect("FormData is iterable");
+        for entry in entries {
+            let entry = js_sys::Array::from(&entry.expect("get FormData entry"));
+            let name = entry.get(0).as_string().unwrap_or_default();
+            let value = entry.get(1);
+            match value.dyn_into::<web_sys::File>() {
+                Ok(file) => {
+                    // Browsers send an empty file for file inputs without selected files.
+                    if !file.name().is_empty() {
+                        form_values.files.entry(name).or_default().push(file);
+                    }
+                }
+                Err(value) => form_values
+                    .values
+                    .entry(name)
+                    .or_default()
+                    .push(value.as_string().unwrap_or_default()),
+            }
+        }
+        form_values
+    }
+
+    /// Values of the control(s) with the given `name`.
+    pub fn get(&self, name: &str) -> Option<&Vec<String>> {
+        self.values.get(name)
+    }
+
+    /// All text values - files are available through `files`.
+    pub const fn values(&self) -> &FieldValues {
+        &self.values
+    }
+
+    /// Selected files of the file input(s) with the given `name`.
+    pub fn files(&self, name: &str) -> &[web_sys::File] {
+        self.files.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Text values as `UrlSearch` - e.g. for `GET` forms.
+    pub fn to_url_search(&self) -> UrlSearch {
+        UrlSearch::new(self.values.clone())
+    }
+
+    /// Deserialize text values into `T`. See `form::from_values` for supported types.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DeError` with the name of the failing field (if it's known).
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, DeError> {
+        form::from_values(&self.values)
+    }
+}
+
+// ------ fill_form ------
+
+/// Fill the `form` controls with values serialized from `value` - e.g. on edit screens.
+///
+/// - Checkboxes and radios are checked when their `value` is among the field values.
+///   Checkboxes are also checked by `true` - i.e. `bool` fields work with checkboxes without `value`.
+/// - Options of selects are selected when their `value` is among the field values.
+/// - Other controls with the same `name` get the field values in order.
+/// - File inputs and controls without field values are skipped.
+///
+/// _Note:_ `input` events aren't fired - update your model accordingly.
+///
+/// # Errors
+///
+/// Returns `SerError` when `value` can't be serialized by `form::to_values`.
+pub fn fill_form<T: Serialize>(form: &web_sys::HtmlFormElement, value: &T) -> Result<(), SerError> {
+    let values = form::to_values(value)?;
+    let mut next_value_indices = BTreeMap::<String, usize>::new();
+
+    let controls = form.elements();
+    for index in 0..controls.length() {
+        let control = match controls.item(index) {
+            Some(control) => control,
+            None => continue,
+        };
+        let name = control.get_attribute("name").unwrap_or_default();
+        let field_values = match values.get(&name) {
+            Some(field_values) => field_values,
+            None => continue,
+        };
+        let contains = |value: &str| field_values.iter().any(|field_value| field_value == value);
+
+        if let Some(input) = control.dyn_ref::<web_sys::HtmlInputElement>() {
+            match input.type_().as_str() {
+                "checkbox" => {
+                    input.set_checked(contains(&input.value()) || contains("true"));
+                }
+                "radio" => input.set_checked(contains(&input.value())),
+                "file" => (),
+                _ => input.set_value(next_value(&name, field_values, &mut next_value_indices)),
+            }
+        } else if let Some(select) = control.dyn_ref::<web_sys::HtmlSelectElement>() {
+            for option_index in 0..select.length() {
+                if let Some(option) = select
+                    .item(option_index)
+                    .and_then(|option| option.dyn_into::<web_sys::HtmlOptionElement>().ok())
+                {
+                    option.set_selected(contains(&option.value()));
+                }
+            }
+        } else if let Some(textarea) = control.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+            textarea.set_value(next_value(&name, field_values, &mut next_value_indices));
+        }
+    }
+    Ok(())
+}
+
+/// The field value for the next control with the given `name`.
+fn next_value<'a>(
+    name: &str,
+    field_values: &'a [String],
+    next_value_indices: &mut BTreeMap<String, usize>,
+) -> &'a str {
+    let index = next_value_indices.entry(name.to_owned()).or_default();
+    let value = field_values.get(*index).map_or("", String::as_str);
+    *index += 1;
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::util;
+    use serde::Deserialize;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Article {
+        title: String,
+        #[serde(default)]
+        published: bool,
+        tags: Vec<String>,
+        rating: Option<u8>,
+    }
+
+    fn form() -> web_sys::HtmlFormElement {
+        let form = util::document()
+            .create_element("form")
+            .unwrap()
+            .unchecked_into::<web_sys::HtmlFormElement>();
+        form.set_inner_html(
+            r#"
+            <input name="title">
+            <input name="published" type="checkbox">
+            <select name="tags" multiple>
+                <option value="rust">Rust</option>
+                <option value="wasm">Wasm</option>
+                <option value="js">JS</option>
+            </select>
+            <input name="rating" type="radio" value="1">
+            <input name="rating" type="radio" value="2">
+            <input name="attachment" type="file">
+            "#,
+        );
+        form
+    }
+
+    #[wasm_bindgen_test]
+    fn fill_and_read_form() {
+        let form = form();
+        let article = Article {
+            title: "Seed".to_owned(),
+            published: true,
+            tags: vec!["rust".to_owned(), "wasm".to_owned()],
+            rating: Some(2),
+        };
+        fill_form(&form, &article).unwrap();
+
+        let form_values = FormValues::read(&form);
+        assert_eq!(form_values.get("published"), Some(&vec!["on".to_owned()]));
+        assert!(form_values.files("attachment").is_empty());
+        assert_eq!(form_values.deserialize::<Article>(), Ok(article));
+    }
+
+    #[wasm_bindgen_test]
+    fn read_error_names_field() {
+        let form = form();
+        form.set_inner_html(r#"<input name="title" value="Seed"><input name="rating" value="x">"#);
+        let error = FormValues::read(&form)
+            .deserialize::<Article>()
+            .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("rating"));
+    }
+}
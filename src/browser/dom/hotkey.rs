@@ -0,0 +1,531 @@
+//! Keyboard shortcuts - see `streams::hotkey` and `hotkey_ev`.
+
+use crate::browser::util;
+use std::{cell::RefCell, fmt, str::FromStr};
+use wasm_bindgen::JsCast;
+
+// ------ Hotkey ------
+
+/// A keyboard shortcut - one chord (e.g. `"ctrl+shift+k"`)
+/// or a sequence of chords separated by spaces (e.g. `"g i"`).
+///
+/// Chords consist of modifiers and a key joined by `+`:
+/// - Modifiers: `ctrl`, `alt`, `shift`, `meta` and `mod`.
+///   `mod` is `meta` (`Cmd`) on macOS and iOS and `ctrl` elsewhere.
+///   Aliases `control`, `option`, `cmd`, `command`, `super` and `win` are supported as well.
+/// - Keys: [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key/Key_Values)
+///   values, case-insensitive (e.g. `k`, `enter`, `f1`, `/`).
+///   Aliases `esc`, `space`, `up`, `down`, `left`, `right`, `del`, `return` and `plus` are supported as well.
+///
+/// Modifiers have to match exactly - i.e. `"ctrl+k"` doesn't match `Ctrl+Shift+K`.
+/// The only exception are symbols without `shift` in the chord - e.g. `"?"` matches `Shift+/`.
+/// Letters and digits are matched also by the physical key so `"alt+k"` works on macOS,
+/// where `Option+K` produces `˚`.
+///
+/// # Example
+///
+/// ```rust,no_run
+///let hotkey: Hotkey = "mod+shift+k".parse()?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkey {
+    chords: Vec<Chord>,
+}
+
+impl Hotkey {
+    /// Parse `hotkey` with `mod` resolved for the given platform.
+    fn parse(hotkey: &str, mac_os: bool) -> Result<Self, ParseHotkeyError> {
+        let chords = hotkey
+            .split_whitespace()
+            .map(|chord| Chord::parse(chord, mac_os))
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(ParseHotkeyError::Empty);
+        }
+        Ok(Self { chords })
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = ParseHotkeyError;
+
+    fn from_str(hotkey: &str) -> Result<Self, Self::Err> {
+        Self::parse(hotkey, is_mac_os())
+    }
+}
+
+// ------ ParseHotkeyError ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHotkeyError {
+    /// The hotkey doesn't contain any chord.
+    Empty,
+    /// The chord doesn't contain a key - e.g. `"ctrl+"`.
+    MissingKey(String),
+    /// The chord contains an unknown modifier - e.g. `"hyper+k"`.
+    UnknownModifier(String),
+}
+
+impl fmt::Display for ParseHotkeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "hotkey is empty"),
+            Self::MissingKey(chord) => write!(f, "chord '{}' doesn't contain a key", chord),
+            Self::UnknownModifier(modifier) => write!(f, "unknown modifier '{}'", modifier),
+        }
+    }
+}
+
+impl std::error::Error for ParseHotkeyError {}
+
+// ------ HotkeyOptions ------
+
+/// Options of `streams::hotkey_with_options` and `hotkey_ev_with_options`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HotkeyOptions {
+    /// Match also events from text inputs, text areas, selects and editable elements.
+    /// Default is `false`.
+    pub in_text_inputs: bool,
+    /// Call `prevent_default` on the matched event - e.g. to prevent `Ctrl+S` from saving the page.
+    /// Default is `true`.
+    pub prevent_default: bool,
+    /// Max delay between chords of a sequence in milliseconds. Default is `1000`.
+    pub sequence_timeout: u32,
+}
+
+impl Default for HotkeyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HotkeyOptions {
+    pub const fn new() -> Self {
+        Self {
+            in_text_inputs: false,
+            prevent_default: true,
+            sequence_timeout: 1000,
+        }
+    }
+
+    pub const fn in_text_inputs(mut self) -> Self {
+        self.in_text_inputs = true;
+        self
+    }
+
+    pub const fn allow_default(mut self) -> Self {
+        self.prevent_default = false;
+        self
+    }
+
+    pub const fn sequence_timeout(mut self, sequence_timeout: u32) -> Self {
+        self.sequence_timeout = sequence_timeout;
+        self
+    }
+}
+
+// ------ Chord ------
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct Chord {
+    key: String,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+}
+
+impl Chord {
+    fn parse(chord: &str, mac_os: bool) -> Result<Self, ParseHotkeyError> {
+        let chord = chord.to_lowercase();
+        // The key `+` - e.g. `"+"` or `"ctrl++"`.
+        let (modifiers, key) = if chord == "+" {
+            ("", "+")
+        } else if let Some(modifiers) = chord.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            match chord.rfind('+') {
+                Some(index) => (&chord[..index], &chord[index + 1..]),
+                None => ("", chord.as_str()),
+            }
+        };
+        if key.is_empty() {
+            return Err(ParseHotkeyError::MissingKey(chord.clone()));
+        }
+
+        let mut parsed_chord = Self {
+            key: normalize_key(key),
+            ..Self::default()
+        };
+        for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+            match modifier {
+                "ctrl" | "control" => parsed_chord.ctrl = true,
+                "alt" | "option" => parsed_chord.alt = true,
+                "shift" => parsed_chord.shift = true,
+                "meta" | "cmd" | "command" | "super" | "win" => parsed_chord.meta = true,
+                "mod" if mac_os => parsed_chord.meta = true,
+                "mod" => parsed_chord.ctrl = true,
+                _ => return Err(ParseHotkeyError::UnknownModifier(modifier.to_owned())),
+            }
+        }
+        Ok(parsed_chord)
+    }
+
+    fn matches(&self, event: &web_sys::KeyboardEvent) -> bool {
+        self.matches_parts(
+            &event.key(),
+            &event.code(),
+            [
+                event.ctrl_key(),
+                event.alt_key(),
+                event.shift_key(),
+                event.meta_key(),
+            ],
+        )
+    }
+
+    /// `modifiers`: `[ctrl, alt, shift, meta]`
+    fn matches_parts(&self, key: &str, code: &str, modifiers: [bool; 4]) -> bool {
+        let [ctrl, alt, mut shift, meta] = modifiers;
+        // Symbols are often typed with `Shift` - e.g. `?` on US keyboards.
+        if !self.shift && self.is_symbol() {
+            shift = false;
+        }
+        if [ctrl, alt, shift, meta] != [self.ctrl, self.alt, self.shift, self.meta] {
+            return false;
+        }
+        if key.to_lowercase() == self.key {
+            return true;
+        }
+        // Physical key - e.g. `KeyK` or `Digit1`.
+        let mut chars = self.key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(character), None) if character.is_ascii_alphabetic() => {
+                code.strip_prefix("Key").map(str::to_lowercase).as_deref()
+                    == Some(self.key.as_str())
+            }
+            (Some(character), None) if character.is_ascii_digit() => {
+                code.strip_prefix("Digit") == Some(self.key.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    fn is_symbol(&self) -> bool {
+        let mut chars = self.key.chars();
+        matches!(
+            (chars.next(), chars.next()),
+            (Some(character), None) if !character.is_alphanumeric() && character != ' '
+        )
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    match key {
+        "esc" => "escape",
+        "space" => " ",
+        "up" => "arrowup",
+        "down" => "arrowdown",
+        "left" => "arrowleft",
+        "right" => "arrowright",
+        "del" => "delete",
+        "return" => "enter",
+        "plus" => "+",
+        key => key,
+    }
+    .to_owned()
+}
+
+// ------ HotkeyMatcher ------
+
+/// Matches `keydown` events against the hotkey and tracks the progress of sequences.
+#[derive(Debug, Clone)]
+pub(crate) struct HotkeyMatcher {
+    hotkey: Hotkey,
+    options: HotkeyOptions,
+    // The number of already matched chords of the sequence.
+    progress: usize,
+    // `time_stamp` of the last matched chord.
+    last_match: f64,
+}
+
+impl HotkeyMatcher {
+    pub fn new(hotkey: Hotkey, options: HotkeyOptions) -> Self {
+        Self {
+            hotkey,
+            options,
+            progress: 0,
+            last_match: 0.,
+        }
+    }
+
+    /// Returns `true` when the last chord of the hotkey has been matched.
+    pub fn matches(&mut self, event: &web_sys::Event) -> bool {
+        let event = match event.dyn_ref::<web_sys::KeyboardEvent>() {
+            Some(event) => event,
+            None => return false,
+        };
+        if is_modifier(&event.key()) || event.repeat() && self.hotkey.chords.len() > 1 {
+            return false;
+        }
+        if !self.options.in_text_inputs && event.target().iter().any(is_text_input) {
+            return false;
+        }
+
+        let time_stamp = event.time_stamp();
+        if time_stamp - self.last_match > f64::from(self.options.sequence_timeout) {
+            self.progress = 0;
+        }
+        let chords = &self.hotkey.chords;
+        self.progress = if chords[self.progress].matches(event) {
+            self.progress + 1
+        } else if chords[0].matches(event) {
+            1
+        } else {
+            0
+        };
+        self.last_match = time_stamp;
+
+        if self.progress < chords.len() {
+            // Swallow the chords of a started sequence (e.g. `g` in `"g i"`) only
+            // when the whole sequence has been matched.
+            return false;
+        }
+        self.progress = 0;
+        if self.options.prevent_default {
+            event.prevent_default();
+        }
+        true
+    }
+}
+
+// ------ Element matchers ------
+
+/// Matcher of one `hotkey_ev` registration.
+struct ElementMatcher {
+    element: web_sys::EventTarget,
+    hotkey: String,
+    options: HotkeyOptions,
+    matcher: HotkeyMatcher,
+}
+
+thread_local! {
+    /// Element hotkey matchers - `hotkey_ev` handlers are recreated on each render,
+    /// so the progress of sequences has to be stored outside of them.
+    static ELEMENT_MATCHERS: RefCell<Vec<ElementMatcher>> = RefCell::default();
+}
+
+/// Match the `event` by the matcher for the element with the handler, `hotkey` and `options`.
+pub(crate) fn matches_element_hotkey(
+    hotkey: &str,
+    parsed_hotkey: &Hotkey,
+    options: HotkeyOptions,
+    event: &web_sys::Event,
+) -> bool {
+    let element = match event.current_target() {
+        Some(element) => element,
+        None => return false,
+    };
+    ELEMENT_MATCHERS.with(|matchers| {
+        let mut matchers = matchers.borrow_mut();
+        let index = matchers.iter().position(|matcher| {
+            matcher.element == element && matcher.hotkey == hotkey && matcher.options == options
+        });
+        let index = index.unwrap_or_else(|| {
+            // Forget matchers of removed elements.
+            matchers.retain(|matcher| match matcher.element.dyn_ref::<web_sys::Node>() {
+                Some(node) => node.is_connected(),
+                None => true,
+            });
+            matchers.push(ElementMatcher {
+                element,
+                hotkey: hotkey.to_owned(),
+                options,
+                matcher: HotkeyMatcher::new(parsed_hotkey.clone(), options),
+            });
+            matchers.len() - 1
+        });
+        matchers[index].matcher.matches(event)
+    })
+}
+
+fn is_modifier(key: &str) -> bool {
+    matches!(
+        key,
+        "Control" | "Alt" | "AltGraph" | "Shift" | "Meta" | "OS" | "CapsLock"
+    )
+}
+
+/// Is the `target` an element where the user types text?
+fn is_text_input(target: &web_sys::EventTarget) -> bool {
+    if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+        return !matches!(
+            input.type_().as_str(),
+            "button"
+                | "checkbox"
+                | "color"
+                | "file"
+                | "image"
+                | "radio"
+                | "range"
+                | "reset"
+                | "submit"
+        );
+    }
+    if target.is_instance_of::<web_sys::HtmlTextAreaElement>()
+        || target.is_instance_of::<web_sys::HtmlSelectElement>()
+    {
+        return true;
+    }
+    target
+        .dyn_ref::<web_sys::HtmlElement>()
+        .iter()
+        .any(|element| element.is_content_editable())
+}
+
+/// Is the app running on macOS or iOS - i.e. is `mod` the `Cmd` key?
+fn is_mac_os() -> bool {
+    util::window()
+        .navigator()
+        .platform()
+        .iter()
+        .any(|platform| {
+            ["Mac", "iPhone", "iPad", "iPod"]
+                .iter()
+                .any(|prefix| platform.starts_with(prefix))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::util::ClosureNew;
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn parse_chords() {
+        assert_eq!(
+            Hotkey::parse("Ctrl+Shift+K", false),
+            Ok(Hotkey {
+                chords: vec![Chord {
+                    key: "k".to_owned(),
+                    ctrl: true,
+                    shift: true,
+                    ..Chord::default()
+                }]
+            })
+        );
+        assert_eq!(
+            Hotkey::parse("mod+plus", true).unwrap().chords,
+            vec![Chord {
+                key: "+".to_owned(),
+                meta: true,
+                ..Chord::default()
+            }]
+        );
+        assert_eq!(
+            Hotkey::parse("mod++", false).unwrap().chords,
+            vec![Chord {
+                key: "+".to_owned(),
+                ctrl: true,
+                ..Chord::default()
+            }]
+        );
+        assert_eq!(Hotkey::parse("g i", false).unwrap().chords.len(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn parse_errors() {
+        assert_eq!(Hotkey::parse(" ", false), Err(ParseHotkeyError::Empty));
+        assert_eq!(
+            Hotkey::parse("ctrl+", false),
+            Err(ParseHotkeyError::MissingKey("ctrl+".to_owned()))
+        );
+        assert_eq!(
+            Hotkey::parse("hyper+k", false),
+            Err(ParseHotkeyError::UnknownModifier("hyper".to_owned()))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn match_chords() {
+        let chord = Chord::parse("alt+k", false).unwrap();
+        assert!(chord.matches_parts("k", "KeyK", [false, true, false, false]));
+        assert!(chord.matches_parts("˚", "KeyK", [false, true, false, false]));
+        assert!(!chord.matches_parts("k", "KeyK", [false, true, true, false]));
+
+        let chord = Chord::parse("?", false).unwrap();
+        assert!(chord.matches_parts("?", "Slash", [false, false, true, false]));
+
+        let chord = Chord::parse("esc", false).unwrap();
+        assert!(chord.matches_parts("Escape", "Escape", [false; 4]));
+    }
+
+    /// Dispatch `keydown` with `key` on `element` handled by an element hotkey matcher.
+    /// Returns `(matched, default_prevented)`.
+    fn dispatch_key(
+        element: &web_sys::Element,
+        hotkey: &'static str,
+        options: HotkeyOptions,
+        key: &str,
+    ) -> (bool, bool) {
+        let matched = Rc::new(Cell::new(false));
+        let matched_in_listener = Rc::clone(&matched);
+        let listener: Closure<dyn FnMut(web_sys::Event)> =
+            Closure::new(move |event: web_sys::Event| {
+                let parsed_hotkey = hotkey.parse::<Hotkey>().unwrap();
+                matched_in_listener.set(matches_element_hotkey(
+                    hotkey,
+                    &parsed_hotkey,
+                    options,
+                    &event,
+                ));
+            });
+        element
+            .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+            .unwrap();
+
+        let mut init = web_sys::KeyboardEventInit::new();
+        init.key(key).cancelable(true);
+        let event =
+            web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap();
+        element.dispatch_event(&event).unwrap();
+
+        element
+            .remove_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+            .unwrap();
+        (matched.get(), event.default_prevented())
+    }
+
+    #[wasm_bindgen_test]
+    fn element_matchers_per_element_and_options() {
+        let document = util::document();
+        let body = document.body().unwrap();
+        let first = document.create_element("div").unwrap();
+        let second = document.create_element("div").unwrap();
+        body.append_child(&first).unwrap();
+        body.append_child(&second).unwrap();
+
+        // Sequence progress isn't shared between elements.
+        let options = HotkeyOptions::new();
+        assert_eq!(dispatch_key(&first, "g i", options, "g"), (false, false));
+        assert_eq!(dispatch_key(&second, "g i", options, "i"), (false, false));
+        assert_eq!(dispatch_key(&first, "g i", options, "i"), (true, true));
+
+        // Options of the first registration aren't reused.
+        let allow_default = HotkeyOptions::new().allow_default();
+        assert_eq!(
+            dispatch_key(&second, "k", allow_default, "k"),
+            (true, false)
+        );
+        assert_eq!(dispatch_key(&second, "k", options, "k"), (true, true));
+
+        body.remove_child(&first).unwrap();
+        body.remove_child(&second).unwrap();
+    }
+}
@@ -0,0 +1,91 @@
+//! Visually hidden live regions for screen reader announcements - see `Orders::announce`.
+
+use crate::browser::util::document;
+use crate::virtual_dom::aria::Politeness;
+use gloo_timers::callback::Timeout;
+
+/// Hides the region visually, but keeps it accessible for screen readers.
+const VISUALLY_HIDDEN_STYLE: &str = "position: absolute; width: 1px; height: 1px; \
+    margin: -1px; padding: 0; border: 0; overflow: hidden; clip: rect(0, 0, 0, 0); \
+    white-space: nowrap;";
+
+/// Delay between clearing the region and setting the new text.
+/// Screen readers don't announce a text again if the region content doesn't change.
+const ANNOUNCE_DELAY_MS: u32 = 100;
+
+/// Announce the `text` through the live region with the given `politeness`.
+/// The region is created on the first use and inserted right after the `mount_point`
+/// - the content of the mount point is owned by the virtual DOM.
+///
+/// `Politeness::Off` is ignored.
+pub(crate) fn announce(mount_point: &web_sys::Element, text: &str, politeness: Politeness) {
+    if politeness == Politeness::Off {
+        return;
+    }
+    let region = live_region(mount_point, politeness);
+    region.set_text_content(None);
+
+    let text = text.to_owned();
+    Timeout::new(ANNOUNCE_DELAY_MS, move || {
+        region.set_text_content(Some(&text));
+    })
+    .forget();
+}
+
+fn live_region(mount_point: &web_sys::Element, politeness: Politeness) -> web_sys::Element {
+    // Live regions are the siblings following the mount point.
+    let mut sibling = mount_point.next_element_sibling();
+    while let Some(element) = sibling {
+        match element.get_attribute("data-seed-live-region") {
+            Some(value) if value == politeness.as_str() => return element,
+            Some(_) => sibling = element.next_element_sibling(),
+            None => break,
+        }
+    }
+
+    let region = document()
+        .create_element("div")
+        .expect("create live region");
+    for (name, value) in &[
+        ("data-seed-live-region", politeness.as_str()),
+        ("aria-live", politeness.as_str()),
+        ("aria-atomic", "true"),
+        ("style", VISUALLY_HIDDEN_STYLE),
+    ] {
+        region
+            .set_attribute(name, value)
+            .expect("set live region attribute");
+    }
+    mount_point
+        .after_with_node_1(&region)
+        .expect("insert live region");
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::util;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn reuse_live_region() {
+        let parent = util::document().create_element("div").unwrap();
+        let mount_point = util::document().create_element("div").unwrap();
+        parent.append_child(&mount_point).unwrap();
+        announce(&mount_point, "Saved", Politeness::Polite);
+        announce(&mount_point, "Saved again", Politeness::Polite);
+        announce(&mount_point, "Failed", Politeness::Assertive);
+        announce(&mount_point, "Ignored", Politeness::Off);
+
+        // The mount point is left untouched.
+        assert_eq!(mount_point.child_element_count(), 0);
+        assert_eq!(parent.child_element_count(), 3);
+        let region = live_region(&mount_point, Politeness::Polite);
+        assert_eq!(region.get_attribute("aria-live").as_deref(), Some("polite"));
+        // The text is set after `ANNOUNCE_DELAY_MS`.
+        assert_eq!(region.text_content().as_deref(), Some(""));
+    }
+}
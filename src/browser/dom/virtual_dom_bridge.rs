@@ -1,7 +1,7 @@
 //! This file contains interactions with `web_sys`.
 
-use super::Namespace;
-use crate::virtual_dom::{At, AtValue, Attrs, El, Mailbox, Node, Style, Text};
+use super::{composition, Namespace};
+use crate::virtual_dom::{patch, At, AtValue, Attrs, El, Mailbox, Node, Style, Text};
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use wasm_bindgen::JsCast;
@@ -99,6 +99,7 @@ fn set_attr_value(el_ws: &web_sys::Node, at: &At, at_value: &AtValue) {
 /// * [MDN docs](https://developer.mozilla.org/en-US/docs/Web/HTML/Element\)
 /// * See also: [`web_sys` Node](https://rustwasm.github.io/wasm-bindgen/api/web_sys/struct.Node.html)
 pub(crate) fn make_websys_el<Ms>(el: &mut El<Ms>, document: &web_sys::Document) -> web_sys::Node {
+    patch::stats::record(|stats| stats.created += 1);
     let tag = el.tag.as_str();
 
     let el_ws = match el.namespace {
@@ -235,26 +236,40 @@ pub(crate) fn patch_el_details<Ms>(
 ) {
     fix_attrs_order(&mut new.attrs);
 
+    let mut attrs_changed = 0;
+
     for (key, new_val) in &new.attrs.vals {
         match old.attrs.vals.get(key) {
             Some(old_val) => {
                 // The value's different
                 if old_val != new_val {
                     set_attr_value(old_el_ws, key, new_val);
+                    attrs_changed += 1;
                 }
             }
             None => {
                 set_attr_value(old_el_ws, key, new_val);
+                attrs_changed += 1;
             }
         }
 
         // We handle value in the vdom using attributes, but the DOM needs
         // to use set_value or set_checked.
         match key {
-            At::Value => match new_val {
-                AtValue::Some(new_val) => crate::util::set_value(old_el_ws, new_val),
-                AtValue::None | AtValue::Ignored => crate::util::set_value(old_el_ws, ""),
-            },
+            At::Value => {
+                let new_val = match new_val {
+                    AtValue::Some(new_val) => new_val.as_str(),
+                    AtValue::None | AtValue::Ignored => "",
+                };
+                if composition::is_composing(old_el_ws) {
+                    // Don't interrupt IME composition - the value is set when it ends.
+                    composition::skip_value(old_el_ws, new_val);
+                    Ok(())
+                } else {
+                    composition::forget_skipped_value(old_el_ws);
+                    crate::util::set_value(old_el_ws, new_val)
+                }
+            }
             At::Checked => match new_val {
                 AtValue::Some(_) | AtValue::None => crate::util::set_checked(old_el_ws, true),
                 AtValue::Ignored => crate::util::set_checked(old_el_ws, false),
@@ -273,6 +288,7 @@ pub(crate) fn patch_el_details<Ms>(
                 Some(el) => {
                     el.remove_attribute(key.as_str())
                         .expect("Removing an attribute");
+                    attrs_changed += 1;
 
                     // We handle value in the vdom using attributes, but the DOM needs
                     // to use set_value or set_checked.
@@ -310,7 +326,15 @@ pub(crate) fn patch_el_details<Ms>(
     // Patch style.
     if old.style != new.style {
         // We can't patch each part of style; rewrite the whole attribute.
-        set_style(old_el_ws, &new.style)
+        set_style(old_el_ws, &new.style);
+        attrs_changed += 1;
+    }
+
+    if attrs_changed > 0 {
+        patch::stats::record(|stats| {
+            stats.updated += 1;
+            stats.attrs_changed += attrs_changed;
+        });
     }
 }
 
@@ -0,0 +1,437 @@
+//! Locale-aware formatting with the browser's
+//! [Intl API](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl).
+//!
+//! Numbers, currencies, percentages, dates, times, relative time and lists are formatted
+//! by cached native formatters.
+//!
+//! Outside of the browser (native tests, server-side rendering) a pure-Rust fallback
+//! formats values like the `en-US` locale with times in UTC.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!let intl = Intl::new("de-DE");
+//!intl.number(1234.5); // "1.234,5"
+//!intl.currency(9.99, "EUR"); // "9,99 €"
+//!intl.percent(0.25); // "25 %"
+//!intl.date(js_sys::Date::now(), DateTimeStyle::Long); // "2. Januar 2021"
+//!intl.relative_time(-3., RelativeTimeUnit::Minute); // "vor 3 Minuten"
+//!intl.list(&["Anna", "Stephan"], ListType::Conjunction); // "Anna und Stephan"
+//! ```
+
+#[cfg(target_arch = "wasm32")]
+use js_sys::{Array, Function, Object, Reflect};
+#[cfg(target_arch = "wasm32")]
+use std::{cell::RefCell, collections::HashMap};
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{JsCast, JsValue};
+
+mod fallback;
+
+const MINUTE: f64 = 60.;
+const HOUR: f64 = 60. * MINUTE;
+const DAY: f64 = 24. * HOUR;
+const WEEK: f64 = 7. * DAY;
+const MONTH: f64 = 30. * DAY;
+const YEAR: f64 = 365. * DAY;
+
+// ------ DateTimeStyle ------
+
+/// Predefined date / time formats.
+///
+/// [MDN reference](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/DateTimeFormat/DateTimeFormat#datestyle)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DateTimeStyle {
+    /// E.g. "Saturday, January 2, 2021" or "3:04:05 PM Coordinated Universal Time".
+    Full,
+    /// E.g. "January 2, 2021" or "3:04:05 PM UTC".
+    Long,
+    /// E.g. "Jan 2, 2021" or "3:04:05 PM".
+    Medium,
+    /// E.g. "1/2/21" or "3:04 PM".
+    Short,
+}
+
+impl DateTimeStyle {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Long => "long",
+            Self::Medium => "medium",
+            Self::Short => "short",
+        }
+    }
+}
+
+// ------ RelativeTimeUnit ------
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RelativeTimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl RelativeTimeUnit {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Second => "second",
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Quarter => "quarter",
+            Self::Year => "year",
+        }
+    }
+}
+
+// ------ ListType ------
+
+/// [MDN reference](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/ListFormat/ListFormat#type)
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ListType {
+    /// "A, B, and C"
+    Conjunction,
+    /// "A, B, or C"
+    Disjunction,
+    /// "A, B, C" - e.g. for units like "5 pounds, 12 ounces".
+    Unit,
+}
+
+impl ListType {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Conjunction => "conjunction",
+            Self::Disjunction => "disjunction",
+            Self::Unit => "unit",
+        }
+    }
+}
+
+// ------ Intl ------
+
+/// Formatter for one locale. Native `Intl` formatters are created on the first use
+/// and cached, so keep `Intl` in your `Model` (or use `I18n::intl` with the `i18n` feature).
+///
+/// Timestamps are milliseconds since the Unix epoch - e.g. `js_sys::Date::now()`.
+#[derive(Debug, Clone)]
+pub struct Intl {
+    locale: String,
+    time_zone: Option<String>,
+    #[cfg(target_arch = "wasm32")]
+    formatters: RefCell<HashMap<String, JsValue>>,
+}
+
+impl Intl {
+    /// Create a formatter for the locale - e.g. `"en-US"` or `"de-DE"`.
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            time_zone: None,
+            #[cfg(target_arch = "wasm32")]
+            formatters: RefCell::default(),
+        }
+    }
+
+    /// Format dates and times in the time zone - e.g. `"UTC"` or `"Europe/Prague"`.
+    /// The user's time zone is used by default.
+    ///
+    /// _Note:_ The fallback formatter always uses UTC.
+    #[must_use]
+    pub fn time_zone(mut self, time_zone: impl Into<String>) -> Self {
+        self.time_zone = Some(time_zone.into());
+        #[cfg(target_arch = "wasm32")]
+        self.formatters.borrow_mut().clear();
+        self
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// `1234.5` => "1,234.5" (at most 3 fraction digits).
+    pub fn number(&self, value: f64) -> String {
+        self.format_number(value, 0, 3)
+    }
+
+    /// Number with exactly `fraction_digits` fraction digits - `(1234.5, 2)` => "1,234.50".
+    pub fn number_fixed(&self, value: f64, fraction_digits: u8) -> String {
+        self.format_number(value, fraction_digits, fraction_digits)
+    }
+
+    /// `(1234.5, "USD")` => "$1,234.50". `currency` is an ISO 4217 code.
+    pub fn currency(&self, value: f64, currency: &str) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let key = format!("currency:{}", currency);
+            let options = || vec![("style", "currency".into()), ("currency", currency.into())];
+            if let Some(text) = self.js_format(&key, "NumberFormat", options, &[value.into()]) {
+                return text;
+            }
+        }
+        fallback::currency(value, currency)
+    }
+
+    /// `0.256` => "26%".
+    pub fn percent(&self, value: f64) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let options = || vec![("style", "percent".into())];
+            if let Some(text) = self.js_format("percent", "NumberFormat", options, &[value.into()])
+            {
+                return text;
+            }
+        }
+        fallback::percent(value)
+    }
+
+    /// `(timestamp, DateTimeStyle::Medium)` => "Jan 2, 2021".
+    pub fn date(&self, timestamp: f64, style: DateTimeStyle) -> String {
+        self.format_date_time(timestamp, Some(style), None)
+    }
+
+    /// `(timestamp, DateTimeStyle::Short)` => "3:04 PM".
+    pub fn time(&self, timestamp: f64, style: DateTimeStyle) -> String {
+        self.format_date_time(timestamp, None, Some(style))
+    }
+
+    /// `(timestamp, DateTimeStyle::Medium, DateTimeStyle::Short)` => "Jan 2, 2021, 3:04 PM".
+    pub fn date_time(
+        &self,
+        timestamp: f64,
+        date_style: DateTimeStyle,
+        time_style: DateTimeStyle,
+    ) -> String {
+        self.format_date_time(timestamp, Some(date_style), Some(time_style))
+    }
+
+    /// `(-3., RelativeTimeUnit::Minute)` => "3 minutes ago", `(2., RelativeTimeUnit::Day)` => "in 2 days".
+    pub fn relative_time(&self, value: f64, unit: RelativeTimeUnit) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let options = || vec![("numeric", "always".into())];
+            let args = [value.into(), unit.as_str().into()];
+            if let Some(text) = self.js_format("relative", "RelativeTimeFormat", options, &args) {
+                return text;
+            }
+        }
+        fallback::relative_time(value, unit)
+    }
+
+    /// Relative time between timestamps with the most suitable unit
+    /// - e.g. "3 minutes ago" or "in 2 weeks".
+    pub fn relative_time_between(&self, timestamp: f64, now: f64) -> String {
+        let seconds = (timestamp - now) / 1000.;
+        let (unit, unit_seconds) = match seconds.abs() {
+            s if s < MINUTE => (RelativeTimeUnit::Second, 1.),
+            s if s < HOUR => (RelativeTimeUnit::Minute, MINUTE),
+            s if s < DAY => (RelativeTimeUnit::Hour, HOUR),
+            s if s < WEEK => (RelativeTimeUnit::Day, DAY),
+            s if s < MONTH => (RelativeTimeUnit::Week, WEEK),
+            s if s < YEAR => (RelativeTimeUnit::Month, MONTH),
+            _ => (RelativeTimeUnit::Year, YEAR),
+        };
+        // `+ 0.` turns `-0` into `0`.
+        self.relative_time((seconds / unit_seconds).round() + 0., unit)
+    }
+
+    /// Relative time between the timestamp and the current time - e.g. "3 minutes ago".
+    pub fn relative_time_from_now(&self, timestamp: f64) -> String {
+        self.relative_time_between(timestamp, now())
+    }
+
+    /// `(&["A", "B", "C"], ListType::Conjunction)` => "A, B, and C".
+    pub fn list<T: AsRef<str>>(&self, items: &[T], list_type: ListType) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let key = format!("list:{}", list_type.as_str());
+            let options = || vec![("type", list_type.as_str().into())];
+            let array = items
+                .iter()
+                .map(|item| JsValue::from(item.as_ref()))
+                .collect::<Array>();
+            if let Some(text) = self.js_format(&key, "ListFormat", options, &[array.into()]) {
+                return text;
+            }
+        }
+        fallback::list(items, list_type)
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), allow(clippy::unused_self))]
+    fn format_number(&self, value: f64, min_fraction: u8, max_fraction: u8) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let key = format!("number:{}:{}", min_fraction, max_fraction);
+            let options = || {
+                vec![
+                    ("minimumFractionDigits", min_fraction.into()),
+                    ("maximumFractionDigits", max_fraction.into()),
+                ]
+            };
+            if let Some(text) = self.js_format(&key, "NumberFormat", options, &[value.into()]) {
+                return text;
+            }
+        }
+        fallback::number(value, usize::from(min_fraction), usize::from(max_fraction))
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), allow(clippy::unused_self))]
+    fn format_date_time(
+        &self,
+        timestamp: f64,
+        date_style: Option<DateTimeStyle>,
+        time_style: Option<DateTimeStyle>,
+    ) -> String {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let key = format!("date_time:{:?}:{:?}", date_style, time_style);
+            let options = || {
+                let mut options = Vec::new();
+                if let Some(style) = date_style {
+                    options.push(("dateStyle", style.as_str().into()));
+                }
+                if let Some(style) = time_style {
+                    options.push(("timeStyle", style.as_str().into()));
+                }
+                if let Some(time_zone) = &self.time_zone {
+                    options.push(("timeZone", time_zone.as_str().into()));
+                }
+                options
+            };
+            let date = js_sys::Date::new(&timestamp.into());
+            if let Some(text) = self.js_format(&key, "DateTimeFormat", options, &[date.into()]) {
+                return text;
+            }
+        }
+        fallback::date_time(timestamp, date_style, time_style)
+    }
+
+    /// Call `format` on the cached `Intl.<constructor>` instance.
+    /// Returns `None` when the formatter can't be created (e.g. invalid currency code,
+    /// unsupported API) or fails.
+    #[cfg(target_arch = "wasm32")]
+    fn js_format(
+        &self,
+        key: &str,
+        constructor: &str,
+        options: impl FnOnce() -> Vec<(&'static str, JsValue)>,
+        args: &[JsValue],
+    ) -> Option<String> {
+        let cached = self.formatters.borrow().get(key).cloned();
+        let formatter = match cached {
+            Some(formatter) => formatter,
+            None => {
+                let formatter = self.js_formatter(constructor, options())?;
+                self.formatters
+                    .borrow_mut()
+                    .insert(key.to_owned(), formatter.clone());
+                formatter
+            }
+        };
+        let format = Reflect::get(&formatter, &"format".into())
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let text = match args {
+            [arg] => format.call1(&formatter, arg),
+            [arg_a, arg_b] => format.call2(&formatter, arg_a, arg_b),
+            _ => return None,
+        };
+        text.ok()?.as_string()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn js_formatter(
+        &self,
+        constructor: &str,
+        options: Vec<(&'static str, JsValue)>,
+    ) -> Option<JsValue> {
+        let intl = Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+        let constructor = Reflect::get(&intl, &constructor.into())
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?;
+        let js_options = Object::new();
+        for (name, value) in options {
+            Reflect::set(&js_options, &name.into(), &value).ok()?;
+        }
+        let args = Array::of2(&self.locale.as_str().into(), &js_options);
+        Reflect::construct(&constructor, &args).ok()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0., |duration| duration.as_secs_f64() * 1000.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    // 2021-01-02T15:04:05Z
+    const TIMESTAMP: f64 = 1_609_599_845_000.;
+
+    #[wasm_bindgen_test]
+    fn format_numbers() {
+        let intl = Intl::new("en-US");
+        assert_eq!(intl.number(-1_234_567.891_23), "-1,234,567.891");
+        assert_eq!(intl.number_fixed(1234.5, 2), "1,234.50");
+        assert_eq!(intl.currency(1234.5, "USD"), "$1,234.50");
+        assert_eq!(intl.percent(0.256), "26%");
+    }
+
+    #[wasm_bindgen_test]
+    fn format_dates() {
+        let intl = Intl::new("en-US").time_zone("UTC");
+        assert_eq!(intl.date(TIMESTAMP, DateTimeStyle::Short), "1/2/21");
+        assert_eq!(
+            intl.date(TIMESTAMP, DateTimeStyle::Full),
+            "Saturday, January 2, 2021"
+        );
+        assert_eq!(
+            intl.date(TIMESTAMP, DateTimeStyle::Medium),
+            intl.date(TIMESTAMP + 1000., DateTimeStyle::Medium)
+        );
+        assert!(intl
+            .time(TIMESTAMP, DateTimeStyle::Medium)
+            .starts_with("3:04:05"));
+    }
+
+    #[wasm_bindgen_test]
+    fn format_relative_time_and_lists() {
+        let intl = Intl::new("en-US");
+        assert_eq!(
+            intl.relative_time_between(TIMESTAMP - 3. * 60_000., TIMESTAMP),
+            "3 minutes ago"
+        );
+        assert_eq!(
+            intl.relative_time_between(TIMESTAMP + 2. * 86_400_000., TIMESTAMP),
+            "in 2 days"
+        );
+        assert_eq!(intl.relative_time(1., RelativeTimeUnit::Hour), "in 1 hour");
+        assert_eq!(
+            intl.list(&["A", "B", "C"], ListType::Conjunction),
+            "A, B, and C"
+        );
+        assert_eq!(intl.list(&["A", "B"], ListType::Disjunction), "A or B");
+    }
+}
@@ -0,0 +1,334 @@
+//! Pure-Rust formatting used outside of the browser or when `Intl` fails.
+//! The output mimics the `en-US` locale; dates and times are in UTC.
+
+use super::{DateTimeStyle, ListType, RelativeTimeUnit};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+// ------ Numbers ------
+
+pub fn number(value: f64, min_fraction: usize, max_fraction: usize) -> String {
+    if value.is_nan() {
+        return "NaN".to_owned();
+    }
+    if value.is_infinite() {
+        return if value < 0. { "-∞" } else { "∞" }.to_owned();
+    }
+    let digits = format!("{:.*}", max_fraction, value.abs());
+    let (integer, fraction) = digits.find('.').map_or((digits.as_str(), ""), |index| {
+        (&digits[..index], &digits[index + 1..])
+    });
+    let mut fraction = fraction;
+    while fraction.len() > min_fraction && fraction.ends_with('0') {
+        fraction = &fraction[..fraction.len() - 1];
+    }
+
+    let mut text = String::with_capacity(digits.len() + integer.len() / 3 + 1);
+    // Don't render "-0".
+    if value < 0. && digits.chars().any(|c| ('1'..='9').contains(&c)) {
+        text.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            text.push(',');
+        }
+        text.push(digit);
+    }
+    if !fraction.is_empty() {
+        text.push('.');
+        text.push_str(fraction);
+    }
+    text
+}
+
+pub fn currency(value: f64, currency: &str) -> String {
+    let fraction_digits = match currency {
+        "JPY" | "KRW" => 0,
+        _ => 2,
+    };
+    let amount = number(value.abs(), fraction_digits, fraction_digits);
+    let sign = if value < 0. && amount.chars().any(|c| ('1'..='9').contains(&c)) {
+        "-"
+    } else {
+        ""
+    };
+    match currency {
+        "USD" => format!("{}${}", sign, amount),
+        "EUR" => format!("{}€{}", sign, amount),
+        "GBP" => format!("{}£{}", sign, amount),
+        "JPY" => format!("{}¥{}", sign, amount),
+        _ => format!("{}{}\u{a0}{}", sign, currency, amount),
+    }
+}
+
+pub fn percent(value: f64) -> String {
+    format!("{}%", number(value * 100., 0, 0))
+}
+
+// ------ Dates and times ------
+
+pub fn date_time(
+    timestamp: f64,
+    date_style: Option<DateTimeStyle>,
+    time_style: Option<DateTimeStyle>,
+) -> String {
+    #[allow(clippy::cast_possible_truncation)]
+    let seconds = (timestamp / 1000.).floor() as i64;
+    let days = seconds.div_euclid(86_400);
+    let seconds_of_day = seconds.rem_euclid(86_400);
+
+    match (date_style, time_style) {
+        (Some(date_style), Some(time_style)) => {
+            let separator = match date_style {
+                DateTimeStyle::Full | DateTimeStyle::Long => " at ",
+                DateTimeStyle::Medium | DateTimeStyle::Short => ", ",
+            };
+            format!(
+                "{}{}{}",
+                date(days, date_style),
+                separator,
+                time(seconds_of_day, time_style)
+            )
+        }
+        (None, Some(time_style)) => time(seconds_of_day, time_style),
+        (Some(date_style), None) => date(days, date_style),
+        (None, None) => date(days, DateTimeStyle::Short),
+    }
+}
+
+fn date(days: i64, style: DateTimeStyle) -> String {
+    let (year, month, day) = civil_from_days(days);
+    let month_name = MONTHS[month - 1];
+    match style {
+        DateTimeStyle::Full => {
+            // 1970-01-01 was Thursday.
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let weekday = WEEKDAYS[(days + 4).rem_euclid(7) as usize];
+            format!("{}, {} {}, {}", weekday, month_name, day, year)
+        }
+        DateTimeStyle::Long => format!("{} {}, {}", month_name, day, year),
+        DateTimeStyle::Medium => format!("{} {}, {}", &month_name[..3], day, year),
+        DateTimeStyle::Short => format!("{}/{}/{:02}", month, day, year.rem_euclid(100)),
+    }
+}
+
+fn time(seconds_of_day: i64, style: DateTimeStyle) -> String {
+    let hour = seconds_of_day / 3600;
+    let minute = seconds_of_day % 3600 / 60;
+    let second = seconds_of_day % 60;
+    let period = if hour < 12 { "AM" } else { "PM" };
+    let hour = match hour % 12 {
+        0 => 12,
+        hour => hour,
+    };
+    match style {
+        DateTimeStyle::Full => format!(
+            "{}:{:02}:{:02} {} Coordinated Universal Time",
+            hour, minute, second, period
+        ),
+        DateTimeStyle::Long => format!("{}:{:02}:{:02} {} UTC", hour, minute, second, period),
+        DateTimeStyle::Medium => format!("{}:{:02}:{:02} {}", hour, minute, second, period),
+        DateTimeStyle::Short => format!("{}:{:02} {}", hour, minute, period),
+    }
+}
+
+/// Days since 1970-01-01 => (year, month, day).
+///
+/// [Algorithm](http://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn civil_from_days(days: i64) -> (i64, usize, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month as usize, day)
+}
+
+// ------ Relative time ------
+
+pub fn relative_time(value: f64, unit: RelativeTimeUnit) -> String {
+    let amount = number(value.abs(), 0, 3);
+    #[allow(clippy::float_cmp)]
+    let plural = if value.abs() == 1. { "" } else { "s" };
+    if value < 0. {
+        format!("{} {}{} ago", amount, unit.as_str(), plural)
+    } else {
+        format!("in {} {}{}", amount, unit.as_str(), plural)
+    }
+}
+
+// ------ Lists ------
+
+pub fn list<T: AsRef<str>>(items: &[T], list_type: ListType) -> String {
+    let conjunction = match list_type {
+        ListType::Conjunction => "and",
+        ListType::Disjunction => "or",
+        ListType::Unit => {
+            return items
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    match items {
+        [] => String::new(),
+        [item] => item.as_ref().to_owned(),
+        [first, second] => format!("{} {} {}", first.as_ref(), conjunction, second.as_ref()),
+        [init @ .., last] => {
+            let mut text = String::new();
+            for item in init {
+                text.push_str(item.as_ref());
+                text.push_str(", ");
+            }
+            format!("{}{} {}", text, conjunction, last.as_ref())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    // The fallback doesn't need the browser - the tests are run also by native `cargo test`.
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn format_number() {
+        assert_eq!(number(1234.5678, 0, 2), "1,234.57");
+        assert_eq!(number(-1_234_567.5, 0, 1), "-1,234,567.5");
+        assert_eq!(number(999.999, 0, 2), "1,000");
+        assert_eq!(number(123., 0, 0), "123");
+        // Trailing zeros are removed only above the minimum fraction digits.
+        assert_eq!(number(1.5, 2, 3), "1.50");
+        assert_eq!(number(1.23, 0, 3), "1.23");
+        // Negative values rounded to zero.
+        assert_eq!(number(-0.001, 0, 2), "0");
+        assert_eq!(number(-0., 0, 0), "0");
+        assert_eq!(number(f64::NAN, 0, 0), "NaN");
+        assert_eq!(number(f64::NEG_INFINITY, 0, 0), "-∞");
+        assert_eq!(percent(0.256), "26%");
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn format_currency() {
+        assert_eq!(currency(-1234.5, "USD"), "-$1,234.50");
+        assert_eq!(currency(1234.6, "JPY"), "¥1,235");
+        assert_eq!(currency(-0.001, "EUR"), "€0.00");
+        assert_eq!(currency(5., "CHF"), "CHF\u{a0}5.00");
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn civil_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+        // 1900 isn't a leap year.
+        assert_eq!(civil_from_days(-25508), (1900, 3, 1));
+        assert_eq!(civil_from_days(-25509), (1900, 2, 28));
+        assert_eq!(civil_from_days(-135_081), (1600, 2, 29));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn format_date_time() {
+        let timestamp = 1_609_599_845_000.;
+        assert_eq!(
+            date_time(
+                timestamp,
+                Some(DateTimeStyle::Full),
+                Some(DateTimeStyle::Full)
+            ),
+            "Saturday, January 2, 2021 at 3:04:05 PM Coordinated Universal Time"
+        );
+        assert_eq!(
+            date_time(
+                timestamp,
+                Some(DateTimeStyle::Medium),
+                Some(DateTimeStyle::Short)
+            ),
+            "Jan 2, 2021, 3:04 PM"
+        );
+        assert_eq!(
+            date_time(timestamp, None, Some(DateTimeStyle::Long)),
+            "3:04:05 PM UTC"
+        );
+
+        // Before 1970.
+        assert_eq!(
+            date_time(-616_894_200_000., Some(DateTimeStyle::Full), None),
+            "Thursday, June 15, 1950"
+        );
+        assert_eq!(
+            date_time(-616_894_200_000., None, Some(DateTimeStyle::Medium)),
+            "12:30:00 AM"
+        );
+        assert_eq!(date_time(-1., None, None), "12/31/69");
+        assert_eq!(
+            date_time(-1., Some(DateTimeStyle::Short), Some(DateTimeStyle::Medium)),
+            "12/31/69, 11:59:59 PM"
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn format_relative_time() {
+        assert_eq!(relative_time(-1., RelativeTimeUnit::Day), "1 day ago");
+        assert_eq!(relative_time(2.5, RelativeTimeUnit::Hour), "in 2.5 hours");
+        assert_eq!(relative_time(0., RelativeTimeUnit::Second), "in 0 seconds");
+        assert_eq!(
+            relative_time(-1500., RelativeTimeUnit::Year),
+            "1,500 years ago"
+        );
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn format_list() {
+        let items = ["A", "B", "C"];
+        assert_eq!(list(&items, ListType::Conjunction), "A, B, and C");
+        assert_eq!(list(&items, ListType::Disjunction), "A, B, or C");
+        assert_eq!(list(&items, ListType::Unit), "A, B, C");
+        assert_eq!(list(&items[..2], ListType::Conjunction), "A and B");
+        assert_eq!(list(&items[..1], ListType::Disjunction), "A");
+        assert_eq!(list::<&str>(&[], ListType::Conjunction), "");
+    }
+}
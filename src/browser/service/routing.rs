@@ -2,47 +2,269 @@ use super::super::{
     util::{self, ClosureNew},
     Url,
 };
-use crate::app::{subs, Notification};
-use std::rc::Rc;
+use crate::app::navigation_guard::{
+    NavigationDecision, NavigationGuards, NavigationKind, NavigationRequest,
+};
+use crate::app::{subs, Notification, ScrollRestoration};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    convert::TryFrom,
+    rc::Rc,
+};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 
+// ------ History state ------
+
+/// State stored by Seed in history entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HistoryState {
+    // We use data to evaluate the path instead of the path displayed in the url.
+    url: Url,
+    /// Index of the entry in the session history - used to restore the previous entry
+    /// when a back / forward navigation is canceled.
+    position: u32,
+    /// Scroll position `(x, y)` saved when the entry was left by a new navigation.
+    #[serde(default)]
+    scroll: Option<(f64, f64)>,
+    /// App data from `Url::go_and_push_with_state` / `Url::go_and_replace_with_state`.
+    #[serde(default)]
+    user_state: Option<serde_json::Value>,
+}
+
+impl HistoryState {
+    fn from_js(state: &JsValue) -> Option<Self> {
+        serde_json::from_str(&state.as_string()?).ok()
+    }
+
+    fn to_js(&self) -> JsValue {
+        JsValue::from_str(&serde_json::to_string(self).expect("Problem serializing route data"))
+    }
+}
+
+thread_local! {
+    /// State of the current history entry.
+    static CURRENT_STATE: RefCell<Option<HistoryState>> = RefCell::default();
+    /// `popstate` fired by restoring the previous entry is ignored.
+    static IGNORE_NEXT_POPSTATE: Cell<bool> = Cell::default();
+    /// Scroll positions of entries left by back / forward navigation - their states
+    /// can't be updated anymore when `popstate` is fired.
+    static SCROLL_POSITIONS: RefCell<BTreeMap<u32, (f64, f64)>> = RefCell::default();
+}
+
+fn current_position() -> u32 {
+    CURRENT_STATE.with(|state| state.borrow().as_ref().map_or(0, |state| state.position))
+}
+
+/// Load the state of the current history entry or create it - e.g. after the first page load.
+pub fn init_history() {
+    let state = util::history()
+        .state()
+        .ok()
+        .and_then(|state| HistoryState::from_js(&state));
+    match state {
+        Some(state) => {
+            CURRENT_STATE.with(|current| current.replace(Some(state)));
+        }
+        None => replace_route(&Url::current(), None),
+    }
+}
+
 // Add a new route using history's `push_state` method.
 //
 // # References
 // * [MDN docs](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
-pub fn push_route<U: Into<Url>>(url: U) -> Url {
+pub fn push_route<U: Into<Url>>(url: U, user_state: Option<serde_json::Value>) -> Url {
     let url = url.into();
-    // We use data to evaluate the path instead of the path displayed in the url.
-    let data =
-        JsValue::from_str(&serde_json::to_string(&url).expect("Problem serializing route data"));
-
+    save_scroll_position();
+    let position = current_position() + 1;
+    // Entries after the current one are removed from the history by pushing.
+    SCROLL_POSITIONS.with(|positions| positions.borrow_mut().split_off(&position));
+    let state = HistoryState {
+        url: url.clone(),
+        position,
+        scroll: None,
+        user_state,
+    };
     util::history()
-        .push_state_with_url(&data, "", Some(&url.to_string()))
+        .push_state_with_url(&state.to_js(), "", Some(&url.to_string()))
         .expect("Problem pushing state");
+    CURRENT_STATE.with(|current| current.replace(Some(state)));
     url
 }
 
+// Replace the current route using history's `replace_state` method.
+pub fn replace_route(url: &Url, user_state: Option<serde_json::Value>) {
+    let state = HistoryState {
+        url: url.clone(),
+        position: current_position(),
+        scroll: None,
+        user_state,
+    };
+    util::history()
+        .replace_state_with_url(&state.to_js(), "", Some(&url.to_string()))
+        .expect("Problem replacing state");
+    CURRENT_STATE.with(|current| current.replace(Some(state)));
+}
+
+/// Return to the entry that was active before the canceled back / forward navigation.
+fn restore_history_entry(popped_state: Option<HistoryState>) {
+    let current_state = CURRENT_STATE.with(|current| current.borrow().clone());
+    let current_state = match current_state {
+        Some(current_state) => current_state,
+        None => return,
+    };
+    // The entry wasn't created by Seed (e.g. by an anchor link), so we don't know where
+    // the previous entry is. It's left untouched - the app stays on the current page,
+    // but the address bar shows the entry's URL.
+    let popped_state = match popped_state {
+        Some(popped_state) => popped_state,
+        None => return,
+    };
+    let delta = i64::from(current_state.position) - i64::from(popped_state.position);
+    if delta != 0 {
+        IGNORE_NEXT_POPSTATE.with(|ignore| ignore.set(true));
+        util::history()
+            .go_with_delta(i32::try_from(delta).unwrap_or_default())
+            .expect("Problem restoring history entry");
+    }
+}
+
+// ------ Scroll ------
+
+/// Scrolling after the render following a navigation.
+#[derive(Debug, Clone)]
+pub enum Scroll {
+    /// Back / forward navigation - restore the saved position.
+    Restore(Option<(f64, f64)>),
+    /// New navigation - scroll to the element with the id from the url hash or to the top.
+    Hash(Option<String>),
+}
+
+impl Scroll {
+    pub fn apply(self, scroll_restoration: ScrollRestoration) {
+        let window = util::window();
+        match self {
+            Self::Restore(position) => {
+                if let (true, Some((x, y))) = (scroll_restoration.restore_on_back_forward, position)
+                {
+                    window.scroll_to_with_x_and_y(x, y);
+                }
+            }
+            Self::Hash(hash) => {
+                let target = hash
+                    .filter(|_| scroll_restoration.scroll_to_hash)
+                    .filter(|hash| !hash.is_empty())
+                    .and_then(|hash| util::document().get_element_by_id(&hash));
+                match target {
+                    Some(target) => target.scroll_into_view(),
+                    None if scroll_restoration.scroll_to_top => {
+                        window.scroll_to_with_x_and_y(0., 0.);
+                    }
+                    None => (),
+                }
+            }
+        }
+    }
+}
+
+/// Let the browser restore scroll positions when Seed doesn't do it.
+pub fn set_browser_scroll_restoration(scroll_restoration: ScrollRestoration) {
+    let value = if scroll_restoration.restore_on_back_forward {
+        "manual"
+    } else {
+        "auto"
+    };
+    // `History.scrollRestoration` isn't available in old browsers.
+    let _ = js_sys::Reflect::set(
+        util::history().as_ref(),
+        &"scrollRestoration".into(),
+        &value.into(),
+    );
+}
+
+fn scroll_position() -> (f64, f64) {
+    let window = util::window();
+    (
+        window.page_x_offset().unwrap_or_default(),
+        window.page_y_offset().unwrap_or_default(),
+    )
+}
+
+/// Store the scroll position into the state of the current entry before leaving it.
+fn save_scroll_position() {
+    let state = CURRENT_STATE.with(|current| current.borrow().clone());
+    if let Some(mut state) = state {
+        state.scroll = Some(scroll_position());
+        util::history()
+            .replace_state(&state.to_js(), "")
+            .expect("Problem saving scroll position");
+        CURRENT_STATE.with(|current| current.replace(Some(state)));
+    }
+}
+
+fn saved_scroll_position(state: &HistoryState) -> Option<(f64, f64)> {
+    SCROLL_POSITIONS
+        .with(|positions| positions.borrow().get(&state.position).copied())
+        .or(state.scroll)
+}
+
+/// Remember the scroll position of the current entry and make the popped entry current.
+fn leave_history_entry(popped_state: Option<HistoryState>) {
+    let position = current_position();
+    SCROLL_POSITIONS.with(|positions| positions.borrow_mut().insert(position, scroll_position()));
+    if popped_state.is_some() {
+        CURRENT_STATE.with(|current| current.replace(popped_state));
+    }
+}
+
 pub fn setup_popstate_listener(
     updated_listener: impl Fn(Closure<dyn FnMut(web_sys::Event)>) + 'static,
     notify: impl Fn(Notification) + 'static,
+    scroll_after_next_render: impl Fn(Scroll) + 'static,
     base_path: Rc<[String]>,
+    navigation_guards: Rc<NavigationGuards>,
 ) {
     let closure = Closure::new(move |ev: web_sys::Event| {
+        if IGNORE_NEXT_POPSTATE.with(|ignore| ignore.replace(false)) {
+            return;
+        }
         let ev = ev
             .dyn_ref::<web_sys::PopStateEvent>()
             .expect("Problem casting as Popstate event");
 
-        let url = match ev.state().as_string() {
-            Some(state_str) => {
-                serde_json::from_str(&state_str).expect("Problem deserializing popstate state")
-            }
+        let state = HistoryState::from_js(&ev.state());
+        let url = match &state {
+            Some(state) => state.url.clone(),
             // Only update when requested for an update by the user.
             None => Url::current(),
         };
+        let url = url.skip_base_path(&base_path);
 
-        notify(Notification::new(subs::UrlChanged(
-            url.skip_base_path(&base_path),
-        )));
+        let request = NavigationRequest {
+            kind: NavigationKind::PopState,
+            url: Some(url.clone()),
+        };
+        match navigation_guards.check(&request) {
+            NavigationDecision::Allow => {
+                let scroll = Scroll::Restore(state.as_ref().and_then(saved_scroll_position));
+                let user_state = state.as_ref().and_then(|state| state.user_state.clone());
+                leave_history_entry(state);
+                scroll_after_next_render(scroll);
+                notify(Notification::new(subs::UrlChanged(url.clone())));
+                notify(Notification::new(subs::HistoryStateChanged::new(
+                    url, user_state,
+                )));
+            }
+            NavigationDecision::Cancel => restore_history_entry(state),
+            NavigationDecision::Redirect(url) => {
+                leave_history_entry(state);
+                let url = push_route(url, None).skip_base_path(&base_path);
+                scroll_after_next_render(Scroll::Hash(url.hash().cloned()));
+                notify(Notification::new(subs::UrlChanged(url)));
+            }
+        }
     });
 
     (util::window().as_ref() as &web_sys::EventTarget)
@@ -56,6 +278,8 @@ pub fn setup_popstate_listener(
 pub fn url_request_handler(
     sub_data: subs::UrlRequested,
     base_path: Rc<[String]>,
+    navigation_guards: &NavigationGuards,
+    scroll_after_next_render: impl Fn(Scroll),
     notify: impl Fn(Notification) + 'static,
 ) {
     let subs::UrlRequested(url, request) = sub_data;
@@ -66,7 +290,17 @@ pub fn url_request_handler(
                 event.prevent_default(); // Prevent page refresh
             }
             let url = url.skip_base_path(&base_path).skip_hash_base_path(&[]);
-            push_route(url.clone());
+            let navigation_request = NavigationRequest {
+                kind: NavigationKind::Link,
+                url: Some(url.clone()),
+            };
+            let url = match navigation_guards.check(&navigation_request) {
+                NavigationDecision::Allow => url,
+                NavigationDecision::Cancel => return,
+                NavigationDecision::Redirect(url) => url.skip_base_path(&base_path),
+            };
+            push_route(url.clone(), None);
+            scroll_after_next_render(Scroll::Hash(url.hash().cloned()));
             notify(Notification::new(subs::UrlChanged(url)));
         }
         subs::url_requested::UrlRequestStatus::Handled(prevent_default) => {
@@ -130,3 +364,57 @@ pub fn setup_link_listener(notify: impl Fn(Notification) + 'static) {
 
     closure.forget(); // todo: Can we store the closure somewhere to avoid using forget?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn history_state_without_scroll_position() {
+        let state = HistoryState {
+            url: Url::new().add_path_part("page"),
+            position: 2,
+            scroll: None,
+            user_state: None,
+        };
+        let mut json = serde_json::to_value(&state).unwrap();
+        // States stored by previous versions don't contain `scroll`.
+        json.as_object_mut().unwrap().remove("scroll");
+        let state: HistoryState = serde_json::from_value(json).unwrap();
+        assert_eq!(state.position, 2);
+        assert_eq!(state.scroll, None);
+
+        let state = HistoryState {
+            scroll: Some((0., 120.5)),
+            ..state
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        let state: HistoryState = serde_json::from_str(&json).unwrap();
+        assert_eq!(saved_scroll_position(&state), Some((0., 120.5)));
+    }
+
+    #[wasm_bindgen_test]
+    fn history_state_with_user_state() {
+        let state = HistoryState {
+            url: Url::new().add_path_part("wizard"),
+            position: 1,
+            scroll: None,
+            user_state: Some(serde_json::json!({ "step": 3 })),
+        };
+        let json = serde_json::to_string(&state).unwrap();
+        let state: HistoryState = serde_json::from_str(&json).unwrap();
+
+        let changed = subs::HistoryStateChanged::new(state.url, state.user_state);
+        assert_eq!(
+            changed.deserialize::<BTreeMap<String, u32>>().unwrap()["step"],
+            3
+        );
+        assert!(changed.deserialize::<String>().is_err());
+
+        let changed = subs::HistoryStateChanged::new(Url::new(), None);
+        assert_eq!(changed.deserialize::<Option<u32>>().unwrap(), None);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::browser::util;
+use crate::browser::{service::routing, util};
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, collections::BTreeMap, fmt, str::FromStr};
 use wasm_bindgen::JsValue;
@@ -39,14 +39,23 @@ impl Url {
     /// # References
     /// * [MDN docs](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
     pub fn go_and_push(&self) {
-        // We use data to evaluate the path instead of the path displayed in the url.
-        let data = JsValue::from_str(
-            &serde_json::to_string(&self).expect("Problem serializing route data"),
-        );
+        routing::push_route(self.clone(), None);
+    }
 
-        util::history()
-            .push_state_with_url(&data, "", Some(&self.to_string()))
-            .expect("Problem pushing state");
+    /// Change the browser URL and attach `state` to the new history entry,
+    /// but do not trigger a page load.
+    ///
+    /// The state is delivered by `subs::HistoryStateChanged` on back / forward navigation
+    /// to the entry - e.g. a selected tab or a wizard step that shouldn't be in the URL.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `state` can't be serialized to JSON.
+    pub fn go_and_push_with_state<T: Serialize + ?Sized>(&self, state: &T) {
+        routing::push_route(
+            self.clone(),
+            Some(serde_json::to_value(state).expect("serialize history state")),
+        );
     }
 
     /// Change the browser URL, but do not trigger a page load.
@@ -56,14 +65,20 @@ impl Url {
     /// # References
     /// * [MDN docs](https://developer.mozilla.org/en-US/docs/Web/API/History_API)
     pub fn go_and_replace(&self) {
-        // We use data to evaluate the path instead of the path displayed in the url.
-        let data = JsValue::from_str(
-            &serde_json::to_string(&self).expect("Problem serializing route data"),
-        );
+        routing::replace_route(self, None);
+    }
 
-        util::history()
-            .replace_state_with_url(&data, "", Some(&self.to_string()))
-            .expect("Problem pushing state");
+    /// Change the browser URL and replace the state of the current history entry,
+    /// but do not trigger a page load - see `Url::go_and_push_with_state`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `state` can't be serialized to JSON.
+    pub fn go_and_replace_with_state<T: Serialize + ?Sized>(&self, state: &T) {
+        routing::replace_route(
+            self,
+            Some(serde_json::to_value(state).expect("serialize history state")),
+        );
     }
 
     /// Creates a new `Url` from the one that is currently set in the browser.
@@ -178,7 +178,10 @@ pub fn set_value(target: &web_sys::EventTarget, value: &str) -> Result<(), Cow<'
     if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
         return set_html_input_element_value(input, value);
     }
-    set!(HtmlTextAreaElement);
+    if let Some(textarea) = target.dyn_ref::<HtmlTextAreaElement>() {
+        set_html_textarea_element_value(textarea, value);
+        return Ok(());
+    }
     set!(HtmlSelectElement);
     set!(HtmlProgressElement, |_| value.parse().map_err(|error| {
         Cow::from(format!(
@@ -234,6 +237,11 @@ fn set_html_input_element_value(
         _ => false,
     };
 
+    // Rewriting the same value would move the cursor and break IME input on some platforms.
+    if input.value() == value {
+        return Ok(());
+    }
+
     // We don't want to set selection in inactive input because
     // that input would "steal" focus from the active element on some platforms.
     if selection_update_required && is_active(input) {
@@ -259,6 +267,33 @@ fn set_html_input_element_value(
     Ok(())
 }
 
+/// See `set_html_input_element_value`.
+fn set_html_textarea_element_value(textarea: &web_sys::HtmlTextAreaElement, value: &str) {
+    if textarea.value() == value {
+        return;
+    }
+
+    if is_active(textarea) {
+        let selection_start = textarea
+            .selection_start()
+            .expect("get `HtmlTextAreaElement` selection start");
+        let selection_end = textarea
+            .selection_end()
+            .expect("get `HtmlTextAreaElement` selection end");
+
+        textarea.set_value(value);
+
+        textarea
+            .set_selection_start(selection_start)
+            .expect("set `HtmlTextAreaElement` selection start");
+        textarea
+            .set_selection_end(selection_end)
+            .expect("set `HtmlTextAreaElement` selection end");
+    } else {
+        textarea.set_value(value);
+    }
+}
+
 /// Return true if passed element is active.
 fn is_active(element: &web_sys::Element) -> bool {
     document().active_element().as_ref() == Some(element)
@@ -46,5 +46,6 @@ macro_rules! make_attrs {
     }
 }
 
+// `seed-macros` has a copy of the list in `macros/src/entity_names`.
 mod attribute_names;
 pub use attribute_names::At;
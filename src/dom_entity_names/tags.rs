@@ -48,5 +48,6 @@ macro_rules! make_tags {
     }
 }
 
+// `seed-macros` has a copy of the list in `macros/src/entity_names`.
 mod tag_names;
 pub use tag_names::Tag;
@@ -0,0 +1,718 @@
+//! Form state management - field values, validation, dirty/touched/submitting state
+//! and deserialization of the submitted form into a `serde` struct.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!#[derive(Deserialize)]
+//!struct Signup {
+//!    email: String,
+//!    age: u8,
+//!    newsletter: bool,
+//!}
+//!
+//!// init
+//!form: Form::new()
+//!    .field("email", "")
+//!    .field("age", "18")
+//!    .field("newsletter", "false")
+//!    .validator("email", |email| {
+//!        if email.contains('@') { Ok(()) } else { Err("Invalid email".to_owned()) }
+//!    })
+//!    .async_validator("email", |email| async move { check_email_availability(email).await }),
+//!
+//!// update
+//!Msg::Form(msg) => {
+//!    if let Some(signup) = model.form.update(msg, &mut orders.proxy(Msg::Form)) {
+//!        orders.perform_cmd(async move { Msg::SignedUp(send_signup(signup).await) });
+//!    }
+//!}
+//!Msg::SignedUp(_) => model.form.finish_submit(),
+//!
+//!// view
+//!form![
+//!    model.form.bind_submit(Msg::Form),
+//!    input![model.form.bind("email", Msg::Form)],
+//!    model.form.error("email").map(|error| div![C!["error"], error]),
+//!    input![attrs!{At::Type => "number"}, model.form.bind("age", Msg::Form)],
+//!    input![attrs!{At::Type => "checkbox"}, model.form.bind_checkbox("newsletter", Msg::Form)],
+//!    button![attrs!{At::Disabled => model.form.is_submitting().as_at_value()}, "Sign up"],
+//!]
+//! ```
+
+use crate::app::{CmdHandle, Orders};
+use crate::browser::dom::event_handler::{ev, input_ev};
+use crate::virtual_dom::{AsAtValue, At, Attrs, El, Ev, EventHandler, UpdateEl};
+use futures::future::{FutureExt, LocalBoxFuture};
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use std::{fmt, future::Future, marker::PhantomData, rc::Rc};
+use wasm_bindgen::JsCast;
+
+pub mod de;
+pub mod ser;
+
+pub use de::{from_values, DeError, FieldValues};
+pub use ser::{to_values, SerError};
+
+type Validator = Rc<dyn Fn(&str) -> Result<(), String>>;
+type AsyncValidator = Rc<dyn Fn(String) -> LocalBoxFuture<'static, Result<(), String>>>;
+
+// ------ Msg ------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Msg {
+    /// The field value has been changed by the user.
+    Input { field: String, value: String },
+    /// The field has lost focus.
+    Blur { field: String },
+    /// The form has been submitted.
+    Submit,
+    /// The async validator of the field has finished.
+    AsyncValidated {
+        field: String,
+        result: Result<(), String>,
+    },
+    /// Reset all fields to their initial values.
+    Reset,
+}
+
+// ------ Form ------
+
+/// Form state - see the module docs for an example.
+///
+/// Validation:
+/// - Sync validators are run when the value is changed and on submit.
+/// - Async validators are run when the field loses focus and on submit.
+///   The running validation is cancelled when the value is changed.
+/// - `error` returns errors only for touched fields (blurred or changed) and after a submit attempt.
+pub struct Form<T> {
+    fields: IndexMap<String, Field>,
+    submit_attempted: bool,
+    // Waiting for async validators before the submit.
+    submit_requested: bool,
+    submitting: bool,
+    submit_error: Option<String>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> Default for Form<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for Form<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Form")
+            .field("fields", &self.fields)
+            .field("submit_attempted", &self.submit_attempted)
+            .field("submitting", &self.submitting)
+            .field("submit_error", &self.submit_error)
+            .finish()
+    }
+}
+
+impl<T> Form<T> {
+    pub fn new() -> Self {
+        Self {
+            fields: IndexMap::new(),
+            submit_attempted: false,
+            submit_requested: false,
+            submitting: false,
+            submit_error: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Add the field with the initial value.
+    pub fn field(mut self, name: impl Into<String>, initial_value: impl Into<String>) -> Self {
+        let initial_value = initial_value.into();
+        self.fields.insert(
+            name.into(),
+            Field {
+                value: initial_value.clone(),
+                initial_value,
+                touched: false,
+                error: None,
+                validators: Vec::new(),
+                async_validator: None,
+                async_validation: None,
+            },
+        );
+        self
+    }
+
+    /// Add a sync validator to the field. Validators are run in the order of addition.
+    /// The initial value is validated immediately, so `is_valid` is correct from the start.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn validator(
+        mut self,
+        name: &str,
+        validator: impl Fn(&str) -> Result<(), String> + 'static,
+    ) -> Self {
+        let field = self.field_mut(name);
+        field.validators.push(Rc::new(validator));
+        field.validate();
+        self
+    }
+
+    /// Set the async validator of the field - e.g. a check on the server.
+    /// It's run only when sync validators pass.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn async_validator<F>(
+        mut self,
+        name: &str,
+        validator: impl Fn(String) -> F + 'static,
+    ) -> Self
+    where
+        F: Future<Output = Result<(), String>> + 'static,
+    {
+        self.field_mut(name).async_validator =
+            Some(Rc::new(move |value| validator(value).boxed_local()));
+        self
+    }
+
+    // ------ Getters ------
+
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn value(&self, name: &str) -> &str {
+        &self.field_ref(name).value
+    }
+
+    /// All field values - e.g. for cross-field validation.
+    pub fn values(&self) -> FieldValues {
+        self.fields
+            .iter()
+            .map(|(name, field)| (name.clone(), vec![field.value.clone()]))
+            .collect()
+    }
+
+    /// The error of the field if it has been touched or the submit has been attempted.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn error(&self, name: &str) -> Option<&str> {
+        let field = self.field_ref(name);
+        if field.touched || self.submit_attempted {
+            field.error.as_deref()
+        } else {
+            None
+        }
+    }
+
+    /// The error of the last submit - e.g. when the values couldn't be deserialized.
+    pub fn submit_error(&self) -> Option<&str> {
+        self.submit_error.as_deref()
+    }
+
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn is_touched(&self, name: &str) -> bool {
+        self.field_ref(name).touched
+    }
+
+    /// Is the field value different from its initial value?
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn is_field_dirty(&self, name: &str) -> bool {
+        self.field_ref(name).is_dirty()
+    }
+
+    /// Is any field value different from its initial value?
+    pub fn is_dirty(&self) -> bool {
+        self.fields.values().any(Field::is_dirty)
+    }
+
+    /// Is the async validator of the field running?
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn is_validating(&self, name: &str) -> bool {
+        self.field_ref(name).async_validation.is_some()
+    }
+
+    /// Do all fields pass their validators?
+    /// (Fields with running async validators or with values not validated by async validators
+    /// yet are considered valid.)
+    pub fn is_valid(&self) -> bool {
+        self.fields.values().all(|field| field.error.is_none())
+    }
+
+    /// The form has been submitted and `finish_submit` hasn't been called yet.
+    pub const fn is_submitting(&self) -> bool {
+        self.submitting
+    }
+
+    // ------ Setters ------
+
+    /// Set the field value - e.g. when the data for an edit form have been loaded.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn set_value(&mut self, name: &str, value: impl Into<String>) {
+        let field = self.field_mut(name);
+        field.value = value.into();
+        field.validate();
+    }
+
+    /// Set the field error - e.g. an error returned from the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn set_error(&mut self, name: &str, error: impl Into<String>) {
+        self.field_mut(name).error = Some(error.into());
+    }
+
+    /// Call when the submitted data have been processed - e.g. the server has responded.
+    pub fn finish_submit(&mut self) {
+        self.submitting = false;
+    }
+
+    /// Reset all fields to their initial values and clear all states.
+    pub fn reset(&mut self) {
+        for field in self.fields.values_mut() {
+            field.value = field.initial_value.clone();
+            field.touched = false;
+            field.validate();
+        }
+        self.submit_attempted = false;
+        self.submit_requested = false;
+        self.submitting = false;
+        self.submit_error = None;
+    }
+
+    // ------ Bindings ------
+
+    /// Bind the field to an `input`, `textarea` or `select` element - it sets
+    /// `name`, `value` and `aria-invalid` attributes and handles `input` and `blur` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn bind<Ms: 'static>(
+        &self,
+        name: &str,
+        to_msg: impl FnOnce(Msg) -> Ms + Clone + 'static,
+    ) -> FieldBinding<Ms> {
+        let mut binding = self.binding(name, to_msg.clone());
+        binding.attrs.add(At::Value, self.value(name));
+        let field = name.to_owned();
+        binding
+            .event_handlers
+            .push(input_ev(Ev::Input, move |value| {
+                to_msg(Msg::Input { field, value })
+            }));
+        binding
+    }
+
+    /// Bind the field to a checkbox - it sets `name`, `checked` and `aria-invalid` attributes
+    /// and handles `change` and `blur` events. The field value is `"true"` or `"false"`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the field doesn't exist.
+    pub fn bind_checkbox<Ms: 'static>(
+        &self,
+        name: &str,
+        to_msg: impl FnOnce(Msg) -> Ms + Clone + 'static,
+    ) -> FieldBinding<Ms> {
+        let mut binding = self.binding(name, to_msg.clone());
+        binding
+            .attrs
+            .add(At::Checked, (self.value(name) == "true").as_at_value());
+        let field = name.to_owned();
+        binding.event_handlers.push(ev(Ev::Change, move |event| {
+            let checked = event
+                .target()
+                .and_then(|target| target.dyn_into::<web_sys::HtmlInputElement>().ok())
+                .map(|input| input.checked());
+            checked.map(|checked| {
+                to_msg(Msg::Input {
+                    field,
+                    value: checked.to_string(),
+                })
+            })
+        }));
+        binding
+    }
+
+    /// Bind the form to a `form` element - it handles `submit` events
+    /// and prevents their default action.
+    pub fn bind_submit<Ms: 'static>(
+        &self,
+        to_msg: impl FnOnce(Msg) -> Ms + Clone + 'static,
+    ) -> EventHandler<Ms> {
+        ev(Ev::Submit, move |event| {
+            event.prevent_default();
+            to_msg(Msg::Submit)
+        })
+    }
+
+    fn binding<Ms: 'static>(
+        &self,
+        name: &str,
+        to_msg: impl FnOnce(Msg) -> Ms + Clone + 'static,
+    ) -> FieldBinding<Ms> {
+        let mut attrs = Attrs::empty();
+        attrs.add(At::Name, name);
+        if self.error(name).is_some() {
+            attrs.add(At::from("aria-invalid"), "true");
+        }
+        let field = name.to_owned();
+        FieldBinding {
+            attrs,
+            event_handlers: vec![ev(Ev::Blur, move |_| to_msg(Msg::Blur { field }))],
+        }
+    }
+
+    // ------ Helpers ------
+
+    fn field_ref(&self, name: &str) -> &Field {
+        self.fields
+            .get(name)
+            .unwrap_or_else(|| panic!("form field '{}' doesn't exist", name))
+    }
+
+    fn field_mut(&mut self, name: &str) -> &mut Field {
+        self.fields
+            .get_mut(name)
+            .unwrap_or_else(|| panic!("form field '{}' doesn't exist", name))
+    }
+}
+
+impl<T: DeserializeOwned> Form<T> {
+    /// Returns the deserialized form once it has been submitted and all validators have passed.
+    ///
+    /// The form is `submitting` until `finish_submit` is called.
+    pub fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) -> Option<T> {
+        match msg {
+            Msg::Input { field: name, value } => {
+                let field = self.field_mut(&name);
+                field.value = value;
+                field.touched = true;
+                field.validate();
+                None
+            }
+            Msg::Blur { field: name } => {
+                let field = self.field_mut(&name);
+                field.touched = true;
+                if field.error.is_none() {
+                    field.validate_async(&name, orders);
+                }
+                None
+            }
+            Msg::Submit => {
+                if self.submitting {
+                    return None;
+                }
+                self.submit_attempted = true;
+                self.submit_error = None;
+                for (name, field) in &mut self.fields {
+                    field.validate();
+                    if field.error.is_none() {
+                        field.validate_async(name, orders);
+                    }
+                }
+                self.submit_requested = true;
+                self.try_submit()
+            }
+            Msg::AsyncValidated {
+                field: name,
+                result,
+            } => {
+                let field = self.field_mut(&name);
+                field.async_validation = None;
+                field.error = result.err();
+                self.try_submit()
+            }
+            Msg::Reset => {
+                self.reset();
+                None
+            }
+        }
+    }
+
+    fn try_submit(&mut self) -> Option<T> {
+        if !self.submit_requested
+            || self
+                .fields
+                .values()
+                .any(|field| field.async_validation.is_some())
+        {
+            return None;
+        }
+        self.submit_requested = false;
+        if !self.is_valid() {
+            return None;
+        }
+        match from_values(&self.values()) {
+            Ok(value) => {
+                self.submitting = true;
+                Some(value)
+            }
+            Err(error) => {
+                if let Some(field) = error
+                    .field
+                    .as_ref()
+                    .and_then(|name| self.fields.get_mut(name))
+                {
+                    field.error = Some(error.message.clone());
+                }
+                self.submit_error = Some(error.to_string());
+                None
+            }
+        }
+    }
+}
+
+// ------ Field ------
+
+struct Field {
+    value: String,
+    initial_value: String,
+    touched: bool,
+    error: Option<String>,
+    validators: Vec<Validator>,
+    async_validator: Option<AsyncValidator>,
+    // The running async validation is aborted on drop.
+    async_validation: Option<CmdHandle>,
+}
+
+impl Field {
+    fn is_dirty(&self) -> bool {
+        self.value != self.initial_value
+    }
+
+    /// Run sync validators and cancel the running async validation.
+    fn validate(&mut self) {
+        self.async_validation = None;
+        self.error = self
+            .validators
+            .iter()
+            .find_map(|validator| validator(&self.value).err());
+    }
+
+    fn validate_async(&mut self, name: &str, orders: &mut impl Orders<Msg>) {
+        if let Some(async_validator) = &self.async_validator {
+            let field = name.to_owned();
+            let validation = async_validator(self.value.clone());
+            self.async_validation = Some(orders.perform_cmd_with_handle(async move {
+                Msg::AsyncValidated {
+                    field,
+                    result: validation.await,
+                }
+            }));
+        }
+    }
+}
+
+impl fmt::Debug for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("value", &self.value)
+            .field("initial_value", &self.initial_value)
+            .field("touched", &self.touched)
+            .field("error", &self.error)
+            .field("validating", &self.async_validation.is_some())
+            .finish()
+    }
+}
+
+// ------ FieldBinding ------
+
+/// Attributes and event handlers of a bound form field - see `Form::bind`.
+#[allow(clippy::module_name_repetitions)]
+pub struct FieldBinding<Ms> {
+    attrs: Attrs,
+    event_handlers: Vec<EventHandler<Ms>>,
+}
+
+impl<Ms> UpdateEl<Ms> for FieldBinding<Ms> {
+    fn update_el(self, el: &mut El<Ms>) {
+        el.attrs.merge(self.attrs);
+        el.event_handler_manager
+            .add_event_handlers(self.event_handlers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, OrdersContainer};
+    use crate::virtual_dom::Node;
+    use serde::Deserialize;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Signup {
+        email: String,
+        age: u8,
+    }
+
+    fn orders() -> OrdersContainer<Msg, (), Node<Msg>> {
+        OrdersContainer::new(App::start(
+            "output",
+            |_, _| (),
+            |_, _, _| (),
+            |_| seed::empty(),
+        ))
+    }
+
+    fn validate_email(email: &str) -> Result<(), String> {
+        if email.contains('@') {
+            Ok(())
+        } else {
+            Err("Invalid email".to_owned())
+        }
+    }
+
+    fn input(field: &str, value: &str) -> Msg {
+        Msg::Input {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn field_state() {
+        let mut form = Form::<()>::new()
+            .field("email", "")
+            .validator("email", validate_email);
+        // The initial value is validated.
+        assert!(!form.is_valid());
+
+        form.set_value("email", "john");
+        assert!(form.is_dirty());
+        assert!(!form.is_valid());
+        // Untouched fields don't show errors.
+        assert_eq!(form.error("email"), None);
+
+        form.fields.get_mut("email").unwrap().touched = true;
+        assert_eq!(form.error("email"), Some("Invalid email"));
+
+        form.set_value("email", "john@example.com");
+        assert!(form.is_valid());
+
+        form.reset();
+        assert!(!form.is_dirty());
+        assert!(!form.is_touched("email"));
+        assert!(!form.is_valid());
+    }
+
+    #[wasm_bindgen_test]
+    fn submit() {
+        let mut orders = orders();
+        let mut form = Form::<Signup>::new()
+            .field("email", "john")
+            .field("age", "42")
+            .validator("email", validate_email);
+
+        // Invalid forms aren't submitted, but their errors are shown.
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+        assert_eq!(form.error("email"), Some("Invalid email"));
+        assert!(!form.is_submitting());
+
+        form.update(input("email", "john@example.com"), &mut orders);
+        assert_eq!(
+            form.update(Msg::Submit, &mut orders),
+            Some(Signup {
+                email: "john@example.com".to_owned(),
+                age: 42,
+            })
+        );
+        assert!(form.is_submitting());
+        // The form can't be submitted again until `finish_submit` is called.
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+
+        form.finish_submit();
+        assert!(form.update(Msg::Submit, &mut orders).is_some());
+    }
+
+    #[wasm_bindgen_test]
+    fn submit_with_async_validation() {
+        let mut orders = orders();
+        let mut form = Form::<Signup>::new()
+            .field("email", "john@example.com")
+            .field("age", "42")
+            .async_validator("email", |_| futures::future::pending());
+        let validated = |result: Result<(), &str>| Msg::AsyncValidated {
+            field: "email".to_owned(),
+            result: result.map_err(str::to_owned),
+        };
+
+        form.update(
+            Msg::Blur {
+                field: "email".to_owned(),
+            },
+            &mut orders,
+        );
+        assert!(form.is_validating("email"));
+
+        // The submit waits for the async validator.
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+        assert_eq!(
+            form.update(validated(Err("Email taken")), &mut orders),
+            None
+        );
+        assert_eq!(form.error("email"), Some("Email taken"));
+        assert!(!form.is_validating("email"));
+        assert!(!form.is_submitting());
+
+        // Changing the value cancels the running validation.
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+        form.update(input("email", "jane@example.com"), &mut orders);
+        assert!(!form.is_validating("email"));
+
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+        assert!(form.is_validating("email"));
+        assert_eq!(
+            form.update(validated(Ok(())), &mut orders),
+            Some(Signup {
+                email: "jane@example.com".to_owned(),
+                age: 42,
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn submit_with_deserialization_error() {
+        let mut orders = orders();
+        let mut form = Form::<Signup>::new()
+            .field("email", "john@example.com")
+            .field("age", "old");
+
+        assert_eq!(form.update(Msg::Submit, &mut orders), None);
+        assert_eq!(
+            form.error("age"),
+            Some("invalid value 'old', expected a positive integer")
+        );
+        assert_eq!(
+            form.submit_error(),
+            Some("field `age`: invalid value 'old', expected a positive integer")
+        );
+        assert!(!form.is_valid());
+        assert!(!form.is_submitting());
+
+        form.update(input("age", "42"), &mut orders);
+        assert!(form.update(Msg::Submit, &mut orders).is_some());
+        assert_eq!(form.submit_error(), None);
+    }
+}
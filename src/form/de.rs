@@ -0,0 +1,383 @@
+//! Deserialization of form values - field names with string values - into `serde` structs.
+
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Form values - a field name with all its values (e.g. for checkboxes with the same `name`).
+pub type FieldValues = BTreeMap<String, Vec<String>>;
+
+/// Deserialize `values` into `T`.
+///
+/// - Single values are parsed from the last field value - e.g. `"42"` into `u8`.
+/// - Sequences (e.g. `Vec<String>`) are deserialized from all field values.
+/// - `bool`s accept `true`/`false`, `on`/`off`, `yes`/`no`, `1`/`0` and the empty string as `false`.
+/// - `Option`s are `None` for missing fields and empty strings.
+/// - Unit enum variants are deserialized from their names.
+///
+/// _Note:_ Browsers don't send unchecked checkboxes - use `#[serde(default)]` for such `bool` fields.
+///
+/// # Errors
+///
+/// Returns `DeError` with the name of the failing field (if it's known).
+pub fn from_values<T: DeserializeOwned>(values: &FieldValues) -> Result<T, DeError> {
+    T::deserialize(FieldsDeserializer(values))
+}
+
+// ------ DeError ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeError {
+    /// The name of the field that couldn't be deserialized.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl DeError {
+    fn in_field(mut self, field: &str) -> Self {
+        if self.field.is_none() {
+            self.field = Some(field.to_owned());
+        }
+        self
+    }
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "field `{}`: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self {
+            field: None,
+            message: message.to_string(),
+        }
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self {
+            field: Some(field.to_owned()),
+            message: "missing value".to_owned(),
+        }
+    }
+}
+
+// ------ FieldsDeserializer ------
+
+struct FieldsDeserializer<'a>(&'a FieldValues);
+
+impl<'de, 'a> de::Deserializer<'de> for FieldsDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(FieldsAccess {
+            fields: self.0.iter(),
+            field: None,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct FieldsAccess<'a> {
+    fields: std::collections::btree_map::Iter<'a, String, Vec<String>>,
+    field: Option<(&'a String, &'a Vec<String>)>,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for FieldsAccess<'a> {
+    type Error = DeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        self.field = self.fields.next();
+        match self.field {
+            Some((name, _)) => seed
+                .deserialize(name.as_str().into_deserializer())
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, DeError> {
+        let (name, values) = self
+            .field
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+        seed.deserialize(ValuesDeserializer(values))
+            .map_err(|error| error.in_field(name))
+    }
+}
+
+// ------ ValuesDeserializer ------
+
+/// Deserializes all values of one field.
+struct ValuesDeserializer<'a>(&'a [String]);
+
+impl<'a> ValuesDeserializer<'a> {
+    fn last(&self) -> Result<ValueDeserializer<'a>, DeError> {
+        self.0
+            .last()
+            .map(|value| ValueDeserializer(value))
+            .ok_or_else(|| de::Error::custom("missing value"))
+    }
+}
+
+macro_rules! forward_to_last_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                self.last()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ValuesDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.0.len() > 1 {
+            self.deserialize_seq(visitor)
+        } else {
+            self.last()?.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.0 {
+            [] => visitor.visit_none(),
+            [value] if value.is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            self.0.iter().map(|value| ValueDeserializer(value)),
+        ))
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.last()?.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_last_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_f32
+        deserialize_f64 deserialize_char deserialize_str deserialize_string deserialize_unit
+        deserialize_identifier
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 bytes byte_buf unit_struct tuple_struct map struct ignored_any
+    }
+}
+
+// ------ ValueDeserializer ------
+
+/// Deserializes one field value.
+struct ValueDeserializer<'a>(&'a str);
+
+impl<'a> ValueDeserializer<'a> {
+    fn parse<T: std::str::FromStr>(&self, expected: &str) -> Result<T, DeError> {
+        self.0.trim().parse().map_err(|_| {
+            de::Error::custom(format!("invalid value '{}', expected {}", self.0, expected))
+        })
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident, $expected:literal;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                visitor.$visit(self.parse($expected)?)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> IntoDeserializer<'de, DeError> for ValueDeserializer<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_str(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        match self.0.trim() {
+            "true" | "on" | "yes" | "1" => visitor.visit_bool(true),
+            "false" | "off" | "no" | "0" | "" => visitor.visit_bool(false),
+            _ => Err(de::Error::custom(format!(
+                "invalid value '{}', expected a boolean",
+                self.0
+            ))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8, "an integer";
+        deserialize_i16 => visit_i16, "an integer";
+        deserialize_i32 => visit_i32, "an integer";
+        deserialize_i64 => visit_i64, "an integer";
+        deserialize_u8 => visit_u8, "a positive integer";
+        deserialize_u16 => visit_u16, "a positive integer";
+        deserialize_u32 => visit_u32, "a positive integer";
+        deserialize_u64 => visit_u64, "a positive integer";
+        deserialize_f32 => visit_f32, "a number";
+        deserialize_f64 => visit_f64, "a number";
+        deserialize_char => visit_char, "a character";
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum Plan {
+        Free,
+        Pro,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Signup {
+        email: String,
+        age: u8,
+        #[serde(default)]
+        newsletter: bool,
+        nickname: Option<String>,
+        plan: Plan,
+        tags: Vec<String>,
+    }
+
+    fn values(pairs: &[(&str, &str)]) -> FieldValues {
+        let mut values = FieldValues::new();
+        for (name, value) in pairs {
+            values
+                .entry((*name).to_owned())
+                .or_default()
+                .push((*value).to_owned());
+        }
+        values
+    }
+
+    #[wasm_bindgen_test]
+    fn deserialize_struct() {
+        let signup = from_values::<Signup>(&values(&[
+            ("email", "john@example.com"),
+            ("age", "42"),
+            ("newsletter", "on"),
+            ("nickname", ""),
+            ("plan", "pro"),
+            ("tags", "rust"),
+            ("tags", "wasm"),
+            ("unknown", "ignored"),
+        ]));
+        assert_eq!(
+            signup,
+            Ok(Signup {
+                email: "john@example.com".to_owned(),
+                age: 42,
+                newsletter: true,
+                nickname: None,
+                plan: Plan::Pro,
+                tags: vec!["rust".to_owned(), "wasm".to_owned()],
+            })
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn deserialize_errors() {
+        let error =
+            from_values::<Signup>(&values(&[("email", "john@example.com"), ("age", "old")]))
+                .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("age"));
+
+        let error = from_values::<Signup>(&values(&[("age", "42")])).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("email"));
+    }
+}
@@ -0,0 +1,80 @@
+//! Serialization of `serde` structs into form values - the reverse of `form::de`.
+
+use super::FieldValues;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// Serialize `value` into field values.
+///
+/// - Strings, numbers and `bool`s (`"true"` / `"false"`) are serialized into one field value.
+/// - `None`s are serialized into the empty string.
+/// - Sequences are serialized into multiple field values.
+/// - Unit enum variants are serialized into their names.
+///
+/// # Errors
+///
+/// Returns `SerError` when `value` isn't a struct or a map,
+/// or when it contains nested structs or maps.
+pub fn to_values<T: Serialize>(value: &T) -> Result<FieldValues, SerError> {
+    let fields = match serde_json::to_value(value) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => return Err(SerError::new(None, "only structs and maps are supported")),
+        Err(error) => return Err(SerError::new(None, &error.to_string())),
+    };
+    fields
+        .into_iter()
+        .map(|(name, value)| {
+            let values = match value {
+                Value::Array(items) => items
+                    .into_iter()
+                    .map(|item| scalar_to_string(&name, item))
+                    .collect::<Result<_, _>>()?,
+                value => vec![scalar_to_string(&name, value)?],
+            };
+            Ok((name, values))
+        })
+        .collect()
+}
+
+fn scalar_to_string(field: &str, value: Value) -> Result<String, SerError> {
+    match value {
+        Value::Null => Ok(String::new()),
+        Value::Bool(value) => Ok(value.to_string()),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::String(value) => Ok(value),
+        Value::Array(_) | Value::Object(_) => Err(SerError::new(
+            Some(field),
+            "nested sequences and structs are not supported",
+        )),
+    }
+}
+
+// ------ SerError ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerError {
+    /// The name of the field that couldn't be serialized.
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl SerError {
+    fn new(field: Option<&str>, message: &str) -> Self {
+        Self {
+            field: field.map(ToOwned::to_owned),
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for SerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "field `{}`: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for SerError {}
@@ -0,0 +1,312 @@
+//! Translations with [Fluent](https://projectfluent.org/).
+//! _Note:_ Requires the `i18n` feature.
+//!
+//! - `seed::i18n_messages!` embeds FTL files and generates the `t!` macro with compile-time
+//!   checks of message ids and argument names.
+//! - `I18n` negotiates the locale from `navigator.languages`, persists the user's choice
+//!   in `LocalStorage` and notifies `subs::LanguageChanged` on switch.
+//! - `I18n::intl` formats numbers, dates and lists for the current locale
+//!   - see `seed::browser::intl`.
+//! - Messages missing in the current locale are translated by the next locale
+//!   in the fallback chain (e.g. `de-AT` -> `de-DE` -> `en-US`).
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!// Files `ftl_messages/en-US.ftl` and `ftl_messages/de-DE.ftl`.
+//!seed::i18n_messages!("ftl_messages", fallback = "en-US");
+//!
+//!fn init(_: Url, orders: &mut impl Orders<Msg>) -> Model {
+//!    orders.subscribe(Msg::LanguageChanged);
+//!    Model { i18n: I18n::new(FTL_RESOURCES) }
+//!}
+//!
+//!fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+//!    match msg {
+//!        Msg::SwitchLanguage(locale) => model.i18n.set_locale(locale, orders),
+//!        Msg::LanguageChanged(subs::LanguageChanged(locale)) => log!("Language:", locale),
+//!    }
+//!}
+//!
+//!fn view(model: &Model) -> Node<Msg> {
+//!    div![
+//!        p![t!(model.i18n, "hello-world")],
+//!        p![t!(model.i18n, "hello-user", userName = "Anna")],
+//!    ]
+//!}
+//! ```
+
+use crate::app::{subs, Orders};
+use crate::browser::intl::Intl;
+use crate::browser::util::{document, window};
+use crate::browser::web_storage::{LocalStorage, WebStorage};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use std::fmt;
+
+pub use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+pub use unic_langid::LanguageIdentifier;
+
+/// `LocalStorage` key of the locale chosen by `I18n::set_locale`.
+pub const STORAGE_KEY: &str = "seed-i18n-locale";
+
+// ------ Resources ------
+
+/// FTL files embedded by `seed::i18n_messages!` - see the generated constant `FTL_RESOURCES`.
+#[derive(Debug, Copy, Clone)]
+pub struct Resources {
+    /// The locale with all messages - the last one in the fallback chain.
+    pub fallback_locale: &'static str,
+    /// Locale and FTL source pairs.
+    pub files: &'static [(&'static str, &'static str)],
+}
+
+// ------ I18n ------
+
+/// Translations for the current locale.
+pub struct I18n {
+    bundles: Vec<FluentBundle<FluentResource>>,
+    locales: Vec<LanguageIdentifier>,
+    fallback_locale: LanguageIdentifier,
+    /// Indices into `bundles` - the current locale first.
+    fallback_chain: Vec<usize>,
+    intl: Intl,
+}
+
+impl fmt::Debug for I18n {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I18n")
+            .field("locale", self.locale())
+            .field("locales", &self.locales)
+            .field("fallback_locale", &self.fallback_locale)
+            .finish()
+    }
+}
+
+impl I18n {
+    /// Create `I18n` with the locale stored by `set_locale` or negotiated from `navigator.languages`.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid locale names or FTL files with syntax errors.
+    pub fn new(resources: Resources) -> Self {
+        let mut i18n = Self::with_locale(resources, &[]);
+        let requested = match LocalStorage::get::<String>(STORAGE_KEY) {
+            Ok(locale) => vec![locale],
+            Err(_) => navigator_languages(),
+        };
+        let requested = requested
+            .iter()
+            .filter_map(|locale| locale.parse().ok())
+            .collect::<Vec<LanguageIdentifier>>();
+        i18n.negotiate(&requested);
+        i18n
+    }
+
+    /// Create `I18n` with the best match for the requested locales - browser settings
+    /// and the stored choice are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics on invalid locale names or FTL files with syntax errors.
+    pub fn with_locale(resources: Resources, requested: &[LanguageIdentifier]) -> Self {
+        let parse_locale = |locale: &str| -> LanguageIdentifier {
+            locale
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid locale `{}`", locale))
+        };
+        let mut bundles = Vec::with_capacity(resources.files.len());
+        let mut locales = Vec::with_capacity(resources.files.len());
+        for (locale, source) in resources.files {
+            let locale = parse_locale(locale);
+            let resource = FluentResource::try_new((*source).to_owned())
+                .unwrap_or_else(|(_, errors)| panic!("invalid FTL `{}`: {:?}", locale, errors));
+            let mut bundle = FluentBundle::new(std::slice::from_ref(&locale));
+            bundle
+                .add_resource(resource)
+                .unwrap_or_else(|errors| panic!("invalid FTL `{}`: {:?}", locale, errors));
+            bundles.push(bundle);
+            locales.push(locale);
+        }
+        let mut i18n = Self {
+            bundles,
+            locales,
+            fallback_locale: parse_locale(resources.fallback_locale),
+            fallback_chain: Vec::new(),
+            intl: Intl::new(resources.fallback_locale),
+        };
+        i18n.negotiate(requested);
+        i18n
+    }
+
+    /// The current locale.
+    pub fn locale(&self) -> &LanguageIdentifier {
+        self.fallback_chain
+            .first()
+            .map_or(&self.fallback_locale, |index| &self.locales[*index])
+    }
+
+    /// Locales with FTL files.
+    pub fn available_locales(&self) -> &[LanguageIdentifier] {
+        &self.locales
+    }
+
+    /// Number, date, relative time and list formatting for the current locale.
+    pub fn intl(&self) -> &Intl {
+        &self.intl
+    }
+
+    /// Switch to the best match for `locale`, store the choice into `LocalStorage`
+    /// and notify `subs::LanguageChanged`.
+    pub fn set_locale<Ms: 'static>(
+        &mut self,
+        locale: LanguageIdentifier,
+        orders: &mut impl Orders<Ms>,
+    ) {
+        self.negotiate(&[locale]);
+        let locale = self.locale().clone();
+        if let Err(error) = LocalStorage::insert(STORAGE_KEY, &locale.to_string()) {
+            crate::error!("I18n: cannot store the locale", error);
+        }
+        orders.notify(subs::LanguageChanged(locale));
+    }
+
+    /// Translate the message - prefer the macro `t!` generated by `seed::i18n_messages!`.
+    ///
+    /// Returns `key` when the message isn't in any locale of the fallback chain.
+    pub fn translate(&self, key: impl AsRef<str>, args: Option<&FluentArgs>) -> String {
+        let key = key.as_ref();
+        for index in &self.fallback_chain {
+            let bundle = &self.bundles[*index];
+            if let Some(pattern) = bundle.get_message(key).and_then(|message| message.value) {
+                let mut errors = Vec::new();
+                let text = bundle.format_pattern(pattern, args, &mut errors);
+                if !errors.is_empty() {
+                    crate::error!("I18n: message formatting errors", key, errors);
+                }
+                return text.into_owned();
+            }
+        }
+        key.to_owned()
+    }
+
+    fn negotiate(&mut self, requested: &[LanguageIdentifier]) {
+        let mut chain = negotiate_languages(
+            requested,
+            &self.locales,
+            Some(&self.fallback_locale),
+            NegotiationStrategy::Filtering,
+        )
+        .into_iter()
+        .filter_map(|locale| self.locales.iter().position(|l| l == locale))
+        .collect::<Vec<_>>();
+        if let Some(index) = self.locales.iter().position(|l| l == &self.fallback_locale) {
+            if !chain.contains(&index) {
+                chain.push(index);
+            }
+        }
+        self.fallback_chain = chain;
+        let locale = self.locale().to_string();
+        set_document_lang(&locale);
+        if self.intl.locale() != locale {
+            self.intl = Intl::new(locale);
+        }
+    }
+}
+
+fn navigator_languages() -> Vec<String> {
+    window()
+        .navigator()
+        .languages()
+        .iter()
+        .filter_map(|language| language.as_string())
+        .collect()
+}
+
+/// Set `<html lang="..">` for screen readers and browser features like spell checking.
+fn set_document_lang(locale: &str) {
+    if let Some(html) = document().document_element() {
+        let _ = html.set_attribute("lang", locale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const RESOURCES: Resources = Resources {
+        fallback_locale: "en-US",
+        files: &[
+            (
+                "en-US",
+                "hello = Hello!\nhello-user = Hello, {$userName}!\nonly-english = Only English.\n",
+            ),
+            (
+                "de-DE",
+                "hello = Hallo!\nhello-user = Hallo, {$userName}!\nphotos = {$count ->\n    [one] ein Foto\n   *[other] {$count} Fotos\n}\n",
+            ),
+            ("fr-FR", "hello = Bonjour !\n"),
+        ],
+    };
+
+    fn locale(locale: &str) -> LanguageIdentifier {
+        locale.parse().unwrap()
+    }
+
+    fn fallback_chain(i18n: &I18n) -> Vec<String> {
+        i18n.fallback_chain
+            .iter()
+            .map(|index| i18n.locales[*index].to_string())
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn negotiate_fallback_chain() {
+        let i18n = I18n::with_locale(RESOURCES, &[locale("de-AT")]);
+        assert_eq!(i18n.locale(), &locale("de-DE"));
+        assert_eq!(fallback_chain(&i18n), vec!["de-DE", "en-US"]);
+
+        let i18n = I18n::with_locale(RESOURCES, &[locale("cs-CZ")]);
+        assert_eq!(i18n.locale(), &locale("en-US"));
+        assert_eq!(fallback_chain(&i18n), vec!["en-US"]);
+
+        let i18n = I18n::with_locale(RESOURCES, &[]);
+        assert_eq!(fallback_chain(&i18n), vec!["en-US"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn translate_with_fallback() {
+        let i18n = I18n::with_locale(RESOURCES, &[locale("de-AT")]);
+        assert_eq!(i18n.translate("hello", None), "Hallo!");
+        // Missing in `de-DE`.
+        assert_eq!(i18n.translate("only-english", None), "Only English.");
+        // Missing in all locales.
+        assert_eq!(i18n.translate("missing-key", None), "missing-key");
+    }
+
+    #[wasm_bindgen_test]
+    fn translate_with_args() {
+        let i18n = I18n::with_locale(RESOURCES, &[locale("de-DE")]);
+
+        let mut args = FluentArgs::new();
+        args.add("userName", FluentValue::from("Anna"));
+        // Fluent wraps placeables in Unicode isolation marks.
+        assert_eq!(
+            i18n.translate("hello-user", Some(&args)),
+            "Hallo, \u{2068}Anna\u{2069}!"
+        );
+
+        let mut args = FluentArgs::new();
+        args.add("count", FluentValue::from(1));
+        assert_eq!(i18n.translate("photos", Some(&args)), "ein Foto");
+
+        let mut args = FluentArgs::new();
+        args.add("count", FluentValue::from(3));
+        assert_eq!(
+            i18n.translate("photos", Some(&args)),
+            "\u{2068}3\u{2069} Fotos"
+        );
+    }
+}
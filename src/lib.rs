@@ -101,12 +101,24 @@ pub use crate::{
         self, body, canvas, canvas_context_2d, cookies, document, error, history, html_document,
         log, window,
     },
+    shortcuts::{html, svg},
     virtual_dom::{Attrs, EventHandler, Style},
 };
 
+// Allows to use `::seed::..` paths generated by `seed-macros` also inside this crate.
+extern crate self as seed;
+
 #[cfg(feature = "panic-hook")]
 pub use console_error_panic_hook;
 
+#[cfg(feature = "markdown")]
+pub use pulldown_cmark;
+
+pub use seed_macros::{html, include_html};
+
+#[cfg(feature = "i18n")]
+pub use seed_macros::i18n_messages;
+
 pub use futures::{
     self,
     future::{self, FutureExt, TryFutureExt},
@@ -119,7 +131,10 @@ pub mod shortcuts;
 pub mod app;
 pub mod browser;
 pub mod dom_entity_names;
+pub mod form;
 pub mod helpers;
+#[cfg(feature = "i18n")]
+pub mod i18n;
 pub mod virtual_dom;
 
 /// Create an element flagged in a way that it will not be rendered. Useful
@@ -174,17 +189,25 @@ pub fn set_timeout(handler: Box<dyn Fn()>, timeout: i32) {
 /// for element-creation macros, input event constructors, and the `History` struct.
 /// Expose the `wasm_bindgen` prelude.
 pub mod prelude {
+    #[cfg(feature = "i18n")]
+    pub use crate::i18n::{I18n, LanguageIdentifier};
+    #[cfg(feature = "markdown")]
+    pub use crate::virtual_dom::Markdown;
     pub use crate::{
         app::{
-            cmds, streams, subs, App, CmdHandle, GetElement, MessageMapper, Orders, RenderInfo,
-            StreamHandle, SubHandle,
+            cmds, streams, streams::RateLimitStreamExt, subs, App, CmdHandle, GetElement,
+            MessageMapper, NavigationDecision, NavigationGuardHandle, NavigationKind,
+            NavigationRequest, Orders, PatchStats, RenderInfo, ScrollRestoration, StreamHandle,
+            SubHandle,
         },
         browser::dom::css_units::*,
         browser::dom::event_handler::{
-            drag_ev, ev, input_ev, keyboard_ev, mouse_ev, pointer_ev, raw_ev, simple_ev, touch_ev,
+            drag_ev, ev, hotkey_ev, hotkey_ev_with_options, input_ev, keyboard_ev, mouse_ev,
+            pointer_ev, raw_ev, simple_ev, touch_ev,
         },
-        browser::dom::Namespace,
+        browser::dom::{focus_trap, Hotkey, HotkeyOptions, Namespace, SavedFocus},
         browser::fetch::{self, fetch, FetchError, Header, Method, Request, Response, Status},
+        browser::intl::{self, DateTimeStyle, Intl, ListType, RelativeTimeUnit},
         browser::util::{
             request_animation_frame, ClosureNew, RequestAnimationFrameHandle,
             RequestAnimationFrameTime,
@@ -197,8 +220,10 @@ pub mod prelude {
         // https://github.com/rust-lang-nursery/reference/blob/master/src/macros-by-example.md
         shortcuts::*,
         virtual_dom::{
-            el_key, el_ref::el_ref, AsAtValue, At, AtValue, CSSValue, El, ElRef, Ev, EventHandler,
-            IntoNodes, Node, St, Tag, ToClasses, UpdateEl, UpdateElForIterator, View,
+            animate_moves, el_key, el_ref::el_ref, A11yRule, A11yRules, AnimateMoves, Aria,
+            AsAtValue, At, AtValue, CSSValue, El, ElBuilder, ElRef, Ev, EventHandler, IntoNodes,
+            ListenerOptions, Node, Politeness, RateLimit, Role, RowHeight, Sanitizer, St, Tag,
+            ToClasses, UpdateEl, UpdateElForIterator, View, VirtualList,
         },
     };
     pub use indexmap::IndexMap; // for attrs and style to work.
@@ -263,4 +288,130 @@ pub mod tests {
             seed::App::start("render test app", |_, _| Model::default(), update, view);
         }
     }
+
+    #[wasm_bindgen_test]
+    fn html_macro() {
+        use crate::prelude::*;
+
+        #[derive(Debug)]
+        enum Msg {
+            Clicked,
+        }
+
+        let items = vec!["a", "b"];
+        let show_more = false;
+        let input_ref = ElRef::<web_sys::HtmlInputElement>::default();
+
+        let node: Node<Msg> = crate::html! {
+            <div class="list" data-count={items.len()} aria-busy="false">
+                <input ref={input_ref} type="checkbox" checked />
+                <button @click={|_| Msg::Clicked}>"Click"</button>
+                for item in &items {
+                    <span key={item}>{*item}</span>
+                }
+                if show_more {
+                    <p>"More"</p>
+                } else {
+                    "Less"
+                }
+                <svg viewBox="0 0 10 10"><circle r="5" /></svg>
+            </div>
+        };
+
+        let el = match node {
+            Node::Element(el) => el,
+            _ => unreachable!(),
+        };
+        assert_eq!(el.tag, Tag::Div);
+        assert_eq!(
+            el.attrs.vals.get(&At::from("data-count")),
+            Some(&AtValue::Some("2".to_owned()))
+        );
+        assert_eq!(el.children.len(), 6);
+        assert!(
+            matches!(&el.children[0], Node::Element(input) if input.attrs.vals.get(&At::Checked) == Some(&AtValue::None))
+        );
+        assert!(matches!(&el.children[4], Node::Text(text) if text.text == "Less"));
+        assert!(
+            matches!(&el.children[5], Node::Element(svg) if svg.namespace == Some(Namespace::Svg))
+        );
+
+        let nodes: Vec<Node<Msg>> = crate::html! { <br> "text" };
+        assert_eq!(nodes.len(), 2);
+
+        let count = 3;
+        let name = String::from("Anna");
+        let nodes: Vec<Node<Msg>> = crate::html! { <p /> {count} {name} };
+        assert_eq!(nodes.len(), 3);
+        assert!(matches!(&nodes[1], Node::Text(text) if text.text == "3"));
+        assert!(matches!(&nodes[2], Node::Text(text) if text.text == "Anna"));
+    }
+
+    #[wasm_bindgen_test]
+    fn include_html_macro() {
+        use crate::prelude::*;
+
+        #[derive(Debug)]
+        enum Msg {
+            Clicked,
+        }
+
+        let node: Node<Msg> = crate::include_html!(
+            "tests/fixtures/icon.svg",
+            size = 24,
+            class = "close",
+            parts = ev(Ev::Click, |_| Msg::Clicked),
+            title = "Close",
+        );
+
+        let svg = match node {
+            Node::Element(svg) => svg,
+            _ => unreachable!(),
+        };
+        assert_eq!(svg.namespace, Some(Namespace::Svg));
+        assert_eq!(
+            svg.attrs.vals.get(&At::Width),
+            Some(&AtValue::Some("24".to_owned()))
+        );
+        assert_eq!(
+            svg.attrs.vals.get(&At::Class),
+            Some(&AtValue::Some("icon close".to_owned()))
+        );
+        assert_eq!(svg.children.len(), 3);
+        assert!(matches!(&svg.children[0], Node::Element(title) if title.children.len() == 1));
+
+        let foreign_object = match &svg.children[2] {
+            Node::Element(foreign_object) => foreign_object,
+            _ => unreachable!(),
+        };
+        assert_eq!(foreign_object.namespace, Some(Namespace::Svg));
+        assert!(matches!(
+            &foreign_object.children[0],
+            Node::Element(p) if p.namespace.is_none()
+                && matches!(&p.children[0], Node::Text(text) if text.text == "Close & exit")
+        ));
+    }
+
+    #[wasm_bindgen_test]
+    fn include_html_placeholder_in_child_and_attribute() {
+        use crate::prelude::*;
+
+        // `{{name}}` is used as a child before its use in an attribute value.
+        let name = String::from("x");
+        let node: Node<()> =
+            crate::include_html!("tests/fixtures/child_and_attribute.html", name = name);
+
+        let div = match node {
+            Node::Element(div) => div,
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            &div.children[0],
+            Node::Element(p) if matches!(&p.children[0], Node::Text(text) if text.text == "x")
+        ));
+        assert!(matches!(
+            &div.children[1],
+            Node::Element(span) if span.attrs.vals.get(&At::Title) == Some(&AtValue::Some("x".to_owned()))
+        ));
+    }
 }
@@ -142,6 +142,32 @@ macro_rules! element {
                 )+
             }
         }
+
+        /// Builder functions for HTML elements - e.g. `div().class("x").child(span())`.
+        /// See `virtual_dom::ElBuilder`.
+        pub mod html {
+            use crate::virtual_dom::{El, ElBuilder, Tag};
+
+            /// Tag markers of `ElBuilder` for tag-specific methods.
+            pub mod tag {
+                $(
+                    #[derive(Debug, Copy, Clone)]
+                    pub struct $Tag_camel;
+                )+
+            }
+
+            $(
+                #[allow(non_snake_case)]
+                pub fn $Tag<Ms>() -> ElBuilder<Ms, tag::$Tag_camel> {
+                    ElBuilder::new(El::empty(Tag::$Tag_camel))
+                }
+            )+
+
+            /// Custom element - e.g. `custom("code-block")`.
+            pub fn custom<Ms>(tag: impl Into<std::borrow::Cow<'static, str>>) -> ElBuilder<Ms> {
+                ElBuilder::new(El::empty(Tag::Custom(tag.into())))
+            }
+        }
    }
 }
 /// Similar to the element! macro above, but with a namespace for svg.
@@ -166,6 +192,27 @@ macro_rules! element_svg {
                 )+
             }
         }
+
+        /// Builder functions for SVG elements - e.g. `svg().view_box("0 0 24 24").child(path().d("M0 0"))`.
+        /// See `virtual_dom::ElBuilder`.
+        pub mod svg {
+            use crate::virtual_dom::{El, ElBuilder, Tag};
+
+            /// Tag markers of `ElBuilder` for tag-specific methods.
+            pub mod tag {
+                $(
+                    #[derive(Debug, Copy, Clone)]
+                    pub struct $Tag_camel;
+                )+
+            }
+
+            $(
+                #[allow(non_snake_case)]
+                pub fn $Tag<Ms>() -> ElBuilder<Ms, tag::$Tag_camel> {
+                    ElBuilder::new(El::empty_svg(Tag::$Tag_camel))
+                }
+            )+
+        }
    }
 }
 
@@ -213,7 +260,7 @@ element! {
 element_svg! {
     // SVG shape elements
     line_ => Line;  // line is a builtin rust macro.
-    rect => Rect; circle => Circle; ellipse => Elipse; polygon => Polygon; polyline => Polyline;
+    rect => Rect; circle => Circle; ellipse => Ellipse; polygon => Polygon; polyline => Polyline;
     mesh => Mesh; path => Path;
     // SVG container elements
     defs => Defs; g => G; marker => Marker; mask => Mask;
@@ -283,6 +330,29 @@ macro_rules! md {
     };
 }
 
+/// Create nodes from an untrusted HTML string - sanitized by `Sanitizer::default()` or the given `Sanitizer`.
+#[macro_export]
+macro_rules! raw_safe {
+    ($raw_html:expr) => {
+        $crate::virtual_dom::Sanitizer::default().sanitize_html($raw_html)
+    };
+    ($raw_html:expr, $sanitizer:expr) => {
+        $crate::virtual_dom::Sanitizer::sanitize_html($sanitizer, $raw_html)
+    };
+}
+
+/// Create nodes from an untrusted markdown string - sanitized by `Sanitizer::default()` or the given `Sanitizer`.
+/// _Note:_ Requires the `markdown` feature.
+#[macro_export]
+macro_rules! md_safe {
+    ($md:expr) => {
+        $crate::virtual_dom::Sanitizer::default().sanitize_markdown($md)
+    };
+    ($md:expr, $sanitizer:expr) => {
+        $crate::virtual_dom::Sanitizer::sanitize_markdown($sanitizer, $md)
+    };
+}
+
 #[macro_export]
 macro_rules! plain {
     ($text:expr) => {
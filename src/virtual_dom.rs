@@ -1,25 +1,43 @@
+pub mod a11y;
+pub mod aria;
 pub mod attrs;
+pub mod builder;
 pub mod el_ref;
 pub mod event_handler_manager;
+#[cfg(feature = "native-html-parser")]
+mod html_parser;
 pub mod mailbox;
+#[cfg(feature = "markdown")]
+pub mod markdown;
 pub mod node;
 pub mod patch;
+pub mod sanitize;
 pub mod style;
 pub mod to_classes;
 pub mod update_el;
 pub mod values;
 pub mod view;
+pub mod virtual_list;
 
+pub use a11y::{A11yIssue, A11yRule, A11yRules};
+pub use aria::{Aria, Politeness, Role};
 pub use attrs::Attrs;
+pub use builder::ElBuilder;
 pub use el_ref::{el_ref, ElRef, SharedNodeWs};
-pub use event_handler_manager::{EventHandler, EventHandlerManager, Listener};
+pub use event_handler_manager::{
+    EventHandler, EventHandlerManager, Listener, ListenerOptions, RateLimit, RateLimitKind,
+};
 pub use mailbox::Mailbox;
-pub use node::{el_key, El, ElKey, IntoNodes, Node, Text};
+#[cfg(feature = "markdown")]
+pub use markdown::Markdown;
+pub use node::{animate_moves, el_key, AnimateMoves, El, ElKey, IntoNodes, Node, Text};
+pub use sanitize::Sanitizer;
 pub use style::Style;
 pub use to_classes::ToClasses;
 pub use update_el::{UpdateEl, UpdateElForIterator};
 pub use values::{AsAtValue, AtValue, CSSValue};
 pub use view::View;
+pub use virtual_list::{RowHeight, VirtualList};
 
 pub use crate::dom_entity_names::{At, Ev, St, Tag};
 
@@ -40,6 +58,14 @@ pub mod tests {
 
     wasm_bindgen_test_configure!(run_in_browser);
 
+    /// The element in the `node` - shared by tests of modules creating nodes.
+    pub(crate) fn el<Ms>(node: &Node<Ms>) -> &El<Ms> {
+        match node {
+            Node::Element(el) => el,
+            _ => panic!("expected element"),
+        }
+    }
+
     #[derive(Clone, Debug)]
     enum Msg {}
 
@@ -756,6 +782,66 @@ pub mod tests {
         }
     }
 
+    /// Test that the moved keyed elements are animated when `animate_moves` is set.
+    #[wasm_bindgen_test]
+    fn el_key_animate_moves() {
+        let app = create_app();
+        let mailbox = Mailbox::new(|_msg: Option<Msg>| {});
+
+        let doc = util::document();
+        let parent = doc.create_element("div").unwrap();
+        // Elements have to be in the document to have layout.
+        util::body().append_child(&parent).unwrap();
+
+        let mut vdom = Node::Element(El::empty(Tag::Div));
+        virtual_dom_bridge::assign_ws_nodes(&doc, &mut vdom);
+        if let Node::Element(vdom_el) = vdom.clone() {
+            let old_ws = vdom_el.node_ws.as_ref().unwrap().clone();
+            parent.append_child(&old_ws).unwrap();
+
+            vdom = call_patch(
+                &doc,
+                &parent,
+                &mailbox,
+                vdom,
+                div![
+                    animate_moves().duration(100).easing("linear"),
+                    li![el_key(&"first"), "first"],
+                    li![el_key(&"last"), "last"],
+                ],
+                &app,
+            );
+
+            call_patch(
+                &doc,
+                &parent,
+                &mailbox,
+                vdom,
+                div![
+                    animate_moves().duration(100).easing("linear"),
+                    li![el_key(&"last"), "last"],
+                    li![el_key(&"first"), "first"],
+                ],
+                &app,
+            );
+
+            let first = old_ws
+                .child_nodes()
+                .item(1)
+                .unwrap()
+                .dyn_into::<web_sys::HtmlElement>()
+                .unwrap();
+            assert_eq!(first.text_content().unwrap(), "first");
+            assert_eq!(
+                first.style().get_property_value("transition").unwrap(),
+                "transform 100ms linear"
+            );
+        } else {
+            panic!("Node not Element")
+        }
+        parent.remove();
+    }
+
     /// Tests an update() function that repeatedly sends messages or performs commands.
     #[wasm_bindgen_test(async)]
     async fn update_promises() {
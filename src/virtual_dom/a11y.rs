@@ -0,0 +1,424 @@
+//! Accessibility linting of the virtual DOM.
+//!
+//! See `App::enable_a11y_lint` to check the app after each render in debug builds.
+
+use super::{AtValue, El, Node, Role};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+// ------ A11yRule ------
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum A11yRule {
+    /// `img` without `alt`.
+    ImgAlt,
+    /// `button` or link without text, `aria-label`, `aria-labelledby` or `title`.
+    AccessibleName,
+    /// `input`, `select` or `textarea` without `label` or `aria-label`.
+    FormLabel,
+    /// Unknown `aria-*` attribute or `role`.
+    InvalidAria,
+    /// More elements with the same `id`.
+    DuplicateId,
+}
+
+// ------ A11yRules ------
+
+/// Enabled lint rules. All rules are enabled by default.
+///
+/// # Example
+///
+/// ```rust,no_run
+///App::start("app", init, update, view)
+///    .enable_a11y_lint(A11yRules::default().disable(A11yRule::FormLabel));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct A11yRules {
+    disabled: BTreeSet<A11yRule>,
+}
+
+impl A11yRules {
+    /// Disable the `rule`.
+    pub fn disable(mut self, rule: A11yRule) -> Self {
+        self.disabled.insert(rule);
+        self
+    }
+
+    /// Enable the previously disabled `rule`.
+    pub fn enable(mut self, rule: A11yRule) -> Self {
+        self.disabled.remove(&rule);
+        self
+    }
+
+    pub fn is_enabled(&self, rule: A11yRule) -> bool {
+        !self.disabled.contains(&rule)
+    }
+}
+
+// ------ A11yIssue ------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct A11yIssue {
+    pub rule: A11yRule,
+    /// Tag path to the element - e.g. `main > form#signup > input:nth-of-type(2)`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for A11yIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[a11y] {}: {} ({:?})",
+            self.path, self.message, self.rule
+        )
+    }
+}
+
+// ------ check ------
+
+/// Check `nodes` and return found accessibility issues.
+///
+/// It's a static analysis of the virtual DOM - i.e. styles, attributes
+/// set through the DOM API and elements outside of the app aren't taken into account.
+pub fn check<Ms>(nodes: &[Node<Ms>], rules: &A11yRules) -> Vec<A11yIssue> {
+    let mut checker = Checker {
+        rules,
+        issues: Vec::new(),
+        ids: BTreeMap::new(),
+        labelled_ids: BTreeSet::new(),
+        unlabelled_controls: Vec::new(),
+    };
+    checker.check_children(nodes, &mut Vec::new(), false);
+
+    for (id, paths) in std::mem::take(&mut checker.ids) {
+        if paths.len() > 1 {
+            let message = format!("id `{}` is used {} times", id, paths.len());
+            for path in paths.into_iter().skip(1) {
+                checker.report(A11yRule::DuplicateId, path, message.clone());
+            }
+        }
+    }
+    for (id, path) in std::mem::take(&mut checker.unlabelled_controls) {
+        if id.iter().all(|id| !checker.labelled_ids.contains(id)) {
+            checker.report(
+                A11yRule::FormLabel,
+                path,
+                "form control without `label`, `aria-label` or `aria-labelledby`".to_owned(),
+            );
+        }
+    }
+    checker.issues
+}
+
+struct Checker<'a> {
+    rules: &'a A11yRules,
+    issues: Vec<A11yIssue>,
+    ids: BTreeMap<String, Vec<String>>,
+    /// Values of `label`s' `for` attributes.
+    labelled_ids: BTreeSet<String>,
+    /// Form controls without `aria-label`s outside of `label`s - `(id, path)`.
+    unlabelled_controls: Vec<(Option<String>, String)>,
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, rule: A11yRule, path: String, message: String) {
+        if self.rules.is_enabled(rule) {
+            self.issues.push(A11yIssue {
+                rule,
+                path,
+                message,
+            });
+        }
+    }
+
+    fn check_children<Ms>(&mut self, nodes: &[Node<Ms>], path: &mut Vec<String>, in_label: bool) {
+        let mut tag_counts = BTreeMap::<String, usize>::new();
+        let same_tag_count = |tag: &str| {
+            nodes
+                .iter()
+                .filter(|node| matches!(node, Node::Element(el) if el.tag.as_str() == tag))
+                .count()
+        };
+        for node in nodes {
+            if let Node::Element(el) = node {
+                let tag = el.tag.as_str().to_lowercase();
+                let nth = tag_counts.entry(tag.clone()).or_default();
+                *nth += 1;
+                let segment = match attr(el, "id") {
+                    Some(id) => format!("{}#{}", tag, id),
+                    None if same_tag_count(el.tag.as_str()) > 1 => {
+                        format!("{}:nth-of-type({})", tag, nth)
+                    }
+                    None => tag,
+                };
+                path.push(segment);
+                self.check_el(el, path, in_label);
+                path.pop();
+            }
+        }
+    }
+
+    fn check_el<Ms>(&mut self, el: &El<Ms>, path: &mut Vec<String>, in_label: bool) {
+        let path_string = path.join(" > ");
+        let tag = el.tag.as_str().to_lowercase();
+        let hidden = attr(el, "aria-hidden") == Some("true");
+
+        if let Some(id) = attr(el, "id") {
+            self.ids
+                .entry(id.to_owned())
+                .or_default()
+                .push(path_string.clone());
+        }
+
+        for at in el.attrs.vals.keys() {
+            let name = at.as_str().to_lowercase();
+            if name.starts_with("aria-") && !ARIA_ATTRIBUTES.contains(&name.as_str()) {
+                self.report(
+                    A11yRule::InvalidAria,
+                    path_string.clone(),
+                    format!("unknown ARIA attribute `{}`", name),
+                );
+            }
+        }
+        if let Some(role) = attr(el, "role") {
+            for role in role.split_whitespace() {
+                if Role::from_name(role).is_none() {
+                    self.report(
+                        A11yRule::InvalidAria,
+                        path_string.clone(),
+                        format!("unknown role `{}`", role),
+                    );
+                }
+            }
+        }
+
+        let presentational = matches!(attr(el, "role"), Some("presentation") | Some("none"));
+        let labelled = has_aria_label(el);
+
+        match tag.as_str() {
+            "img" if attr(el, "alt").is_none() && !presentational && !hidden => {
+                self.report(
+                    A11yRule::ImgAlt,
+                    path_string.clone(),
+                    "`img` without `alt` - use `alt=\"\"` for decorative images".to_owned(),
+                );
+            }
+            "button" | "a"
+                if (tag == "button" || attr(el, "href").is_some())
+                    && !hidden
+                    && !labelled
+                    && attr(el, "title").is_none()
+                    && accessible_text(el).trim().is_empty() =>
+            {
+                self.report(
+                    A11yRule::AccessibleName,
+                    path_string.clone(),
+                    format!("`{}` without an accessible name", tag),
+                );
+            }
+            "input" | "select" | "textarea" if !in_label && !labelled && !hidden => {
+                let unlabelled_type = match attr(el, "type") {
+                    Some(type_) => !matches!(
+                        type_.to_lowercase().as_str(),
+                        "hidden" | "submit" | "button" | "reset" | "image"
+                    ),
+                    None => true,
+                };
+                if unlabelled_type && attr(el, "title").is_none() {
+                    self.unlabelled_controls
+                        .push((attr(el, "id").map(ToOwned::to_owned), path_string.clone()));
+                }
+            }
+            "label" => {
+                if let Some(for_id) = attr(el, "for") {
+                    self.labelled_ids.insert(for_id.to_owned());
+                }
+            }
+            _ => (),
+        }
+
+        self.check_children(&el.children, path, in_label || tag == "label");
+    }
+}
+
+/// The value of the attribute with the given `name`. Attributes without value are `Some("")`.
+fn attr<'a, Ms>(el: &'a El<Ms>, name: &str) -> Option<&'a str> {
+    el.attrs
+        .vals
+        .iter()
+        .find(|(at, _)| at.as_str().eq_ignore_ascii_case(name))
+        .and_then(|(_, value)| match value {
+            AtValue::Some(value) => Some(value.as_str()),
+            AtValue::None => Some(""),
+            AtValue::Ignored => None,
+        })
+}
+
+fn has_aria_label<Ms>(el: &El<Ms>) -> bool {
+    ["aria-label", "aria-labelledby"]
+        .iter()
+        .any(|name| matches!(attr(el, name), Some(value) if !value.trim().is_empty()))
+}
+
+/// Text content of the element including `alt`s of its images and labels of its descendants.
+fn accessible_text<Ms>(el: &El<Ms>) -> String {
+    let mut text = String::new();
+    for child in &el.children {
+        match child {
+            Node::Text(child_text) => text.push_str(&child_text.text),
+            Node::Element(child_el) => {
+                if let Some(label) = attr(child_el, "aria-label") {
+                    text.push_str(label);
+                } else if child_el.tag.as_str().eq_ignore_ascii_case("img") {
+                    text.push_str(attr(child_el, "alt").unwrap_or_default());
+                } else {
+                    text.push_str(&accessible_text(child_el));
+                }
+            }
+            Node::Empty | Node::NoChange => (),
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug)]
+    enum Msg {}
+
+    fn rules_and_paths(nodes: Vec<Node<Msg>>, rules: &A11yRules) -> Vec<(A11yRule, String)> {
+        check(&nodes, rules)
+            .into_iter()
+            .map(|issue| (issue.rule, issue.path))
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn check_rules() {
+        let nodes = || -> Vec<Node<Msg>> {
+            nodes![main![
+                img![attrs! {At::Src => "logo.png"}],
+                img![attrs! {At::Src => "divider.png", At::Alt => ""}],
+                button![],
+                button!["Save"],
+                a![attrs! {At::Href => "/", "aria-label" => "Home"}, img![]],
+                form![
+                    attrs! {At::Id => "signup"},
+                    label![attrs! {At::For => "email"}, "Email"],
+                    input![attrs! {At::Id => "email"}],
+                    label!["Name", input![]],
+                    input![attrs! {At::Type => "submit"}],
+                    textarea![],
+                ],
+                div![attrs! {"role" => "dialogue", "aria-lable" => "Oops"}],
+                div![attrs! {At::Id => "signup"}],
+            ]]
+        };
+
+        assert_eq!(
+            rules_and_paths(nodes(), &A11yRules::default()),
+            vec![
+                (A11yRule::ImgAlt, "main > img:nth-of-type(1)".to_owned()),
+                (
+                    A11yRule::AccessibleName,
+                    "main > button:nth-of-type(1)".to_owned()
+                ),
+                (A11yRule::ImgAlt, "main > a > img".to_owned()),
+                (
+                    A11yRule::InvalidAria,
+                    "main > div:nth-of-type(1)".to_owned()
+                ),
+                (
+                    A11yRule::InvalidAria,
+                    "main > div:nth-of-type(1)".to_owned()
+                ),
+                (A11yRule::DuplicateId, "main > div#signup".to_owned()),
+                (
+                    A11yRule::FormLabel,
+                    "main > form#signup > textarea".to_owned()
+                ),
+            ]
+        );
+
+        let rules = A11yRules::default()
+            .disable(A11yRule::ImgAlt)
+            .disable(A11yRule::InvalidAria)
+            .disable(A11yRule::DuplicateId);
+        assert_eq!(
+            rules_and_paths(nodes(), &rules),
+            vec![
+                (
+                    A11yRule::AccessibleName,
+                    "main > button:nth-of-type(1)".to_owned()
+                ),
+                (
+                    A11yRule::FormLabel,
+                    "main > form#signup > textarea".to_owned()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Typed [ARIA](https://www.w3.org/TR/wai-aria-1.2/) roles and attributes.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!button![
+//!    Role::Tab,
+//!    Aria::Selected(model.tab == Tab::Settings),
+//!    Aria::Controls("settings-panel".into()),
+//!    "Settings",
+//!]
+//! ```
+
+use super::{At, AtValue, El, UpdateEl};
+use std::borrow::Cow;
+
+/// Define a token enum with `ALL` and `as_str`.
+macro_rules! tokens {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident => $value:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            /// All values in the definition order.
+            pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $value),*
+                }
+            }
+        }
+    };
+}
+
+// ------ Role ------
+
+tokens! {
+    /// The value of the attribute `role`.
+    Role {
+        Alert => "alert", AlertDialog => "alertdialog", Application => "application",
+        Article => "article", Banner => "banner", Blockquote => "blockquote", Button => "button",
+        Caption => "caption", Cell => "cell", Checkbox => "checkbox", Code => "code",
+        ColumnHeader => "columnheader", Combobox => "combobox", Complementary => "complementary",
+        ContentInfo => "contentinfo", Definition => "definition", Deletion => "deletion",
+        Dialog => "dialog", Directory => "directory", Document => "document",
+        Emphasis => "emphasis", Feed => "feed", Figure => "figure", Form => "form",
+        Generic => "generic", Grid => "grid", GridCell => "gridcell", Group => "group",
+        Heading => "heading", Img => "img", Insertion => "insertion", Link => "link",
+        List => "list", Listbox => "listbox", ListItem => "listitem", Log => "log",
+        Main => "main", Marquee => "marquee", Math => "math", Menu => "menu",
+        Menubar => "menubar", MenuItem => "menuitem", MenuItemCheckbox => "menuitemcheckbox",
+        MenuItemRadio => "menuitemradio", Meter => "meter", Navigation => "navigation",
+        None => "none", Note => "note", Option => "option", Paragraph => "paragraph",
+        Presentation => "presentation", ProgressBar => "progressbar", Radio => "radio",
+        RadioGroup => "radiogroup", Region => "region", Row => "row", RowGroup => "rowgroup",
+        RowHeader => "rowheader", Scrollbar => "scrollbar", Search => "search",
+        Searchbox => "searchbox", Separator => "separator", Slider => "slider",
+        SpinButton => "spinbutton", Status => "status", Strong => "strong",
+        Subscript => "subscript", Superscript => "superscript", Switch => "switch", Tab => "tab",
+        Table => "table", TabList => "tablist", TabPanel => "tabpanel", Term => "term",
+        Textbox => "textbox", Time => "time", Timer => "timer", Toolbar => "toolbar",
+        Tooltip => "tooltip", Tree => "tree", TreeGrid => "treegrid", TreeItem => "treeitem",
+    }
+}
+
+impl Role {
+    /// Find the role by its name - e.g. `"tablist"` => `Role::TabList`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|role| role.as_str() == name)
+    }
+}
+
+impl<Ms> UpdateEl<Ms> for Role {
+    fn update_el(self, el: &mut El<Ms>) {
+        el.add_attr("role", self.as_str());
+    }
+}
+
+// ------ Attribute values ------
+
+tokens! {
+    /// The value of `aria-live`.
+    Politeness { Off => "off", Polite => "polite", Assertive => "assertive" }
+}
+
+tokens! {
+    /// The value of `aria-checked` and `aria-pressed`.
+    Tristate { False => "false", True => "true", Mixed => "mixed" }
+}
+
+impl From<bool> for Tristate {
+    fn from(value: bool) -> Self {
+        if value {
+            Self::True
+        } else {
+            Self::False
+        }
+    }
+}
+
+tokens! {
+    /// The value of `aria-autocomplete`.
+    AutoComplete { Inline => "inline", List => "list", Both => "both", None => "none" }
+}
+
+tokens! {
+    /// The value of `aria-current`.
+    Current {
+        Page => "page", Step => "step", Location => "location", Date => "date",
+        Time => "time", True => "true", False => "false",
+    }
+}
+
+tokens! {
+    /// The value of `aria-haspopup`.
+    HasPopup {
+        False => "false", True => "true", Menu => "menu", Listbox => "listbox",
+        Tree => "tree", Grid => "grid", Dialog => "dialog",
+    }
+}
+
+tokens! {
+    /// The value of `aria-invalid`.
+    Invalid { False => "false", True => "true", Grammar => "grammar", Spelling => "spelling" }
+}
+
+tokens! {
+    /// The value of `aria-orientation`.
+    Orientation { Horizontal => "horizontal", Vertical => "vertical", Undefined => "undefined" }
+}
+
+tokens! {
+    /// The value of `aria-sort`.
+    Sort { Ascending => "ascending", Descending => "descending", Other => "other", None => "none" }
+}
+
+// ------ Aria ------
+
+/// ARIA state or property - i.e. an `aria-*` attribute with its allowed values.
+///
+/// Id references (e.g. `Controls`) are space-separated lists of element `id`s.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Aria {
+    ActiveDescendant(Cow<'static, str>),
+    Atomic(bool),
+    AutoComplete(AutoComplete),
+    Busy(bool),
+    Checked(Tristate),
+    ColCount(i32),
+    ColIndex(u32),
+    ColSpan(u32),
+    Controls(Cow<'static, str>),
+    Current(Current),
+    DescribedBy(Cow<'static, str>),
+    Description(Cow<'static, str>),
+    Details(Cow<'static, str>),
+    Disabled(bool),
+    ErrorMessage(Cow<'static, str>),
+    Expanded(bool),
+    FlowTo(Cow<'static, str>),
+    HasPopup(HasPopup),
+    Hidden(bool),
+    Invalid(Invalid),
+    KeyShortcuts(Cow<'static, str>),
+    Label(Cow<'static, str>),
+    LabelledBy(Cow<'static, str>),
+    Level(u32),
+    Live(Politeness),
+    Modal(bool),
+    MultiLine(bool),
+    MultiSelectable(bool),
+    Orientation(Orientation),
+    Owns(Cow<'static, str>),
+    Placeholder(Cow<'static, str>),
+    PosInSet(u32),
+    Pressed(Tristate),
+    ReadOnly(bool),
+    Relevant(Cow<'static, str>),
+    Required(bool),
+    RoleDescription(Cow<'static, str>),
+    RowCount(i32),
+    RowIndex(u32),
+    RowSpan(u32),
+    Selected(bool),
+    SetSize(i32),
+    Sort(Sort),
+    ValueMax(f64),
+    ValueMin(f64),
+    ValueNow(f64),
+    ValueText(Cow<'static, str>),
+}
+
+impl Aria {
+    /// The attribute name - e.g. `aria-expanded`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::ActiveDescendant(_) => "aria-activedescendant",
+            Self::Atomic(_) => "aria-atomic",
+            Self::AutoComplete(_) => "aria-autocomplete",
+            Self::Busy(_) => "aria-busy",
+            Self::Checked(_) => "aria-checked",
+            Self::ColCount(_) => "aria-colcount",
+            Self::ColIndex(_) => "aria-colindex",
+            Self::ColSpan(_) => "aria-colspan",
+            Self::Controls(_) => "aria-controls",
+            Self::Current(_) => "aria-current",
+            Self::DescribedBy(_) => "aria-describedby",
+            Self::Description(_) => "aria-description",
+            Self::Details(_) => "aria-details",
+            Self::Disabled(_) => "aria-disabled",
+            Self::ErrorMessage(_) => "aria-errormessage",
+            Self::Expanded(_) => "aria-expanded",
+            Self::FlowTo(_) => "aria-flowto",
+            Self::HasPopup(_) => "aria-haspopup",
+            Self::Hidden(_) => "aria-hidden",
+            Self::Invalid(_) => "aria-invalid",
+            Self::KeyShortcuts(_) => "aria-keyshortcuts",
+            Self::Label(_) => "aria-label",
+            Self::LabelledBy(_) => "aria-labelledby",
+            Self::Level(_) => "aria-level",
+            Self::Live(_) => "aria-live",
+            Self::Modal(_) => "aria-modal",
+            Self::MultiLine(_) => "aria-multiline",
+            Self::MultiSelectable(_) => "aria-multiselectable",
+            Self::Orientation(_) => "aria-orientation",
+            Self::Owns(_) => "aria-owns",
+            Self::Placeholder(_) => "aria-placeholder",
+            Self::PosInSet(_) => "aria-posinset",
+            Self::Pressed(_) => "aria-pressed",
+            Self::ReadOnly(_) => "aria-readonly",
+            Self::Relevant(_) => "aria-relevant",
+            Self::Required(_) => "aria-required",
+            Self::RoleDescription(_) => "aria-roledescription",
+            Self::RowCount(_) => "aria-rowcount",
+            Self::RowIndex(_) => "aria-rowindex",
+            Self::RowSpan(_) => "aria-rowspan",
+            Self::Selected(_) => "aria-selected",
+            Self::SetSize(_) => "aria-setsize",
+            Self::Sort(_) => "aria-sort",
+            Self::ValueMax(_) => "aria-valuemax",
+            Self::ValueMin(_) => "aria-valuemin",
+            Self::ValueNow(_) => "aria-valuenow",
+            Self::ValueText(_) => "aria-valuetext",
+        }
+    }
+
+    pub fn at(&self) -> At {
+        At::from(self.name())
+    }
+
+    #[allow(clippy::match_same_arms)]
+    pub fn at_value(&self) -> AtValue {
+        AtValue::Some(match self {
+            Self::ActiveDescendant(value)
+            | Self::Controls(value)
+            | Self::DescribedBy(value)
+            | Self::Description(value)
+            | Self::Details(value)
+            | Self::ErrorMessage(value)
+            | Self::FlowTo(value)
+            | Self::KeyShortcuts(value)
+            | Self::Label(value)
+            | Self::LabelledBy(value)
+            | Self::Owns(value)
+            | Self::Placeholder(value)
+            | Self::Relevant(value)
+            | Self::RoleDescription(value)
+            | Self::ValueText(value) => value.to_string(),
+            Self::Atomic(value)
+            | Self::Busy(value)
+            | Self::Disabled(value)
+            | Self::Expanded(value)
+            | Self::Hidden(value)
+            | Self::Modal(value)
+            | Self::MultiLine(value)
+            | Self::MultiSelectable(value)
+            | Self::ReadOnly(value)
+            | Self::Required(value)
+            | Self::Selected(value) => value.to_string(),
+            Self::ColCount(value) | Self::RowCount(value) | Self::SetSize(value) => {
+                value.to_string()
+            }
+            Self::ColIndex(value)
+            | Self::ColSpan(value)
+            | Self::Level(value)
+            | Self::PosInSet(value)
+            | Self::RowIndex(value)
+            | Self::RowSpan(value) => value.to_string(),
+            Self::ValueMax(value) | Self::ValueMin(value) | Self::ValueNow(value) => {
+                value.to_string()
+            }
+            Self::AutoComplete(value) => value.as_str().to_owned(),
+            Self::Checked(value) | Self::Pressed(value) => value.as_str().to_owned(),
+            Self::Current(value) => value.as_str().to_owned(),
+            Self::HasPopup(value) => value.as_str().to_owned(),
+            Self::Invalid(value) => value.as_str().to_owned(),
+            Self::Live(value) => value.as_str().to_owned(),
+            Self::Orientation(value) => value.as_str().to_owned(),
+            Self::Sort(value) => value.as_str().to_owned(),
+        })
+    }
+}
+
+impl From<Aria> for (At, AtValue) {
+    fn from(aria: Aria) -> Self {
+        (aria.at(), aria.at_value())
+    }
+}
+
+impl<Ms> UpdateEl<Ms> for Aria {
+    fn update_el(self, el: &mut El<Ms>) {
+        el.add_attr(self.name(), self.at_value());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn aria_attributes() {
+        let el: El<()> = match button![
+            Role::Switch,
+            Aria::Checked(true.into()),
+            Aria::Controls("panel".into()),
+            Aria::ValueNow(0.5),
+        ] {
+            Node::Element(el) => el,
+            _ => unreachable!(),
+        };
+        let attrs = el
+            .attrs
+            .vals
+            .iter()
+            .map(|(at, value)| (at.as_str().to_owned(), value.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            attrs,
+            vec![
+                ("role".to_owned(), AtValue::Some("switch".to_owned())),
+                ("aria-checked".to_owned(), AtValue::Some("true".to_owned())),
+                (
+                    "aria-controls".to_owned(),
+                    AtValue::Some("panel".to_owned())
+                ),
+                ("aria-valuenow".to_owned(), AtValue::Some("0.5".to_owned())),
+            ]
+        );
+        assert_eq!(Role::from_name("tablist"), Some(Role::TabList));
+        assert_eq!(Role::from_name("tabs"), None);
+    }
+}
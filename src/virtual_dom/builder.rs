@@ -0,0 +1,281 @@
+//! Builder API for elements - an alternative to element macros like `div!`.
+//!
+//! Builders are created by functions in modules `seed::html` and `seed::svg`
+//! and they interoperate with macros - builders can be passed into macros as children
+//! and all macro arguments (`C!`, `attrs!`, `ev`, ..) can be passed into `ElBuilder::with`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!use seed::{prelude::*, html::*};
+//!
+//!fn view(model: &Model) -> Node<Msg> {
+//!    div()
+//!        .class("counter")
+//!        .attr(At::Id, "main-counter")
+//!        .child(button().button_type("button").on(Ev::Click, |_| Msg::Increment).text("+"))
+//!        .child(span![model.counter])
+//!        .child(a().href("/about").text("About"))
+//!        .into()
+//!}
+//! ```
+
+use super::{AsAtValue, At, AtValue, CSSValue, El, ElRef, Ev, IntoNodes, Node, St, Text, UpdateEl};
+use crate::browser::dom::event_handler::ev;
+use crate::shortcuts::{html, svg};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+// ------ ElBuilder ------
+
+/// Element builder. `T` is a tag marker (e.g. `html::tag::A`) for tag-specific methods.
+#[derive(Debug)]
+pub struct ElBuilder<Ms, T = ()> {
+    el: El<Ms>,
+    tag: PhantomData<T>,
+}
+
+impl<Ms, T> ElBuilder<Ms, T> {
+    pub fn new(el: El<Ms>) -> Self {
+        Self {
+            el,
+            tag: PhantomData,
+        }
+    }
+
+    /// Add the class. Classes are merged.
+    pub fn class(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.el.add_class(name);
+        self
+    }
+
+    /// Add the class when `enabled` is `true`.
+    pub fn class_if(self, name: impl Into<Cow<'static, str>>, enabled: bool) -> Self {
+        if enabled {
+            self.class(name)
+        } else {
+            self
+        }
+    }
+
+    pub fn id(self, id: impl ToString) -> Self {
+        self.attr(At::Id, id.to_string())
+    }
+
+    /// Set the attribute. Use `AsAtValue` for boolean attributes - e.g. `true.as_at_value()`.
+    pub fn attr(mut self, at: impl Into<At>, value: impl Into<AtValue>) -> Self {
+        self.el.attrs.vals.insert(at.into(), value.into());
+        self
+    }
+
+    pub fn style(mut self, key: impl Into<St>, value: impl Into<CSSValue>) -> Self {
+        self.el.add_style(key, value);
+        self
+    }
+
+    /// Add the event handler.
+    ///
+    /// Handler has to return `Msg`, `Option<Msg>` or `()`.
+    /// Pass typed handlers (e.g. `mouse_ev`) into `ElBuilder::with`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the handler doesn't return `Msg`, `Option<Msg>` or `()`.
+    pub fn on<MsU: 'static>(
+        mut self,
+        trigger: impl Into<Ev>,
+        handler: impl FnOnce(web_sys::Event) -> MsU + 'static + Clone,
+    ) -> Self
+    where
+        Ms: 'static,
+    {
+        self.el.add_event_handler(ev(trigger, handler));
+        self
+    }
+
+    /// Add child node(s) - e.g. a builder, a node created by a macro or `Vec<Node<Ms>>`.
+    pub fn child(mut self, child: impl IntoNodes<Ms>) -> Self {
+        self.el.children.extend(child.into_nodes());
+        self
+    }
+
+    /// Add children.
+    pub fn children<C: IntoNodes<Ms>>(mut self, children: impl IntoIterator<Item = C>) -> Self {
+        for child in children {
+            self.el.children.extend(child.into_nodes());
+        }
+        self
+    }
+
+    /// Add the text node.
+    pub fn text(mut self, text: impl Into<Cow<'static, str>>) -> Self {
+        self.el.children.push(Node::Text(Text::new(text)));
+        self
+    }
+
+    pub fn el_ref<E: Clone>(self, el_ref: &ElRef<E>) -> Self {
+        self.with(el_ref.clone())
+    }
+
+    /// Set the key - see `el_key`.
+    pub fn key(self, key: &impl ToString) -> Self {
+        self.with(super::el_key(key))
+    }
+
+    /// Add anything that can be passed into element macros - e.g. `C!`, `attrs!`, `style!` or `keyboard_ev`.
+    pub fn with(mut self, part: impl UpdateEl<Ms>) -> Self {
+        part.update_el(&mut self.el);
+        self
+    }
+
+    pub fn build(self) -> El<Ms> {
+        self.el
+    }
+}
+
+impl<Ms, T> From<ElBuilder<Ms, T>> for El<Ms> {
+    fn from(builder: ElBuilder<Ms, T>) -> Self {
+        builder.el
+    }
+}
+
+impl<Ms, T> From<ElBuilder<Ms, T>> for Node<Ms> {
+    fn from(builder: ElBuilder<Ms, T>) -> Self {
+        Node::Element(builder.el)
+    }
+}
+
+impl<Ms, T> IntoNodes<Ms> for ElBuilder<Ms, T> {
+    fn into_nodes(self) -> Vec<Node<Ms>> {
+        vec![Node::Element(self.el)]
+    }
+}
+
+impl<Ms, T> UpdateEl<Ms> for ElBuilder<Ms, T> {
+    fn update_el(self, el: &mut El<Ms>) {
+        el.children.push(Node::Element(self.el));
+    }
+}
+
+// ------ Tag-specific methods ------
+
+/// Define setters for attributes of the given tags.
+/// Boolean attributes (`flag`) are set or removed according to the passed `bool`.
+macro_rules! tag_attrs {
+    ($($($tag:ty),+ => $methods:tt)+) => {
+        $($(
+            tag_attrs!(@impl $tag, $methods);
+        )+)+
+    };
+    (@impl $tag:ty, { $($method:ident: $at:expr $(=> $flag:ident)?),* $(,)? }) => {
+        impl<Ms> ElBuilder<Ms, $tag> {
+            $(tag_attrs!(@method $method, $at $(, $flag)?);)*
+        }
+    };
+    (@method $method:ident, $at:expr) => {
+        pub fn $method(self, value: impl ToString) -> Self {
+            self.attr($at, value.to_string())
+        }
+    };
+    (@method $method:ident, $at:expr, flag) => {
+        pub fn $method(self, enabled: bool) -> Self {
+            self.attr($at, enabled.as_at_value())
+        }
+    };
+}
+
+tag_attrs! {
+    html::tag::A => {
+        href: At::Href, target: At::Target, rel: At::Rel, download: At::Download,
+    }
+    html::tag::Img => {
+        src: At::Src, alt: At::Alt, width: At::Width, height: At::Height, loading: "loading",
+    }
+    html::tag::Form => {
+        action: At::Action, method: At::Method, novalidate: At::NoValidate => flag,
+    }
+    html::tag::Label => {
+        for_id: At::For,
+    }
+    html::tag::Button => {
+        button_type: At::Type, name: At::Name, value: At::Value, disabled: At::Disabled => flag,
+    }
+    html::tag::Input => {
+        input_type: At::Type, name: At::Name, value: At::Value, placeholder: At::Placeholder,
+        min: At::Min, max: At::Max, step: At::Step,
+        checked: At::Checked => flag, disabled: At::Disabled => flag,
+        required: At::Required => flag, readonly: At::ReadOnly => flag,
+    }
+    html::tag::TextArea => {
+        name: At::Name, value: At::Value, placeholder: At::Placeholder, rows: At::Rows,
+        disabled: At::Disabled => flag, required: At::Required => flag,
+    }
+    html::tag::Select => {
+        name: At::Name, value: At::Value,
+        multiple: At::Multiple => flag, disabled: At::Disabled => flag,
+    }
+    html::tag::Option => {
+        value: At::Value, selected: At::Selected => flag, disabled: At::Disabled => flag,
+    }
+    html::tag::Td, html::tag::Th => {
+        colspan: At::ColSpan, rowspan: At::RowSpan,
+    }
+    html::tag::Iframe, html::tag::Audio, html::tag::Video, html::tag::Source => {
+        src: At::Src,
+    }
+    svg::tag::Svg => {
+        view_box: At::ViewBox, width: At::Width, height: At::Height,
+    }
+    svg::tag::Path => {
+        d: At::D,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::html::{a, div, input};
+    use crate::prelude::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug)]
+    enum Msg {
+        Clicked,
+    }
+
+    #[wasm_bindgen_test]
+    fn build_el() {
+        let el: El<Msg> = div()
+            .class("a")
+            .class_if("b", false)
+            .class("c")
+            .attr(At::Id, "main")
+            .on(Ev::Click, |_| Msg::Clicked)
+            .child(a().href("/about").text("About"))
+            .child(span!["macro"])
+            .child(input().input_type("checkbox").checked(true).disabled(false))
+            .build();
+
+        assert_eq!(el.tag, Tag::Div);
+        assert_eq!(
+            el.attrs.vals.get(&At::Class),
+            Some(&AtValue::Some("a c".to_owned()))
+        );
+        assert_eq!(
+            el.attrs.vals.get(&At::Id),
+            Some(&AtValue::Some("main".to_owned()))
+        );
+        assert_eq!(el.children.len(), 3);
+
+        let input = match &el.children[2] {
+            Node::Element(input) => input,
+            _ => unreachable!(),
+        };
+        assert_eq!(input.attrs.vals.get(&At::Checked), Some(&AtValue::None));
+        assert_eq!(input.attrs.vals.get(&At::Disabled), Some(&AtValue::Ignored));
+
+        let node: Node<Msg> = section![div().text("Builder in macro")];
+        assert!(matches!(node, Node::Element(section) if section.children.len() == 1));
+    }
+}
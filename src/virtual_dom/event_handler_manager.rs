@@ -2,20 +2,28 @@ use crate::app::MessageMapper;
 use crate::virtual_dom::{Ev, Mailbox};
 use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
 
+pub(crate) mod event_delegator;
 pub mod event_handler;
 pub mod listener;
+pub mod listener_options;
+pub mod rate_limit;
 
 pub use event_handler::EventHandler;
 pub use listener::Listener;
+pub use listener_options::ListenerOptions;
+pub use rate_limit::{RateLimit, RateLimitKind};
 
 // ------ EventHandlerManager ------
 
 #[derive(Debug, Default)]
 /// Manages event handlers and listeners for elements.
 pub struct EventHandlerManager<Ms> {
-    groups: BTreeMap<Ev, Group<Ms>>,
+    groups: BTreeMap<GroupKey, Group<Ms>>,
 }
 
+/// Handlers are grouped by the trigger, listener options and rate limit - each group has its own listener.
+type GroupKey = (Ev, ListenerOptions, Option<RateLimit>);
+
 // @TODO remove custom impl once https://github.com/rust-lang/rust/issues/26925 is fixed
 impl<Ms> Clone for EventHandlerManager<Ms> {
     fn clone(&self) -> Self {
@@ -53,17 +61,20 @@ impl<Ms> EventHandlerManager<Ms> {
     ) {
         let event_target = event_target.into();
 
-        for (trigger, group) in &mut self.groups {
+        for (group_key, group) in &mut self.groups {
             if group.listener.is_none() {
                 group.listener = old_manager
                     .as_mut()
                     .and_then(|old_manager| {
                         old_manager
-                            .take_and_setup_listener(trigger, Rc::clone(&group.event_handlers))
+                            .take_and_setup_listener(group_key, Rc::clone(&group.event_handlers))
                     })
                     .or_else(|| {
+                        let (trigger, options, rate_limit) = group_key;
                         Some(Listener::new(
                             trigger.clone(),
+                            *options,
+                            *rate_limit,
                             event_target.clone(),
                             Rc::clone(&group.event_handlers),
                             mailbox.clone(),
@@ -77,11 +88,12 @@ impl<Ms> EventHandlerManager<Ms> {
     /// It doesn't create listeners automatically - you have to call `attach_listeners`.
     pub fn add_event_handlers(&mut self, event_handlers: Vec<EventHandler<Ms>>) {
         for handler in event_handlers {
-            if let Some(group) = self.groups.get_mut(&handler.trigger) {
+            let group_key = (handler.trigger.clone(), handler.options, handler.rate_limit);
+            if let Some(group) = self.groups.get_mut(&group_key) {
                 group.event_handlers.borrow_mut().push(handler);
             } else {
                 self.groups.insert(
-                    handler.trigger.clone(),
+                    group_key,
                     Group {
                         event_handlers: Rc::new(RefCell::new(vec![handler])),
                         listener: None,
@@ -94,11 +106,11 @@ impl<Ms> EventHandlerManager<Ms> {
     /// This method is used in `attach_listeners` method to move listeners from the old manager.
     pub fn take_and_setup_listener(
         &mut self,
-        trigger: &Ev,
+        group_key: &GroupKey,
         event_handlers: Rc<RefCell<Vec<EventHandler<Ms>>>>,
     ) -> Option<Listener<Ms>> {
         self.groups
-            .get_mut(trigger)
+            .get_mut(group_key)
             .and_then(|group| group.listener.take())
             .map(|listener| {
                 listener.set_event_handlers(event_handlers);
@@ -119,7 +131,7 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for EventHandlerM
             groups: self
                 .groups
                 .into_iter()
-                .map(|(trigger, group)| (trigger, group.map_msg(f.clone())))
+                .map(|(group_key, group)| (group_key, group.map_msg(f.clone())))
                 .collect(),
         }
     }
@@ -128,7 +140,7 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for EventHandlerM
 // ------ Group ------
 
 #[derive(Debug)]
-/// A group of event handlers and a listener with the same trigger (event).
+/// A group of event handlers and a listener with the same trigger (event), listener options and rate limit.
 struct Group<Ms> {
     // `event_handlers` are wrapped in `Rc` & `RefCell`
     // because they are sent to callback in `listener`.
@@ -168,3 +180,209 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for Group<Ms> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::util;
+    use enclose::enc;
+    use event_delegator::EventDelegator;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum Msg {
+        ParentClicked,
+        ChildClicked,
+    }
+
+    #[wasm_bindgen_test]
+    fn listener_options() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let mailbox = Mailbox::new(enc!((messages) move |msg: Option<Msg>| {
+            messages.borrow_mut().extend(msg);
+        }));
+
+        let parent = util::document().create_element("div").unwrap();
+        let child = util::document().create_element("span").unwrap();
+        parent.append_child(&child).unwrap();
+
+        let mut parent_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ParentClicked)
+            })
+            .capture()
+            .once()]);
+        parent_manager.attach_listeners(parent.clone(), None, &mailbox);
+
+        let mut child_manager = EventHandlerManager::with_event_handlers(vec![
+            EventHandler::new(Ev::Click, |_| Some(Msg::ChildClicked)),
+            EventHandler::new(Ev::Click, |_| Some(Msg::ChildClicked)).passive(),
+        ]);
+        assert_eq!(child_manager.groups.len(), 2);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        let click = || {
+            child.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+        };
+
+        click();
+        assert_eq!(
+            *messages.borrow(),
+            vec![Msg::ParentClicked, Msg::ChildClicked, Msg::ChildClicked]
+        );
+
+        messages.borrow_mut().clear();
+        click();
+        assert_eq!(
+            *messages.borrow(),
+            vec![Msg::ChildClicked, Msg::ChildClicked]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn event_delegation() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let root = util::document().create_element("div").unwrap();
+        let mailbox = Mailbox::new(enc!((messages) move |msg: Option<Msg>| {
+            messages.borrow_mut().extend(msg);
+        }));
+        let mailbox = mailbox
+            .clone()
+            .with_event_delegator(EventDelegator::new(root.clone(), mailbox));
+
+        let parent = util::document().create_element("div").unwrap();
+        let child = util::document().create_element("span").unwrap();
+        root.append_child(&parent).unwrap();
+        parent.append_child(&child).unwrap();
+
+        let mut parent_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ParentClicked)
+            })]);
+        parent_manager.attach_listeners(parent.clone(), None, &mailbox);
+
+        let mut child_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ChildClicked)
+            })]);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        let click = || {
+            child.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+        };
+
+        click();
+        assert_eq!(
+            *messages.borrow(),
+            vec![Msg::ChildClicked, Msg::ParentClicked]
+        );
+
+        // `stop_propagation` in the child's handler.
+        let mut child_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |event| {
+                event.stop_propagation();
+                Some(Msg::ChildClicked)
+            })]);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        messages.borrow_mut().clear();
+        click();
+        assert_eq!(*messages.borrow(), vec![Msg::ChildClicked]);
+
+        // Handlers are unregistered with their listeners.
+        drop(child_manager);
+        messages.borrow_mut().clear();
+        click();
+        assert_eq!(*messages.borrow(), vec![Msg::ParentClicked]);
+    }
+
+    #[wasm_bindgen_test]
+    fn event_delegation_with_direct_listeners() {
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let root = util::document().create_element("div").unwrap();
+        let mailbox = Mailbox::new(enc!((messages) move |msg: Option<Msg>| {
+            messages.borrow_mut().extend(msg);
+        }));
+        let mailbox = mailbox
+            .clone()
+            .with_event_delegator(EventDelegator::new(root.clone(), mailbox));
+
+        let parent = util::document().create_element("div").unwrap();
+        let child = util::document().create_element("span").unwrap();
+        root.append_child(&parent).unwrap();
+        parent.append_child(&child).unwrap();
+
+        let click = || {
+            child.dyn_ref::<web_sys::HtmlElement>().unwrap().click();
+        };
+
+        // Passive listeners aren't delegated.
+        let mut parent_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ParentClicked)
+            })
+            .passive()]);
+        parent_manager.attach_listeners(parent.clone(), None, &mailbox);
+
+        let child_element = child.clone();
+        let mut child_manager = EventHandlerManager::with_event_handlers(vec![EventHandler::new(
+            Ev::Click,
+            move |event| {
+                assert_eq!(event.current_target(), Some(child_element.clone().into()));
+                event.stop_propagation();
+                Some(Msg::ChildClicked)
+            },
+        )]);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        // The delegated handler stops the direct listener of the parent.
+        click();
+        assert_eq!(*messages.borrow(), vec![Msg::ChildClicked]);
+
+        drop(parent_manager);
+        drop(child_manager);
+        let mut parent_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ParentClicked)
+            })]);
+        parent_manager.attach_listeners(parent.clone(), None, &mailbox);
+
+        let mut child_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |event| {
+                event.stop_propagation();
+                Some(Msg::ChildClicked)
+            })
+            .passive()]);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        // The direct listener of the child stops the delegated handler of the parent.
+        messages.borrow_mut().clear();
+        click();
+        assert_eq!(*messages.borrow(), vec![Msg::ChildClicked]);
+
+        // The delegated handler of the parent is called after the direct listener of the child.
+        drop(child_manager);
+        let mut child_manager =
+            EventHandlerManager::with_event_handlers(vec![EventHandler::new(Ev::Click, |_| {
+                Some(Msg::ChildClicked)
+            })
+            .passive()]);
+        child_manager.attach_listeners(child.clone(), None, &mailbox);
+
+        messages.borrow_mut().clear();
+        click();
+        click();
+        assert_eq!(
+            *messages.borrow(),
+            vec![
+                Msg::ChildClicked,
+                Msg::ParentClicked,
+                Msg::ChildClicked,
+                Msg::ParentClicked
+            ]
+        );
+    }
+}
@@ -0,0 +1,328 @@
+//! Event delegation - one listener per event type attached to the app's mount point.
+//!
+//! See `App::enable_event_delegation` for more info.
+
+use super::listener::Portal;
+use crate::browser::util::ClosureNew;
+use crate::virtual_dom::{Ev, EventHandler, Mailbox};
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    fmt,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// Delegation id stored on the element with delegated handlers.
+const NODE_ID_KEY: &str = "__seedDelegationId";
+
+pub(crate) type DelegatedHandlers<Ms> = Portal<Rc<RefCell<Vec<EventHandler<Ms>>>>>;
+type Listeners = RefCell<BTreeMap<Ev, Closure<dyn FnMut(web_sys::Event)>>>;
+
+/// Is the event dispatched by the browser in the bubbling phase?
+///
+/// Only bubbling events can be delegated - the other ones (e.g. `focus`, `mouseenter` or `scroll`)
+/// are always listened to directly on elements.
+pub(crate) fn is_delegable(trigger: &Ev) -> bool {
+    matches!(
+        trigger,
+        Ev::Click
+            | Ev::AuxClick
+            | Ev::DblClick
+            | Ev::ContextMenu
+            | Ev::MouseDown
+            | Ev::MouseUp
+            | Ev::MouseMove
+            | Ev::MouseOver
+            | Ev::MouseOut
+            | Ev::PointerDown
+            | Ev::PointerUp
+            | Ev::PointerMove
+            | Ev::PointerOver
+            | Ev::PointerOut
+            | Ev::PointerCancel
+            | Ev::TouchStart
+            | Ev::TouchEnd
+            | Ev::TouchMove
+            | Ev::TouchCancel
+            | Ev::KeyDown
+            | Ev::KeyUp
+            | Ev::KeyPress
+            | Ev::Input
+            | Ev::Change
+            | Ev::Submit
+            | Ev::Cut
+            | Ev::Copy
+            | Ev::Paste
+            | Ev::Drag
+            | Ev::DragStart
+            | Ev::DragEnd
+            | Ev::DragEnter
+            | Ev::DragLeave
+            | Ev::DragOver
+            | Ev::Drop
+            | Ev::Wheel
+            | Ev::CompositionStart
+            | Ev::CompositionUpdate
+            | Ev::CompositionEnd
+            | Ev::AnimationStart
+            | Ev::AnimationEnd
+            | Ev::AnimationIteration
+            | Ev::TransitionEnd
+    )
+}
+
+// ------ EventDelegator ------
+
+/// Attaches one listener per event type to the `root` element and dispatches events
+/// to the registered handlers of the event target and its ancestors.
+///
+/// The root listener is called in the capture phase - before any listener of the event path.
+/// It attaches a bubbling listener to the elements with registered handlers on the path,
+/// so the handlers are called in the same order as directly attached listeners
+/// and `stop_propagation` works in both directions.
+pub(crate) struct EventDelegator<Ms>(Rc<Inner<Ms>>);
+
+struct Inner<Ms> {
+    root: web_sys::Element,
+    // `Mailbox::send` of the app's mailbox without the delegator.
+    send: Box<dyn Fn(Option<Ms>)>,
+    root_listeners: Listeners,
+    /// Listeners attached to elements on event paths - one per event type.
+    path_listeners: Listeners,
+    /// Elements with attached path listeners and the events they have been attached for.
+    path_elements: RefCell<Vec<(web_sys::Event, web_sys::Node, Ev)>>,
+    registry: RefCell<BTreeMap<(u32, Ev), DelegatedHandlers<Ms>>>,
+    next_node_id: Cell<u32>,
+}
+
+impl<Ms: 'static> EventDelegator<Ms> {
+    /// Create a new delegator. Messages from handlers are sent to the `mailbox`.
+    pub fn new(root: web_sys::Element, mailbox: Mailbox<Ms>) -> Self {
+        Self(Rc::new(Inner {
+            root,
+            send: Box::new(move |msg| mailbox.send(msg)),
+            root_listeners: RefCell::new(BTreeMap::new()),
+            path_listeners: RefCell::new(BTreeMap::new()),
+            path_elements: RefCell::new(Vec::new()),
+            registry: RefCell::new(BTreeMap::new()),
+            next_node_id: Cell::new(0),
+        }))
+    }
+
+    /// Register `handlers` of the `element` and return the element's delegation id.
+    pub fn register(
+        &self,
+        element: &web_sys::Element,
+        trigger: &Ev,
+        handlers: DelegatedHandlers<Ms>,
+    ) -> u32 {
+        let node_id = node_id(element).unwrap_or_else(|| {
+            let node_id = self.0.next_node_id.get();
+            self.0.next_node_id.set(node_id.wrapping_add(1));
+            js_sys::Reflect::set(element, &NODE_ID_KEY.into(), &node_id.into())
+                .expect("store delegation id");
+            node_id
+        });
+        self.attach_root_listener(trigger);
+        self.0
+            .registry
+            .borrow_mut()
+            .insert((node_id, trigger.clone()), handlers);
+        node_id
+    }
+
+    fn attach_root_listener(&self, trigger: &Ev) {
+        let mut root_listeners = self.0.root_listeners.borrow_mut();
+        if root_listeners.contains_key(trigger) {
+            return;
+        }
+        // `Weak` - listeners are owned by `Inner`.
+        let inner = Rc::downgrade(&self.0);
+        let root_listener = Closure::new({
+            let inner = Weak::clone(&inner);
+            let trigger = trigger.clone();
+            move |event: web_sys::Event| attach_path_listeners(&inner, &trigger, &event)
+        });
+        let path_listener = Closure::new({
+            let trigger = trigger.clone();
+            move |event: web_sys::Event| call_handlers(&inner, &trigger, &event)
+        });
+        self.0
+            .root
+            .add_event_listener_with_callback_and_bool(
+                trigger.as_str(),
+                root_listener.as_ref().unchecked_ref(),
+                true,
+            )
+            .expect("attach delegated listener");
+        root_listeners.insert(trigger.clone(), root_listener);
+        self.0
+            .path_listeners
+            .borrow_mut()
+            .insert(trigger.clone(), path_listener);
+    }
+}
+
+impl<Ms> EventDelegator<Ms> {
+    /// Remove `handlers` registered by `register`.
+    ///
+    /// _Note:_ Handlers registered later for the same element and event are kept.
+    pub fn unregister(&self, node_id: u32, trigger: &Ev, handlers: &DelegatedHandlers<Ms>) {
+        let mut registry = self.0.registry.borrow_mut();
+        let key = (node_id, trigger.clone());
+        if let Some(registered_handlers) = registry.get(&key) {
+            if registered_handlers.ptr_eq(handlers) {
+                registry.remove(&key);
+            }
+        }
+    }
+}
+
+impl<Ms> Clone for EventDelegator<Ms> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<Ms> fmt::Debug for EventDelegator<Ms> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "EventDelegator({} events)",
+            self.0.root_listeners.borrow().len()
+        )
+    }
+}
+
+impl<Ms> Inner<Ms> {
+    /// Detach path listeners of events that have been already dispatched.
+    ///
+    /// Listeners of events being dispatched (e.g. an event fired by a handler of another event)
+    /// are kept.
+    fn detach_path_listeners(&self, all: bool) {
+        let mut path_elements = self.path_elements.borrow_mut();
+        let (detached, kept): (Vec<_>, Vec<_>) = path_elements
+            .drain(..)
+            .partition(|(event, _, _)| all || event.event_phase() == web_sys::Event::NONE);
+        let path_listeners = self.path_listeners.borrow();
+        for (_, node, trigger) in &detached {
+            let still_used = kept
+                .iter()
+                .any(|(_, kept_node, kept_trigger)| kept_node == node && kept_trigger == trigger);
+            if let (false, Some(listener)) = (still_used, path_listeners.get(trigger)) {
+                node.remove_event_listener_with_callback(
+                    trigger.as_str(),
+                    listener.as_ref().unchecked_ref(),
+                )
+                .expect("detach delegated listener");
+            }
+        }
+        *path_elements = kept;
+    }
+}
+
+impl<Ms> Drop for Inner<Ms> {
+    fn drop(&mut self) {
+        self.detach_path_listeners(true);
+        for (trigger, callback) in self.root_listeners.get_mut().iter() {
+            self.root
+                .remove_event_listener_with_callback_and_bool(
+                    trigger.as_str(),
+                    callback.as_ref().unchecked_ref(),
+                    true,
+                )
+                .expect("detach delegated listener");
+        }
+    }
+}
+
+/// Attach the path listener to the event target and its ancestors with registered handlers.
+///
+/// Adding the same listener again to the same element is a no-op in browsers.
+fn attach_path_listeners<Ms>(inner: &Weak<Inner<Ms>>, trigger: &Ev, event: &web_sys::Event) {
+    let inner = match inner.upgrade() {
+        Some(inner) => inner,
+        None => return,
+    };
+    inner.detach_path_listeners(false);
+
+    let path_listeners = inner.path_listeners.borrow();
+    let path_listener = match path_listeners.get(trigger) {
+        Some(path_listener) => path_listener,
+        None => return,
+    };
+    let root: &web_sys::Node = inner.root.as_ref();
+    let mut node = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+
+    while let Some(current) = node {
+        let has_handlers = match node_id(&current) {
+            Some(node_id) => inner
+                .registry
+                .borrow()
+                .contains_key(&(node_id, trigger.clone())),
+            None => false,
+        };
+        if has_handlers {
+            current
+                .add_event_listener_with_callback(
+                    trigger.as_str(),
+                    path_listener.as_ref().unchecked_ref(),
+                )
+                .expect("attach delegated listener");
+            inner.path_elements.borrow_mut().push((
+                event.clone(),
+                current.clone(),
+                trigger.clone(),
+            ));
+        }
+        if &current == root {
+            break;
+        }
+        node = current.parent_node();
+    }
+}
+
+/// Call handlers registered for the element the path listener is attached to.
+fn call_handlers<Ms>(inner: &Weak<Inner<Ms>>, trigger: &Ev, event: &web_sys::Event) {
+    let inner = match inner.upgrade() {
+        Some(inner) => inner,
+        None => return,
+    };
+    let handlers = event
+        .current_target()
+        .and_then(|target| node_id(&target))
+        .and_then(|node_id| {
+            inner
+                .registry
+                .borrow()
+                .get(&(node_id, trigger.clone()))
+                .cloned()
+        });
+    let handlers = match handlers {
+        Some(handlers) => handlers,
+        None => return,
+    };
+    // Handler callbacks are cloned because messages may be processed synchronously
+    // and the registry may be changed during the processing.
+    let mut handler_callbacks = Vec::new();
+    handlers.update(|event_handlers| {
+        for event_handler in event_handlers.borrow().iter() {
+            handler_callbacks.push(Rc::clone(&event_handler.callback));
+        }
+        event_handlers
+    });
+    for handler_callback in handler_callbacks {
+        (inner.send)(handler_callback(event.clone()));
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn node_id(node: &JsValue) -> Option<u32> {
+    js_sys::Reflect::get(node, &NODE_ID_KEY.into())
+        .ok()
+        .and_then(|node_id| node_id.as_f64())
+        .map(|node_id| node_id as u32)
+}
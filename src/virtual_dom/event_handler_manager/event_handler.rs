@@ -1,11 +1,16 @@
+use super::{ListenerOptions, RateLimit};
 use crate::app::MessageMapper;
 use crate::virtual_dom::Ev;
 use std::{fmt, rc::Rc};
 
 /// `EventHandler`s are called by DOM event listeners with the same trigger (an event to listen to).
+///
+/// Handlers with different `options` or `rate_limit` are called by different listeners.
 pub struct EventHandler<Ms> {
     pub trigger: Ev,
     pub callback: Rc<dyn Fn(web_sys::Event) -> Option<Ms>>,
+    pub options: ListenerOptions,
+    pub rate_limit: Option<RateLimit>,
 }
 
 // @TODO remove custom impl once https://github.com/rust-lang/rust/issues/26925 is fixed
@@ -14,6 +19,8 @@ impl<Ms> Clone for EventHandler<Ms> {
         Self {
             trigger: self.trigger.clone(),
             callback: Rc::clone(&self.callback),
+            options: self.options,
+            rate_limit: self.rate_limit,
         }
     }
 }
@@ -26,8 +33,65 @@ impl<Ms> EventHandler<Ms> {
         Self {
             trigger: trigger.into(),
             callback: Rc::new(callback),
+            options: ListenerOptions::default(),
+            rate_limit: None,
         }
     }
+
+    /// Set all listener options at once.
+    pub const fn options(mut self, options: ListenerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The handler never calls `prevent_default` - it allows browsers to optimize scrolling.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///ev(Ev::Scroll, |_| Msg::Scrolled).passive()
+    /// ```
+    pub const fn passive(mut self) -> Self {
+        self.options.passive = true;
+        self
+    }
+
+    /// The handler is invoked in the capture phase - i.e. before the handlers on descendants.
+    pub const fn capture(mut self) -> Self {
+        self.options.capture = true;
+        self
+    }
+
+    /// The handler is invoked only once. Its listener is removed by the browser
+    /// after the first invocation and it isn't attached again while the element exists.
+    pub const fn once(mut self) -> Self {
+        self.options.once = true;
+        self
+    }
+
+    /// Set the rate limiting of the handler. See `RateLimit` for more info.
+    ///
+    /// The scheduled call is cancelled when the element is removed.
+    pub const fn rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// The handler is called once there are no new events for `ms` milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    ///input_ev(Ev::Input, Msg::SearchChanged).debounce(300)
+    /// ```
+    pub const fn debounce(self, ms: u32) -> Self {
+        self.rate_limit(RateLimit::debounce(ms))
+    }
+
+    /// The handler is called at most once per `ms` milliseconds.
+    pub const fn throttle(self, ms: u32) -> Self {
+        self.rate_limit(RateLimit::throttle(ms))
+    }
 }
 
 impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for EventHandler<Ms> {
@@ -44,6 +108,8 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for EventHandler<
         EventHandler {
             trigger: self.trigger,
             callback: Rc::new(new_callback),
+            options: self.options,
+            rate_limit: self.rate_limit,
         }
     }
 }
@@ -1,5 +1,8 @@
+use super::event_delegator::{self, EventDelegator};
+use super::listener_options::{self, ListenerOptions};
+use super::rate_limit::{RateLimit, RateLimiter};
 use crate::browser::util::ClosureNew;
-use crate::virtual_dom::{Ev, EventHandler, Mailbox};
+use crate::virtual_dom::{patch, Ev, EventHandler, Mailbox};
 use enclose::enc;
 use std::{
     cell::{Cell, RefCell},
@@ -14,51 +17,101 @@ use wasm_bindgen::{closure::Closure, JsCast};
 pub struct Listener<Ms> {
     // Event to listen to.
     trigger: Ev,
+    options: ListenerOptions,
     // "portal" to event handlers - it allows to call event handlers from the JS world.
     portal: Portal<Rc<RefCell<Vec<EventHandler<Ms>>>>>,
-    // `callback` is invoked from the JS world and calls event handlers in the `portal`.
-    callback: Closure<dyn FnMut(web_sys::Event)>,
-    // Element where the listener is attached.
-    event_target: web_sys::EventTarget,
+    attachment: Attachment<Ms>,
+}
+
+enum Attachment<Ms> {
+    Direct {
+        // `callback` is invoked from the JS world and calls event handlers in the `portal`.
+        callback: Closure<dyn FnMut(web_sys::Event)>,
+        // Element where the listener is attached.
+        event_target: web_sys::EventTarget,
+    },
+    // Event handlers in the `portal` are called by the app's `EventDelegator`.
+    Delegated {
+        event_delegator: EventDelegator<Ms>,
+        node_id: u32,
+    },
 }
 
 impl<Ms> Listener<Ms> {
     /// Create a new listener and attach it to the element.
+    ///
+    /// The listener is registered in the `EventDelegator` instead
+    /// when the `mailbox` has one and the event can be delegated.
+    ///
+    /// Event handlers are called through a `RateLimiter` when `rate_limit` is set.
     pub fn new(
         trigger: Ev,
+        options: ListenerOptions,
+        rate_limit: Option<RateLimit>,
         event_target: web_sys::EventTarget,
         event_handlers: Rc<RefCell<Vec<EventHandler<Ms>>>>,
         mailbox: Mailbox<Ms>,
     ) -> Self {
         let portal_to_event_handlers = Portal::new(event_handlers);
+        patch::stats::record(|stats| stats.listeners_attached += 1);
+
+        if let (Some(event_delegator), Some(element)) = (
+            mailbox.event_delegator(),
+            event_target.dyn_ref::<web_sys::Element>(),
+        ) {
+            if options == ListenerOptions::default()
+                && rate_limit.is_none()
+                && event_delegator::is_delegable(&trigger)
+            {
+                let node_id =
+                    event_delegator.register(element, &trigger, portal_to_event_handlers.clone());
+                return Self {
+                    attachment: Attachment::Delegated {
+                        event_delegator: event_delegator.clone(),
+                        node_id,
+                    },
+                    trigger,
+                    options,
+                    portal: portal_to_event_handlers,
+                };
+            }
+        }
 
-        let callback = Closure::new(
-            enc!((portal_to_event_handlers) move |event: web_sys::Event| {
-                let mut handler_callbacks = Vec::new();
-                portal_to_event_handlers.update(|event_handlers| {
-                    // We need to clone handler callbacks and call them later
-                    // because otherwise the app may crash while mutable borrowing event handlers.
-                    // As a trade-off, all callbacks are called although their parents may not exist anymore.
-                    for event_handler in event_handlers.borrow().iter() {
-                        handler_callbacks.push(Rc::clone(&event_handler.callback));
-                    }
-                    event_handlers
-                });
-                for handler_callback in handler_callbacks {
-                    let msg = handler_callback(event.clone());
-                    mailbox.send(msg);
+        let call_event_handlers = enc!((portal_to_event_handlers) move |event: web_sys::Event| {
+            let mut handler_callbacks = Vec::new();
+            portal_to_event_handlers.update(|event_handlers| {
+                // We need to clone handler callbacks and call them later
+                // because otherwise the app may crash while mutable borrowing event handlers.
+                // As a trade-off, all callbacks are called although their parents may not exist anymore.
+                for event_handler in event_handlers.borrow().iter() {
+                    handler_callbacks.push(Rc::clone(&event_handler.callback));
                 }
-            }),
-        );
+                event_handlers
+            });
+            for handler_callback in handler_callbacks {
+                let msg = handler_callback(event.clone());
+                mailbox.send(msg);
+            }
+        });
 
-        event_target
-            .add_event_listener_with_callback(trigger.as_str(), callback.as_ref().unchecked_ref())
-            .expect("attach listener");
+        let callback: Closure<dyn FnMut(web_sys::Event)> = match rate_limit {
+            // The limiter is dropped with the listener - i.e. the scheduled call is cancelled.
+            Some(rate_limit) => {
+                let rate_limiter = RateLimiter::new(rate_limit, call_event_handlers);
+                Closure::new(move |event| rate_limiter.call(event))
+            }
+            None => Closure::new(call_event_handlers),
+        };
+
+        listener_options::add_event_listener(&event_target, &trigger, callback.as_ref(), options);
 
         Self {
             trigger,
-            callback,
-            event_target,
+            options,
+            attachment: Attachment::Direct {
+                callback,
+                event_target,
+            },
             portal: portal_to_event_handlers,
         }
     }
@@ -70,12 +123,21 @@ impl<Ms> Listener<Ms> {
 
 impl<Ms> Drop for Listener<Ms> {
     fn drop(&mut self) {
-        self.event_target
-            .remove_event_listener_with_callback(
-                self.trigger.as_str(),
-                self.callback.as_ref().unchecked_ref(),
-            )
-            .expect("detach listener");
+        match &self.attachment {
+            Attachment::Direct {
+                callback,
+                event_target,
+            } => listener_options::remove_event_listener(
+                event_target,
+                &self.trigger,
+                callback.as_ref(),
+                self.options,
+            ),
+            Attachment::Delegated {
+                event_delegator,
+                node_id,
+            } => event_delegator.unregister(*node_id, &self.trigger, &self.portal),
+        }
     }
 }
 
@@ -89,7 +151,7 @@ impl<Ms> fmt::Debug for Listener<Ms> {
 
 #[derive(Clone)]
 /// "Portal" between the Rust world and the JS world.
-struct Portal<T>(Rc<Cell<Option<T>>>);
+pub(crate) struct Portal<T>(Rc<Cell<Option<T>>>);
 
 impl<T> Portal<T> {
     pub fn new(shared_data: T) -> Self {
@@ -100,4 +162,9 @@ impl<T> Portal<T> {
         // @TODO replace with `Cell::update` once stable
         self.0.set(self.0.take().map(f))
     }
+
+    /// Do both portals lead to the same data?
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
 }
@@ -0,0 +1,105 @@
+use crate::virtual_dom::Ev;
+use wasm_bindgen::{JsCast, JsValue};
+
+// ------ ListenerOptions ------
+
+/// Options of DOM event listeners.
+///
+/// [MDN reference](https://developer.mozilla.org/en-US/docs/Web/API/EventTarget/addEventListener#parameters)
+///
+/// # Example
+///
+/// ```rust,no_run
+///ev(Ev::Scroll, |_| Msg::Scrolled).passive()
+///ev(Ev::Click, |_| Msg::ClickedAnywhere).capture()
+///orders.stream(streams::window_event_with_options(
+///    Ev::TouchMove,
+///    ListenerOptions::new().passive(),
+///    |_| Msg::TouchMoved,
+///));
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ListenerOptions {
+    /// The listener never calls `prevent_default` - it allows browsers to optimize scrolling.
+    pub passive: bool,
+    /// The listener is invoked in the capture phase - i.e. before the listeners on descendants.
+    pub capture: bool,
+    /// The listener is automatically removed by the browser after the first invocation.
+    pub once: bool,
+}
+
+impl ListenerOptions {
+    pub const fn new() -> Self {
+        Self {
+            passive: false,
+            capture: false,
+            once: false,
+        }
+    }
+
+    pub const fn passive(mut self) -> Self {
+        self.passive = true;
+        self
+    }
+
+    pub const fn capture(mut self) -> Self {
+        self.capture = true;
+        self
+    }
+
+    pub const fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    fn to_web_sys(self) -> web_sys::AddEventListenerOptions {
+        let mut options = web_sys::AddEventListenerOptions::new();
+        options
+            .passive(self.passive)
+            .capture(self.capture)
+            .once(self.once);
+        options
+    }
+}
+
+/// Attach the `callback` to the `event_target` with the given `options`.
+///
+/// Listeners with default options are attached without options
+/// to respect browsers' default values (e.g. `passive` listeners for `touchmove` on `Window`).
+pub(crate) fn add_event_listener(
+    event_target: &web_sys::EventTarget,
+    trigger: &Ev,
+    callback: &JsValue,
+    options: ListenerOptions,
+) {
+    let callback = callback.unchecked_ref();
+    if options == ListenerOptions::default() {
+        event_target.add_event_listener_with_callback(trigger.as_str(), callback)
+    } else {
+        event_target.add_event_listener_with_callback_and_add_event_listener_options(
+            trigger.as_str(),
+            callback,
+            &options.to_web_sys(),
+        )
+    }
+    .expect("attach listener");
+}
+
+/// Detach the `callback` attached by `add_event_listener`.
+///
+/// _Note:_ Only `capture` is taken into account by browsers when they search for the listener.
+pub(crate) fn remove_event_listener(
+    event_target: &web_sys::EventTarget,
+    trigger: &Ev,
+    callback: &JsValue,
+    options: ListenerOptions,
+) {
+    event_target
+        .remove_event_listener_with_callback_and_bool(
+            trigger.as_str(),
+            callback.unchecked_ref(),
+            options.capture,
+        )
+        .expect("detach listener");
+}
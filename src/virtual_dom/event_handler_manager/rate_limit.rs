@@ -0,0 +1,235 @@
+use gloo_timers::callback::Timeout;
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+// ------ RateLimit ------
+
+/// Rate limiting of event handlers and streams.
+///
+/// - `Debounce` - values are delayed until there are no new values for `ms` milliseconds.
+///   By default, only the last value is passed (trailing edge).
+/// - `Throttle` - at most one value is passed per `ms` milliseconds.
+///   By default, the first value (leading edge) and the last value (trailing edge) are passed.
+///
+/// _Note:_ Delayed event handlers are called after the event has been dispatched -
+/// calling `prevent_default` or `stop_propagation` in them has no effect.
+///
+/// # Example
+///
+/// ```rust,no_run
+///input_ev(Ev::Input, Msg::SearchChanged).debounce(300)
+///ev(Ev::Scroll, |_| Msg::Scrolled).rate_limit(RateLimit::throttle(100).trailing(false))
+///orders.stream(streams::window_event(Ev::Resize, |_| Msg::Resized).debounce(200));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::module_name_repetitions)]
+pub struct RateLimit {
+    pub kind: RateLimitKind,
+    /// Interval in milliseconds.
+    pub ms: u32,
+    /// Pass the first value immediately.
+    pub leading: bool,
+    /// Pass the last value at the end of the interval.
+    pub trailing: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RateLimitKind {
+    Debounce,
+    Throttle,
+}
+
+impl RateLimit {
+    /// Debounce on the trailing edge.
+    pub const fn debounce(ms: u32) -> Self {
+        Self {
+            kind: RateLimitKind::Debounce,
+            ms,
+            leading: false,
+            trailing: true,
+        }
+    }
+
+    /// Throttle on the leading and trailing edges.
+    pub const fn throttle(ms: u32) -> Self {
+        Self {
+            kind: RateLimitKind::Throttle,
+            ms,
+            leading: true,
+            trailing: true,
+        }
+    }
+
+    pub const fn leading(mut self, leading: bool) -> Self {
+        self.leading = leading;
+        self
+    }
+
+    pub const fn trailing(mut self, trailing: bool) -> Self {
+        self.trailing = trailing;
+        self
+    }
+}
+
+// ------ Limiter ------
+
+/// Timer-agnostic rate limiting state machine.
+#[derive(Debug)]
+pub(crate) struct Limiter<T> {
+    rate_limit: RateLimit,
+    // A value waiting for the trailing edge.
+    pending: Option<T>,
+    // The timer is running.
+    active: bool,
+}
+
+impl<T> Limiter<T> {
+    pub const fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit,
+            pending: None,
+            active: false,
+        }
+    }
+
+    pub const fn ms(&self) -> u32 {
+        self.rate_limit.ms
+    }
+
+    /// Returns the value to pass immediately and `true` if the timer has to be (re)started.
+    pub fn push(&mut self, value: T) -> (Option<T>, bool) {
+        let was_active = self.active;
+        self.active = true;
+        match self.rate_limit.kind {
+            RateLimitKind::Debounce => {
+                if !was_active && self.rate_limit.leading {
+                    (Some(value), true)
+                } else {
+                    self.pending = Some(value);
+                    (None, true)
+                }
+            }
+            RateLimitKind::Throttle => {
+                if was_active {
+                    self.pending = Some(value);
+                    (None, false)
+                } else if self.rate_limit.leading {
+                    (Some(value), true)
+                } else {
+                    self.pending = Some(value);
+                    (None, true)
+                }
+            }
+        }
+    }
+
+    /// Call when the timer has elapsed.
+    /// Returns the value to pass and `true` if the timer has to be restarted.
+    pub fn timeout(&mut self) -> (Option<T>, bool) {
+        let pending = self.pending.take().filter(|_| self.rate_limit.trailing);
+        match (self.rate_limit.kind, pending) {
+            // The next value can't be passed sooner than after another interval.
+            (RateLimitKind::Throttle, Some(value)) => (Some(value), true),
+            (_, pending) => {
+                self.active = false;
+                (pending, false)
+            }
+        }
+    }
+}
+
+// ------ RateLimiter ------
+
+/// Calls `callback` with values passed to `call` according to the `RateLimit`.
+///
+/// The scheduled call is cancelled on drop.
+pub(crate) struct RateLimiter<T>(Rc<RefCell<State<T>>>);
+
+struct State<T> {
+    limiter: Limiter<T>,
+    callback: Rc<dyn Fn(T)>,
+    timeout: Option<Timeout>,
+}
+
+impl<T: 'static> RateLimiter<T> {
+    pub fn new(rate_limit: RateLimit, callback: impl Fn(T) + 'static) -> Self {
+        Self(Rc::new(RefCell::new(State {
+            limiter: Limiter::new(rate_limit),
+            callback: Rc::new(callback),
+            timeout: None,
+        })))
+    }
+
+    pub fn call(&self, value: T) {
+        let (value, start_timer) = self.0.borrow_mut().limiter.push(value);
+        if start_timer {
+            start_timeout(&self.0);
+        }
+        // The callback is called without borrowed state - it may invoke `call` again.
+        if let Some(value) = value {
+            let callback = Rc::clone(&self.0.borrow().callback);
+            callback(value);
+        }
+    }
+}
+
+fn start_timeout<T: 'static>(state: &Rc<RefCell<State<T>>>) {
+    let weak_state = Rc::downgrade(state);
+    let ms = state.borrow().limiter.ms();
+    // The old timeout is cancelled on drop.
+    state.borrow_mut().timeout = Some(Timeout::new(ms, move || on_timeout(&weak_state)));
+}
+
+fn on_timeout<T: 'static>(weak_state: &Weak<RefCell<State<T>>>) {
+    let state = match weak_state.upgrade() {
+        Some(state) => state,
+        None => return,
+    };
+    let (value, restart_timer) = state.borrow_mut().limiter.timeout();
+    if restart_timer {
+        start_timeout(&state);
+    } else {
+        state.borrow_mut().timeout = None;
+    }
+    if let Some(value) = value {
+        let callback = Rc::clone(&state.borrow().callback);
+        callback(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn debounce() {
+        let mut limiter = Limiter::new(RateLimit::debounce(100));
+        assert_eq!(limiter.push(1), (None, true));
+        assert_eq!(limiter.push(2), (None, true));
+        assert_eq!(limiter.timeout(), (Some(2), false));
+
+        let mut limiter = Limiter::new(RateLimit::debounce(100).leading(true).trailing(false));
+        assert_eq!(limiter.push(1), (Some(1), true));
+        assert_eq!(limiter.push(2), (None, true));
+        assert_eq!(limiter.timeout(), (None, false));
+        assert_eq!(limiter.push(3), (Some(3), true));
+    }
+
+    #[wasm_bindgen_test]
+    fn throttle() {
+        let mut limiter = Limiter::new(RateLimit::throttle(100));
+        assert_eq!(limiter.push(1), (Some(1), true));
+        assert_eq!(limiter.push(2), (None, false));
+        assert_eq!(limiter.push(3), (None, false));
+        assert_eq!(limiter.timeout(), (Some(3), true));
+        assert_eq!(limiter.push(4), (None, false));
+        assert_eq!(limiter.timeout(), (Some(4), true));
+        assert_eq!(limiter.timeout(), (None, false));
+        assert_eq!(limiter.push(5), (Some(5), true));
+    }
+}
@@ -0,0 +1,950 @@
+//! Pure-Rust HTML fragment parser used by `El::from_html` (and so `raw!` and `md!`)
+//! when the feature `native-html-parser` is enabled. It doesn't need a browser,
+//! so it works also in native tests and during server-side rendering.
+//!
+//! The parser follows the main rules of the HTML fragment parsing algorithm
+//! to produce the same nodes like `set_inner_html` + `virtual_dom_bridge::node_from_ws`:
+//! - Tag and attribute names are lowercased; SVG names are case-adjusted (e.g. `viewBox`).
+//! - `svg` and `math` elements (and their descendants) get `Namespace::Svg` / `Namespace::MathMl`;
+//!   `foreignObject`, `desc` and `title` in SVG and MathML text elements contain HTML again.
+//! - Void elements, raw text elements (`script`, `style`, ..) and `textarea` / `title` content.
+//! - Optional end tags - e.g. `<li>` closes the previous `<li>` and block elements close `<p>`.
+//! - Missing `tbody` is inserted into tables.
+//! - Comments, the doctype and processing instructions are removed.
+//! - Character references - numeric and the common named ones.
+//!
+//! Error recovery is simplified - e.g. misnested formatting elements are not reconstructed.
+
+use super::{At, El, Node, Tag};
+use crate::browser::dom::Namespace;
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "keygen", "link", "meta", "param",
+    "source", "track", "wbr",
+];
+
+/// Elements with text content that isn't decoded.
+const RAW_TEXT_TAGS: &[&str] = &[
+    "script", "style", "xmp", "iframe", "noembed", "noframes", "noscript",
+];
+
+/// Elements with text content with decoded character references.
+const ESCAPABLE_RAW_TEXT_TAGS: &[&str] = &["textarea", "title"];
+
+/// Start tags that close an open `p`.
+const CLOSES_P_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "center",
+    "dd",
+    "details",
+    "dialog",
+    "dir",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "li",
+    "listing",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+    "xmp",
+];
+
+const HEADING_TAGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Elements that stop searching for an element to close.
+const SCOPE_TAGS: &[&str] = &[
+    "applet", "button", "caption", "html", "marquee", "object", "table", "td", "template", "th",
+];
+
+/// HTML start tags that break out of SVG and MathML content.
+const BREAKOUT_TAGS: &[&str] = &[
+    "b",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "listing",
+    "menu",
+    "meta",
+    "nobr",
+    "ol",
+    "p",
+    "pre",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strike",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+/// SVG elements whose names aren't lowercase.
+const SVG_TAGS: &[&str] = &[
+    "altGlyph",
+    "altGlyphDef",
+    "altGlyphItem",
+    "animateColor",
+    "animateMotion",
+    "animateTransform",
+    "clipPath",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "foreignObject",
+    "glyphRef",
+    "linearGradient",
+    "radialGradient",
+    "textPath",
+];
+
+/// SVG attributes whose names aren't lowercase.
+const SVG_ATTRIBUTES: &[&str] = &[
+    "attributeName",
+    "attributeType",
+    "baseFrequency",
+    "baseProfile",
+    "calcMode",
+    "clipPathUnits",
+    "diffuseConstant",
+    "edgeMode",
+    "filterUnits",
+    "glyphRef",
+    "gradientTransform",
+    "gradientUnits",
+    "kernelMatrix",
+    "kernelUnitLength",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "lengthAdjust",
+    "limitingConeAngle",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "maskContentUnits",
+    "maskUnits",
+    "numOctaves",
+    "pathLength",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "pointsAtX",
+    "pointsAtY",
+    "pointsAtZ",
+    "preserveAlpha",
+    "preserveAspectRatio",
+    "primitiveUnits",
+    "refX",
+    "refY",
+    "repeatCount",
+    "repeatDur",
+    "requiredExtensions",
+    "requiredFeatures",
+    "specularConstant",
+    "specularExponent",
+    "spreadMethod",
+    "startOffset",
+    "stdDeviation",
+    "stitchTiles",
+    "surfaceScale",
+    "systemLanguage",
+    "tableValues",
+    "targetX",
+    "targetY",
+    "textLength",
+    "viewBox",
+    "viewTarget",
+    "xChannelSelector",
+    "yChannelSelector",
+    "zoomAndPan",
+];
+
+/// SVG elements with HTML content.
+const SVG_HTML_INTEGRATION_TAGS: &[&str] = &["foreignObject", "desc", "title"];
+
+/// MathML elements with HTML content.
+const MATHML_TEXT_INTEGRATION_TAGS: &[&str] = &["mi", "mo", "mn", "ms", "mtext"];
+
+/// Named character references without the trailing `;` recognized in text.
+const LEGACY_REFERENCES: &[&str] = &["amp", "lt", "gt", "quot", "nbsp", "copy", "reg"];
+
+// ------ NodeNamespace ------
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum NodeNamespace {
+    Html,
+    Svg,
+    MathMl,
+}
+
+impl NodeNamespace {
+    fn to_namespace(self) -> Option<Namespace> {
+        match self {
+            // `node_from_ws` doesn't set the HTML namespace.
+            NodeNamespace::Html => None,
+            NodeNamespace::Svg => Some(Namespace::Svg),
+            NodeNamespace::MathMl => Some(Namespace::MathMl),
+        }
+    }
+}
+
+// ------ parse ------
+
+/// Parse the HTML fragment like it would be set as `inner_html` of an element in `namespace`.
+pub(crate) fn parse<Ms>(namespace: Option<&Namespace>, html: &str) -> Vec<Node<Ms>> {
+    let context_namespace = match namespace {
+        Some(Namespace::Svg) => NodeNamespace::Svg,
+        Some(Namespace::MathMl) => NodeNamespace::MathMl,
+        _ => NodeNamespace::Html,
+    };
+    let html = html.replace("\r\n", "\n").replace('\r', "\n");
+    let mut parser = Parser {
+        rest: &html,
+        context_namespace,
+        open_elements: Vec::new(),
+        nodes: Vec::new(),
+        text: String::new(),
+    };
+    parser.parse();
+    parser.finish()
+}
+
+struct OpenElement<Ms> {
+    name: String,
+    namespace: NodeNamespace,
+    el: El<Ms>,
+}
+
+struct Parser<'a, Ms> {
+    rest: &'a str,
+    context_namespace: NodeNamespace,
+    open_elements: Vec<OpenElement<Ms>>,
+    /// Root nodes.
+    nodes: Vec<Node<Ms>>,
+    /// Pending text - adjacent text is merged into one node.
+    text: String,
+}
+
+impl<'a, Ms> Parser<'a, Ms> {
+    fn parse(&mut self) {
+        while !self.rest.is_empty() {
+            let rest = self.rest;
+            if rest.starts_with("<!--") {
+                self.skip_past(4, "-->");
+            } else if rest.starts_with("<![CDATA[")
+                && self.insertion_namespace() != NodeNamespace::Html
+            {
+                let content = self.take_until(9, "]]>");
+                self.text.push_str(content);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                self.skip_past(2, ">");
+            } else if rest.starts_with("</") && starts_with_letter(&rest[2..]) {
+                self.rest = &rest[2..];
+                let name = self.take_name();
+                self.skip_past(0, ">");
+                self.end_tag(&name);
+            } else if rest.starts_with('<') && starts_with_letter(&rest[1..]) {
+                self.rest = &rest[1..];
+                self.start_tag();
+            } else {
+                // `<` that doesn't start a tag is text.
+                let end = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|(_, c)| *c == '<')
+                    .map_or(rest.len(), |(index, _)| index);
+                self.text.push_str(&decode_references(&rest[..end], false));
+                self.rest = &rest[end..];
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<Node<Ms>> {
+        self.flush_text();
+        while !self.open_elements.is_empty() {
+            self.pop();
+        }
+        self.nodes
+    }
+
+    // ------ Tokenizer ------
+
+    /// Skip `offset` bytes and then everything up to and including `end` (or the rest).
+    fn skip_past(&mut self, offset: usize, end: &str) {
+        let rest = &self.rest[offset.min(self.rest.len())..];
+        self.rest = rest
+            .find(end)
+            .map_or("", |index| &rest[index + end.len()..]);
+    }
+
+    /// Skip `offset` bytes, return the content before `end` and skip `end`.
+    fn take_until(&mut self, offset: usize, end: &str) -> &'a str {
+        let rest = &self.rest[offset..];
+        let (content, rest) = match rest.find(end) {
+            Some(index) => (&rest[..index], &rest[index + end.len()..]),
+            None => (rest, ""),
+        };
+        self.rest = rest;
+        content
+    }
+
+    fn take_name(&mut self) -> String {
+        let end = self
+            .rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .unwrap_or(self.rest.len());
+        let name = self.rest[..end].to_ascii_lowercase();
+        self.rest = &self.rest[end..];
+        name
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Parse attributes and the end of the start tag. Returns attributes and the self-closing flag.
+    fn take_attributes(&mut self) -> (Vec<(String, String)>, bool) {
+        let mut attributes: Vec<(String, String)> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest;
+            if rest.is_empty() {
+                return (attributes, false);
+            } else if let Some(rest) = rest.strip_prefix("/>") {
+                self.rest = rest;
+                return (attributes, true);
+            } else if let Some(rest) = rest.strip_prefix('>') {
+                self.rest = rest;
+                return (attributes, false);
+            } else if let Some(rest) = rest.strip_prefix('/') {
+                self.rest = rest;
+                continue;
+            }
+
+            // The first character may be `=`.
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+                .map_or(rest.len(), |(index, _)| index);
+            let name = rest[..end].to_ascii_lowercase();
+            self.rest = &rest[end..];
+            self.skip_whitespace();
+
+            let mut value = String::new();
+            if self.rest.starts_with('=') {
+                self.rest = &self.rest[1..];
+                self.skip_whitespace();
+                let raw_value = match self.rest.chars().next() {
+                    Some(quote @ '"') | Some(quote @ '\'') => {
+                        let mut end_quote = [0; 1];
+                        self.take_until(1, quote.encode_utf8(&mut end_quote))
+                    }
+                    _ => {
+                        let end = self
+                            .rest
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(self.rest.len());
+                        let raw_value = &self.rest[..end];
+                        self.rest = &self.rest[end..];
+                        raw_value
+                    }
+                };
+                value = decode_references(raw_value, true);
+            }
+            // Duplicate attributes are ignored.
+            if attributes.iter().all(|(existing, _)| existing != &name) {
+                attributes.push((name, value));
+            }
+        }
+    }
+
+    // ------ Tree construction ------
+
+    fn current(&self) -> Option<&OpenElement<Ms>> {
+        self.open_elements.last()
+    }
+
+    fn current_name_is(&self, names: &[&str]) -> bool {
+        self.current().iter().any(|current| {
+            current.namespace == NodeNamespace::Html && names.contains(&current.name.as_str())
+        })
+    }
+
+    /// Namespace of the element that would be inserted now - before checking its name.
+    fn insertion_namespace(&self) -> NodeNamespace {
+        match self.current() {
+            None => self.context_namespace,
+            Some(current) => match current.namespace {
+                NodeNamespace::Svg
+                    if SVG_HTML_INTEGRATION_TAGS.contains(&current.name.as_str()) =>
+                {
+                    NodeNamespace::Html
+                }
+                NodeNamespace::MathMl
+                    if MATHML_TEXT_INTEGRATION_TAGS.contains(&current.name.as_str()) =>
+                {
+                    NodeNamespace::Html
+                }
+                namespace => namespace,
+            },
+        }
+    }
+
+    fn flush_text(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        let text = Node::new_text(std::mem::take(&mut self.text));
+        self.append(text);
+    }
+
+    fn append(&mut self, node: Node<Ms>) {
+        match self.open_elements.last_mut() {
+            Some(parent) => parent.el.children.push(node),
+            None => self.nodes.push(node),
+        }
+    }
+
+    fn pop(&mut self) {
+        if let Some(element) = self.open_elements.pop() {
+            self.append(Node::Element(element.el));
+        }
+    }
+
+    /// Close the nearest open HTML element named in `names` (and all elements opened after it)
+    /// unless an element in `stop_at` or a foreign element is found first.
+    /// Returns `true` when the element has been closed.
+    fn close(&mut self, names: &[&str], stop_at: &[&str]) -> bool {
+        for index in (0..self.open_elements.len()).rev() {
+            let element = &self.open_elements[index];
+            if element.namespace != NodeNamespace::Html {
+                return false;
+            }
+            if names.contains(&element.name.as_str()) {
+                while self.open_elements.len() > index {
+                    self.pop();
+                }
+                return true;
+            }
+            if stop_at.contains(&element.name.as_str())
+                || SCOPE_TAGS.contains(&element.name.as_str())
+            {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn start_tag(&mut self) {
+        let name = self.take_name();
+        let (attributes, self_closing) = self.take_attributes();
+        self.flush_text();
+
+        let mut namespace = self.insertion_namespace();
+        if namespace != NodeNamespace::Html && BREAKOUT_TAGS.contains(&name.as_str()) {
+            while self.insertion_namespace() != NodeNamespace::Html
+                && !self.open_elements.is_empty()
+            {
+                self.pop();
+            }
+            namespace = NodeNamespace::Html;
+        }
+
+        match namespace {
+            NodeNamespace::Html => self.html_start_tag(name, attributes, self_closing),
+            foreign => {
+                self.insert(name, foreign, attributes, !self_closing);
+            }
+        }
+    }
+
+    fn html_start_tag(
+        &mut self,
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    ) {
+        match name.as_str() {
+            // The fragment is parsed in `body` - the document structure is ignored.
+            "html" | "head" | "body" | "frameset" => return,
+            "svg" => {
+                self.close(&["p"], &[]);
+                self.insert(name, NodeNamespace::Svg, attributes, !self_closing);
+                return;
+            }
+            "math" => {
+                self.close(&["p"], &[]);
+                self.insert(name, NodeNamespace::MathMl, attributes, !self_closing);
+                return;
+            }
+            "li" => {
+                self.close(&["li"], &["ol", "ul"]);
+            }
+            "dd" | "dt" => {
+                self.close(&["dd", "dt"], &["dl"]);
+            }
+            "option" if self.current_name_is(&["option"]) => {
+                self.pop();
+            }
+            "optgroup" => {
+                if self.current_name_is(&["option"]) {
+                    self.pop();
+                }
+                if self.current_name_is(&["optgroup"]) {
+                    self.pop();
+                }
+            }
+            "tr" => {
+                self.close(&["tr"], &["tbody", "thead", "tfoot"]);
+                if self.current_name_is(&["table"]) {
+                    self.insert("tbody".to_owned(), NodeNamespace::Html, Vec::new(), true);
+                }
+            }
+            "td" | "th" => {
+                self.close(&["td", "th"], &["tr"]);
+                if self.current_name_is(&["table", "tbody", "thead", "tfoot"]) {
+                    if self.current_name_is(&["table"]) {
+                        self.insert("tbody".to_owned(), NodeNamespace::Html, Vec::new(), true);
+                    }
+                    self.insert("tr".to_owned(), NodeNamespace::Html, Vec::new(), true);
+                }
+            }
+            "tbody" | "thead" | "tfoot" => {
+                self.close(&["tbody", "thead", "tfoot"], &[]);
+            }
+            _ => (),
+        }
+
+        if CLOSES_P_TAGS.contains(&name.as_str()) {
+            self.close(&["p"], &[]);
+        }
+        if HEADING_TAGS.contains(&name.as_str()) && self.current_name_is(HEADING_TAGS) {
+            self.pop();
+        }
+
+        if VOID_TAGS.contains(&name.as_str()) {
+            self.insert(name, NodeNamespace::Html, attributes, false);
+        } else if RAW_TEXT_TAGS.contains(&name.as_str()) {
+            let content = self.take_raw_text(&name);
+            self.insert(name, NodeNamespace::Html, attributes, true);
+            self.text.push_str(content);
+            self.flush_text();
+            self.pop();
+        } else if ESCAPABLE_RAW_TEXT_TAGS.contains(&name.as_str()) {
+            let content = decode_references(self.take_raw_text(&name), false);
+            self.insert(name, NodeNamespace::Html, attributes, true);
+            self.text.push_str(skip_leading_newline(&content));
+            self.flush_text();
+            self.pop();
+        } else {
+            let skips_newline = matches!(name.as_str(), "pre" | "listing");
+            self.insert(name, NodeNamespace::Html, attributes, true);
+            if skips_newline && self.rest.starts_with('\n') {
+                self.rest = &self.rest[1..];
+            }
+        }
+    }
+
+    /// Take the element content up to its end tag (case-insensitive) and skip the end tag.
+    fn take_raw_text(&mut self, name: &str) -> &'a str {
+        let end_tag = format!("</{}", name);
+        let lowercase_rest = self.rest.to_ascii_lowercase();
+        let end = lowercase_rest.find(&end_tag).unwrap_or(self.rest.len());
+        let content = &self.rest[..end];
+        self.rest = &self.rest[end..];
+        self.skip_past(0, ">");
+        content
+    }
+
+    fn insert(
+        &mut self,
+        name: String,
+        namespace: NodeNamespace,
+        attributes: Vec<(String, String)>,
+        open: bool,
+    ) {
+        let name = match namespace {
+            NodeNamespace::Svg => adjust_case(SVG_TAGS, name),
+            _ => name,
+        };
+        let mut el = El::empty(Tag::from(name.clone()));
+        el.namespace = namespace.to_namespace();
+        for (attribute, value) in attributes {
+            let attribute = match namespace {
+                NodeNamespace::Svg => adjust_case(SVG_ATTRIBUTES, attribute),
+                NodeNamespace::MathMl if attribute == "definitionurl" => "definitionURL".to_owned(),
+                _ => attribute,
+            };
+            el.attrs.add(At::from(attribute), &value);
+        }
+
+        if open {
+            self.open_elements.push(OpenElement {
+                name,
+                namespace,
+                el,
+            });
+        } else {
+            self.append(Node::Element(el));
+        }
+    }
+
+    fn end_tag(&mut self, name: &str) {
+        self.flush_text();
+
+        let index = self.open_elements.iter().rposition(|element| {
+            if element.namespace == NodeNamespace::Html {
+                element.name == name
+            } else {
+                element.name.eq_ignore_ascii_case(name)
+            }
+        });
+        match (index, name) {
+            (Some(index), _) => {
+                while self.open_elements.len() > index {
+                    self.pop();
+                }
+            }
+            // Browsers create an empty `p` and `br` for unmatched `</p>` and `</br>`.
+            (None, "p") => self.insert("p".to_owned(), NodeNamespace::Html, Vec::new(), false),
+            (None, "br") => self.insert("br".to_owned(), NodeNamespace::Html, Vec::new(), false),
+            (None, _) => (),
+        }
+    }
+}
+
+fn starts_with_letter(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic())
+}
+
+fn skip_leading_newline(text: &str) -> &str {
+    text.strip_prefix('\n').unwrap_or(text)
+}
+
+/// Replace the lowercase `name` with the matching mixed-case name.
+fn adjust_case(names: &[&str], name: String) -> String {
+    names
+        .iter()
+        .find(|adjusted| adjusted.eq_ignore_ascii_case(&name))
+        .map_or(name, |adjusted| (*adjusted).to_owned())
+}
+
+// ------ Character references ------
+
+fn decode_references(text: &str, in_attribute: bool) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        match decode_reference(rest, in_attribute) {
+            Some((c, length)) => {
+                decoded.push(c);
+                rest = &rest[length..];
+            }
+            None => decoded.push('&'),
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Decode the reference at the start of `text` (after `&`).
+/// Returns the character and the length of the reference.
+fn decode_reference(text: &str, in_attribute: bool) -> Option<(char, usize)> {
+    if let Some(number) = text.strip_prefix('#') {
+        let (digits, radix, prefix_length) = match number.strip_prefix(|c| c == 'x' || c == 'X') {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let length = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if length == 0 {
+            return None;
+        }
+        let c = u32::from_str_radix(&digits[..length], radix)
+            .ok()
+            .filter(|code| *code != 0)
+            .and_then(std::char::from_u32)
+            .unwrap_or('\u{fffd}');
+        let semicolon = usize::from(digits[length..].starts_with(';'));
+        return Some((c, prefix_length + length + semicolon));
+    }
+
+    let length = text
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(text.len());
+    let name = &text[..length];
+    if text[length..].starts_with(';') {
+        return named_reference(name).map(|c| (c, length + 1));
+    }
+    // Legacy references without `;` - not in attributes where they could be a part of URLs.
+    if in_attribute {
+        return None;
+    }
+    LEGACY_REFERENCES
+        .iter()
+        .find(|legacy| name.starts_with(*legacy))
+        .and_then(|legacy| named_reference(legacy).map(|c| (c, legacy.len())))
+}
+
+#[allow(clippy::too_many_lines)]
+fn named_reference(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "sbquo" => '‚',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "bdquo" => '„',
+        "laquo" => '«',
+        "raquo" => '»',
+        "lsaquo" => '‹',
+        "rsaquo" => '›',
+        "bull" => '•',
+        "middot" => '·',
+        "times" => '×',
+        "divide" => '÷',
+        "minus" => '−',
+        "plusmn" => '±',
+        "deg" => '°',
+        "micro" => 'µ',
+        "para" => '¶',
+        "sect" => '§',
+        "euro" => '€',
+        "pound" => '£',
+        "yen" => '¥',
+        "cent" => '¢',
+        "larr" => '←',
+        "rarr" => '→',
+        "uarr" => '↑',
+        "darr" => '↓',
+        "harr" => '↔',
+        "le" => '≤',
+        "ge" => '≥',
+        "ne" => '≠',
+        "asymp" => '≈',
+        "infin" => '∞',
+        "frac12" => '½',
+        "frac14" => '¼',
+        "frac34" => '¾',
+        "sup2" => '²',
+        "sup3" => '³',
+        "iexcl" => '¡',
+        "iquest" => '¿',
+        "shy" => '\u{ad}',
+        "ensp" => '\u{2002}',
+        "emsp" => '\u{2003}',
+        "thinsp" => '\u{2009}',
+        "zwnj" => '\u{200c}',
+        "zwj" => '\u{200d}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::virtual_dom::{tests::el, AtValue};
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    // The parser doesn't need the browser - the tests are run also by native `cargo test`.
+
+    fn text(node: &Node<()>) -> &str {
+        match node {
+            Node::Text(text) => &text.text,
+            _ => panic!("expected text"),
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn parse_elements_and_text() {
+        let nodes = parse::<()>(
+            None,
+            "<!-- comment --><DIV Class=\"a &amp; b\" hidden>Tom &amp Jerry &lt;3<br>end</div> tail",
+        );
+        assert_eq!(nodes.len(), 2);
+
+        let div = el(&nodes[0]);
+        assert_eq!(div.tag, Tag::Div);
+        assert_eq!(div.namespace, None);
+        assert_eq!(
+            div.attrs.vals.get(&At::Class),
+            Some(&AtValue::Some("a & b".to_owned()))
+        );
+        assert_eq!(
+            div.attrs.vals.get(&At::Hidden),
+            Some(&AtValue::Some(String::new()))
+        );
+        assert_eq!(text(&div.children[0]), "Tom & Jerry <3");
+        assert_eq!(el(&div.children[1]).tag, Tag::Br);
+        assert_eq!(text(&div.children[2]), "end");
+        assert_eq!(text(&nodes[1]), " tail");
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn parse_optional_end_tags() {
+        let nodes = parse::<()>(
+            None,
+            "<p>one<p>two<ul><li>a<li>b</ul><table><tr><td>1<td>2</table>",
+        );
+        assert_eq!(nodes.len(), 4);
+        assert_eq!(text(&el(&nodes[0]).children[0]), "one");
+        assert_eq!(el(&nodes[1]).children.len(), 1);
+
+        let ul = el(&nodes[2]);
+        assert_eq!(ul.children.len(), 2);
+
+        let tbody = el(&el(&nodes[3]).children[0]);
+        assert_eq!(tbody.tag, Tag::Tbody);
+        let tr = el(&tbody.children[0]);
+        assert_eq!(tr.children.len(), 2);
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn parse_namespaces() {
+        let nodes = parse::<()>(
+            None,
+            "<svg viewbox=\"0 0 10 10\"><lineargradient/><foreignObject><p>html</p></foreignObject></svg><math><mi>x</mi></math>",
+        );
+        let svg = el(&nodes[0]);
+        assert_eq!(svg.namespace, Some(Namespace::Svg));
+        assert!(svg.attrs.vals.contains_key(&At::ViewBox));
+
+        let gradient = el(&svg.children[0]);
+        assert_eq!(gradient.tag.to_string(), "linearGradient");
+        assert_eq!(gradient.namespace, Some(Namespace::Svg));
+
+        let p = el(&el(&svg.children[1]).children[0]);
+        assert_eq!(p.namespace, None);
+
+        let math = el(&nodes[1]);
+        assert_eq!(math.namespace, Some(Namespace::MathMl));
+
+        let nodes = parse::<()>(Some(&Namespace::Svg), "<circle r=\"1\"/><g></g>");
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(el(&nodes[0]).namespace, Some(Namespace::Svg));
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn parse_raw_text() {
+        let nodes = parse::<()>(
+            None,
+            "<style>a > b { }</style><textarea>\n&lt;x&gt;</textarea>",
+        );
+        assert_eq!(text(&el(&nodes[0]).children[0]), "a > b { }");
+        assert_eq!(text(&el(&nodes[1]).children[0]), "<x>");
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn from_html() {
+        let nodes = El::<()>::from_html(None, "<p class=\"x\">a<b>b</b></p>");
+        let p = el(&nodes[0]);
+        assert_eq!(p.tag, Tag::P);
+        assert_eq!(text(&el(&p.children[1]).children[0]), "b");
+    }
+
+    #[cfg(feature = "markdown")]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn from_markdown() {
+        let nodes = El::<()>::from_markdown("Press <kbd>Ctrl</kbd> *now*");
+        let p = el(&nodes[0]);
+        assert_eq!(text(&el(&p.children[1]).children[0]), "Ctrl");
+        assert_eq!(el(&p.children[3]).tag, Tag::Em);
+    }
+}
@@ -1,25 +1,39 @@
+use crate::virtual_dom::event_handler_manager::event_delegator::EventDelegator;
 use std::rc::Rc;
 
 pub struct Mailbox<Message: 'static> {
     func: Rc<dyn Fn(Option<Message>)>,
+    // `Some` when the app uses event delegation - see `App::enable_event_delegation`.
+    event_delegator: Option<EventDelegator<Message>>,
 }
 
 impl<Ms> Mailbox<Ms> {
     pub fn new(func: impl Fn(Option<Ms>) + 'static) -> Self {
         Mailbox {
             func: Rc::new(func),
+            event_delegator: None,
         }
     }
 
     pub fn send(&self, message: Option<Ms>) {
         (self.func)(message)
     }
+
+    pub(crate) fn with_event_delegator(mut self, event_delegator: EventDelegator<Ms>) -> Self {
+        self.event_delegator = Some(event_delegator);
+        self
+    }
+
+    pub(crate) const fn event_delegator(&self) -> Option<&EventDelegator<Ms>> {
+        self.event_delegator.as_ref()
+    }
 }
 
 impl<Ms> Clone for Mailbox<Ms> {
     fn clone(&self) -> Self {
         Mailbox {
             func: self.func.clone(),
+            event_delegator: self.event_delegator.clone(),
         }
     }
 }
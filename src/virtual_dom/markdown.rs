@@ -0,0 +1,715 @@
+//! Markdown rendering with configurable extensions and custom element renderers.
+//!
+//! Unlike `El::from_markdown`, `Markdown` converts `pulldown_cmark` events directly into nodes,
+//! so renderers may return any nodes - including nodes with event handlers.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!use seed::{prelude::*, *};
+//!use seed::pulldown_cmark::Options;
+//!
+//!fn view(model: &Model) -> Vec<Node<Msg>> {
+//!    Markdown::new()
+//!        .options(Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH)
+//!        .heading_anchors(true)
+//!        .base_path("/docs")
+//!        .code_block(|code| pre![C!["highlighted"], highlight(&code.code, code.language.as_deref())])
+//!        .link(|link| {
+//!            if link.is_internal() {
+//!                a![attrs! {At::Href => link.url}, link.children]
+//!            } else {
+//!                a![attrs! {At::Href => link.url, At::Target => "_blank"}, link.children]
+//!            }
+//!        })
+//!        .render(&model.readme)
+//!}
+//! ```
+
+use super::{At, El, Node, St, Tag};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Options, Parser, Tag as MdTag};
+use std::collections::BTreeMap;
+
+// ------ Hook arguments ------
+
+/// Heading passed into `Markdown::heading`.
+#[derive(Debug)]
+pub struct Heading<Ms> {
+    /// 1 - 6.
+    pub level: u32,
+    /// Unique slug created from the heading text - e.g. `getting-started`.
+    pub id: String,
+    /// Plain heading text.
+    pub text: String,
+    pub children: Vec<Node<Ms>>,
+}
+
+/// Code block passed into `Markdown::code_block`.
+#[derive(Debug)]
+pub struct CodeBlock {
+    /// Language of the fenced code block - e.g. `rust` in ```` ```rust ````.
+    pub language: Option<String>,
+    pub code: String,
+}
+
+/// Link passed into `Markdown::link`.
+#[derive(Debug)]
+pub struct Link<Ms> {
+    /// Destination - relative URLs are resolved against `Markdown::base_path`.
+    pub url: String,
+    pub title: String,
+    pub children: Vec<Node<Ms>>,
+}
+
+impl<Ms> Link<Ms> {
+    /// `true` for URLs without a scheme and host - they are handled by the app router
+    /// through `subs::UrlRequested` when rendered as `a` elements.
+    pub fn is_internal(&self) -> bool {
+        is_internal_url(&self.url)
+    }
+}
+
+/// Image passed into `Markdown::image`.
+#[derive(Debug)]
+pub struct Image {
+    pub url: String,
+    pub title: String,
+    /// Plain text of the image description.
+    pub alt: String,
+}
+
+// ------ Markdown ------
+
+type Render<T, Ms> = Box<dyn Fn(T) -> Node<Ms>>;
+
+/// Markdown renderer.
+///
+/// Renderers (`heading`, `code_block`, `link` and `image`) replace the default elements.
+pub struct Markdown<Ms> {
+    options: Options,
+    heading_anchors: bool,
+    base_path: Option<String>,
+    heading: Option<Render<Heading<Ms>, Ms>>,
+    code_block: Option<Render<CodeBlock, Ms>>,
+    link: Option<Render<Link<Ms>, Ms>>,
+    image: Option<Render<Image, Ms>>,
+}
+
+impl<Ms> Default for Markdown<Ms> {
+    fn default() -> Self {
+        Self {
+            options: Options::all(),
+            heading_anchors: false,
+            base_path: None,
+            heading: None,
+            code_block: None,
+            link: None,
+            image: None,
+        }
+    }
+}
+
+impl<Ms> Markdown<Ms> {
+    /// Renderer with all `pulldown_cmark` extensions enabled - the same like `El::from_markdown`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set enabled extensions - e.g. `Options::empty()` for CommonMark only.
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add slugs as `id`s to default headings so they can be linked (`#getting-started`).
+    pub fn heading_anchors(mut self, enabled: bool) -> Self {
+        self.heading_anchors = enabled;
+        self
+    }
+
+    /// Resolve relative link and image URLs (e.g. `intro` or `./intro`) against the path
+    /// (e.g. `/docs` => `/docs/intro`), so they are handled by the app router.
+    pub fn base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = Some(base_path.into());
+        self
+    }
+
+    pub fn heading(mut self, render: impl Fn(Heading<Ms>) -> Node<Ms> + 'static) -> Self {
+        self.heading = Some(Box::new(render));
+        self
+    }
+
+    pub fn code_block(mut self, render: impl Fn(CodeBlock) -> Node<Ms> + 'static) -> Self {
+        self.code_block = Some(Box::new(render));
+        self
+    }
+
+    pub fn link(mut self, render: impl Fn(Link<Ms>) -> Node<Ms> + 'static) -> Self {
+        self.link = Some(Box::new(render));
+        self
+    }
+
+    pub fn image(mut self, render: impl Fn(Image) -> Node<Ms> + 'static) -> Self {
+        self.image = Some(Box::new(render));
+        self
+    }
+
+    /// Render the markdown string into nodes.
+    ///
+    /// _Note:_ Raw HTML in markdown is parsed by `El::from_html` - HTML blocks are parsed
+    /// as a whole; inline HTML tags contain the following content until their closing tags,
+    /// the same like `El::from_markdown`.
+    pub fn render(&self, markdown: &str) -> Vec<Node<Ms>> {
+        let mut renderer = Renderer {
+            markdown: self,
+            stack: vec![Frame::new(FrameKind::Root)],
+            html: String::new(),
+            slugs: BTreeMap::new(),
+            footnotes: BTreeMap::new(),
+        };
+        for event in Parser::new_ext(markdown, self.options) {
+            renderer.event(event);
+        }
+        renderer.flush_html();
+        renderer
+            .stack
+            .pop()
+            .map(|root| root.children)
+            .unwrap_or_default()
+    }
+
+    fn resolve_url(&self, url: String) -> String {
+        match &self.base_path {
+            Some(base_path)
+                if is_internal_url(&url)
+                    && !url.is_empty()
+                    && !url.starts_with('/')
+                    && !url.starts_with('#')
+                    && !url.starts_with('?') =>
+            {
+                format!(
+                    "{}/{}",
+                    base_path.trim_end_matches('/'),
+                    url.trim_start_matches("./")
+                )
+            }
+            _ => url,
+        }
+    }
+}
+
+// ------ Renderer ------
+
+enum FrameKind<Ms> {
+    Root,
+    Element(El<Ms>),
+    Heading(u32),
+    CodeBlock(Option<String>),
+    Link(String, String),
+    Image(String, String),
+    TableHead,
+    Table(Vec<Alignment>, Option<El<Ms>>),
+    /// Inline HTML element (e.g. `<kbd>`) waiting for its closing tag; with the tag name.
+    InlineHtml(El<Ms>, String),
+}
+
+struct Frame<Ms> {
+    kind: FrameKind<Ms>,
+    children: Vec<Node<Ms>>,
+    /// Plain text - for headings, code blocks and image descriptions.
+    text: String,
+}
+
+impl<Ms> Frame<Ms> {
+    fn new(kind: FrameKind<Ms>) -> Self {
+        Self {
+            kind,
+            children: Vec::new(),
+            text: String::new(),
+        }
+    }
+}
+
+struct Renderer<'a, Ms> {
+    markdown: &'a Markdown<Ms>,
+    stack: Vec<Frame<Ms>>,
+    /// Consecutive raw HTML blocks.
+    html: String,
+    slugs: BTreeMap<String, usize>,
+    /// Footnote name -> number.
+    footnotes: BTreeMap<String, usize>,
+}
+
+impl<'a, Ms> Renderer<'a, Ms> {
+    fn event(&mut self, event: Event) {
+        if let Event::Html(html) = event {
+            // Lines of HTML blocks end with a new line, inline HTML tags don't.
+            if html.ends_with('\n') {
+                self.html.push_str(&html);
+            } else {
+                self.flush_html();
+                self.inline_html(&html);
+            }
+            return;
+        }
+        self.flush_html();
+
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(_) => {
+                // Close inline HTML elements without closing tags.
+                while self.in_inline_html() {
+                    self.end();
+                }
+                self.end();
+            }
+            Event::Text(text) => {
+                self.push_text(&text);
+            }
+            Event::Code(code) => {
+                self.push_text_to_parents(&code);
+                let mut el = El::empty(Tag::Code);
+                el.add_text(code.into_string());
+                self.push(Node::Element(el));
+            }
+            Event::FootnoteReference(name) => {
+                let number = self.footnote_number(&name);
+                let mut link = El::empty(Tag::A);
+                link.attrs.add(At::Href, format!("#{}", &*name));
+                link.add_text(number.to_string());
+                let mut sup = El::empty(Tag::Sup);
+                sup.add_class("footnote-reference");
+                sup.add_child(Node::Element(link));
+                self.push(Node::Element(sup));
+            }
+            Event::SoftBreak => self.push_text("\n"),
+            Event::HardBreak => self.push(Node::Element(El::empty(Tag::Br))),
+            Event::Rule => self.push(Node::Element(El::empty(Tag::Hr))),
+            Event::TaskListMarker(checked) => {
+                let mut input = El::empty(Tag::Input);
+                input.attrs.add(At::Type, "checkbox");
+                input.attrs.add(At::Disabled, "");
+                if checked {
+                    input.attrs.add(At::Checked, "");
+                }
+                self.push(Node::Element(input));
+            }
+            Event::Html(_) => unreachable!(),
+        }
+    }
+
+    fn start(&mut self, tag: MdTag) {
+        let kind = match tag {
+            MdTag::Paragraph => FrameKind::Element(El::empty(Tag::P)),
+            MdTag::Heading(level) => FrameKind::Heading(level),
+            MdTag::BlockQuote => FrameKind::Element(El::empty(Tag::BlockQuote)),
+            MdTag::CodeBlock(CodeBlockKind::Fenced(info)) => {
+                FrameKind::CodeBlock(info.split_whitespace().next().map(ToOwned::to_owned))
+            }
+            MdTag::CodeBlock(CodeBlockKind::Indented) => FrameKind::CodeBlock(None),
+            MdTag::List(Some(start)) => {
+                let mut ol = El::empty(Tag::Ol);
+                if start != 1 {
+                    ol.attrs.add(At::Start, start);
+                }
+                FrameKind::Element(ol)
+            }
+            MdTag::List(None) => FrameKind::Element(El::empty(Tag::Ul)),
+            MdTag::Item => FrameKind::Element(El::empty(Tag::Li)),
+            MdTag::FootnoteDefinition(name) => {
+                let number = self.footnote_number(&name);
+                let mut div = El::empty(Tag::Div);
+                div.add_class("footnote-definition");
+                div.attrs.add(At::Id, name.into_string());
+                let mut label = El::empty(Tag::Sup);
+                label.add_class("footnote-definition-label");
+                label.add_text(number.to_string());
+                div.add_child(Node::Element(label));
+                FrameKind::Element(div)
+            }
+            MdTag::Table(alignments) => FrameKind::Table(alignments, None),
+            MdTag::TableHead => FrameKind::TableHead,
+            MdTag::TableRow => FrameKind::Element(El::empty(Tag::Tr)),
+            MdTag::TableCell => {
+                let (in_head, alignment) = self.table_cell_context();
+                let mut cell = El::empty(if in_head { Tag::Th } else { Tag::Td });
+                let alignment = match alignment {
+                    Alignment::Left => Some("left"),
+                    Alignment::Center => Some("center"),
+                    Alignment::Right => Some("right"),
+                    Alignment::None => None,
+                };
+                if let Some(alignment) = alignment {
+                    cell.add_style(St::TextAlign, alignment);
+                }
+                FrameKind::Element(cell)
+            }
+            MdTag::Emphasis => FrameKind::Element(El::empty(Tag::Em)),
+            MdTag::Strong => FrameKind::Element(El::empty(Tag::Strong)),
+            MdTag::Strikethrough => FrameKind::Element(El::empty(Tag::Del)),
+            MdTag::Link(_, url, title) => FrameKind::Link(
+                self.markdown.resolve_url(url.into_string()),
+                title.into_string(),
+            ),
+            MdTag::Image(_, url, title) => FrameKind::Image(
+                self.markdown.resolve_url(url.into_string()),
+                title.into_string(),
+            ),
+        };
+        self.stack.push(Frame::new(kind));
+    }
+
+    fn end(&mut self) {
+        let frame = match self.stack.pop() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let Frame {
+            kind,
+            children,
+            text,
+        } = frame;
+        let node = match kind {
+            FrameKind::Root => return self.stack.push(Frame::new(FrameKind::Root)),
+            FrameKind::Element(mut el) | FrameKind::InlineHtml(mut el, _) => {
+                el.children.extend(children);
+                Node::Element(el)
+            }
+            FrameKind::Heading(level) => self.heading(level, text, children),
+            FrameKind::CodeBlock(language) => self.code_block(language, text),
+            FrameKind::Link(url, title) => match &self.markdown.link {
+                Some(render) => render(Link {
+                    url,
+                    title,
+                    children,
+                }),
+                None => {
+                    let mut a = El::empty(Tag::A);
+                    a.attrs.add(At::Href, url);
+                    if !title.is_empty() {
+                        a.attrs.add(At::Title, title);
+                    }
+                    a.children = children;
+                    Node::Element(a)
+                }
+            },
+            FrameKind::Image(url, title) => match &self.markdown.image {
+                Some(render) => render(Image {
+                    url,
+                    title,
+                    alt: text,
+                }),
+                None => {
+                    let mut img = El::empty(Tag::Img);
+                    img.attrs.add(At::Src, url);
+                    img.attrs.add(At::Alt, text);
+                    if !title.is_empty() {
+                        img.attrs.add(At::Title, title);
+                    }
+                    Node::Element(img)
+                }
+            },
+            FrameKind::TableHead => {
+                let mut tr = El::empty(Tag::Tr);
+                tr.children = children;
+                let mut thead = El::empty(Tag::Thead);
+                thead.add_child(Node::Element(tr));
+                Node::Element(thead)
+            }
+            FrameKind::Table(_, tbody) => {
+                let mut table = El::empty(Tag::Table);
+                table.children = children;
+                if let Some(tbody) = tbody {
+                    table.add_child(Node::Element(tbody));
+                }
+                Node::Element(table)
+            }
+        };
+        self.push(node);
+    }
+
+    fn heading(&mut self, level: u32, text: String, children: Vec<Node<Ms>>) -> Node<Ms> {
+        let id = self.unique_slug(&text);
+        match &self.markdown.heading {
+            Some(render) => render(Heading {
+                level,
+                id,
+                text,
+                children,
+            }),
+            None => {
+                let mut heading = El::empty(Tag::from(format!("h{}", level)));
+                if self.markdown.heading_anchors {
+                    heading.attrs.add(At::Id, id);
+                }
+                heading.children = children;
+                Node::Element(heading)
+            }
+        }
+    }
+
+    fn code_block(&self, language: Option<String>, code: String) -> Node<Ms> {
+        match &self.markdown.code_block {
+            Some(render) => render(CodeBlock { language, code }),
+            None => {
+                let mut code_el = El::empty(Tag::Code);
+                if let Some(language) = language {
+                    code_el.add_class(format!("language-{}", language));
+                }
+                code_el.add_text(code);
+                let mut pre = El::empty(Tag::Pre);
+                pre.add_child(Node::Element(code_el));
+                Node::Element(pre)
+            }
+        }
+    }
+
+    /// Add the node into the current frame; rows of table bodies are wrapped in `tbody`.
+    fn push(&mut self, node: Node<Ms>) {
+        let frame = match self.stack.last_mut() {
+            Some(frame) => frame,
+            None => return,
+        };
+        match &mut frame.kind {
+            FrameKind::Table(_, tbody) if matches!(&node, Node::Element(el) if el.tag == Tag::Tr) =>
+            {
+                tbody
+                    .get_or_insert_with(|| El::empty(Tag::Tbody))
+                    .add_child(node);
+            }
+            _ => frame.children.push(node),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.push_text_to_parents(text);
+        let in_code_or_image = self
+            .stack
+            .last()
+            .iter()
+            .any(|frame| matches!(frame.kind, FrameKind::CodeBlock(_) | FrameKind::Image(..)));
+        if !in_code_or_image {
+            self.push(Node::new_text(text.to_owned()));
+        }
+    }
+
+    /// Collect plain text for the nearest heading, code block or image.
+    fn push_text_to_parents(&mut self, text: &str) {
+        let frame = self.stack.iter_mut().rev().find(|frame| {
+            matches!(
+                frame.kind,
+                FrameKind::Heading(_) | FrameKind::CodeBlock(_) | FrameKind::Image(..)
+            )
+        });
+        if let Some(frame) = frame {
+            frame.text.push_str(text);
+        }
+    }
+
+    fn flush_html(&mut self) {
+        if self.html.is_empty() {
+            return;
+        }
+        let html = std::mem::take(&mut self.html);
+        for node in El::from_html(None, &html) {
+            self.push(node);
+        }
+    }
+
+    fn in_inline_html(&self) -> bool {
+        matches!(
+            self.stack.last().map(|frame| &frame.kind),
+            Some(FrameKind::InlineHtml(..))
+        )
+    }
+
+    /// Open an element by its start tag, close it by its end tag or add other inline HTML
+    /// (e.g. `<br>` or comments) as nodes.
+    fn inline_html(&mut self, html: &str) {
+        let html = html.trim();
+        if let Some(end_tag) = html.strip_prefix("</") {
+            let name = tag_name(end_tag);
+            // Only elements opened in the current markdown element can be closed.
+            let position = self
+                .stack
+                .iter()
+                .rev()
+                .take_while(|frame| matches!(frame.kind, FrameKind::InlineHtml(..)))
+                .position(
+                    |frame| matches!(&frame.kind, FrameKind::InlineHtml(_, open) if *open == name),
+                );
+            if let Some(position) = position {
+                for _ in 0..=position {
+                    self.end();
+                }
+            }
+            return;
+        }
+
+        let name = html.strip_prefix('<').map(tag_name).unwrap_or_default();
+        let is_start_tag =
+            !name.is_empty() && !html.ends_with("/>") && !VOID_ELEMENTS.contains(&name.as_str());
+        let mut nodes = El::from_html(None, html);
+        match nodes.pop() {
+            Some(Node::Element(el))
+                if is_start_tag && nodes.is_empty() && el.children.is_empty() =>
+            {
+                self.stack.push(Frame::new(FrameKind::InlineHtml(el, name)));
+            }
+            node => {
+                for node in nodes.into_iter().chain(node) {
+                    self.push(node);
+                }
+            }
+        }
+    }
+
+    /// Returns `(in table head, alignment)` for a new table cell.
+    fn table_cell_context(&self) -> (bool, Alignment) {
+        let in_head = matches!(
+            self.stack.last().map(|frame| &frame.kind),
+            Some(FrameKind::TableHead)
+        );
+        let column = self.stack.last().map_or(0, |frame| frame.children.len());
+        let alignment = self
+            .stack
+            .iter()
+            .rev()
+            .find_map(|frame| match &frame.kind {
+                FrameKind::Table(alignments, _) => alignments.get(column).cloned(),
+                _ => None,
+            })
+            .unwrap_or(Alignment::None);
+        (in_head, alignment)
+    }
+
+    fn footnote_number(&mut self, name: &str) -> usize {
+        let next_number = self.footnotes.len() + 1;
+        *self.footnotes.entry(name.to_owned()).or_insert(next_number)
+    }
+
+    fn unique_slug(&mut self, text: &str) -> String {
+        let slug = slug(text);
+        let count = self.slugs.entry(slug.clone()).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            slug
+        } else {
+            format!("{}-{}", slug, *count - 1)
+        }
+    }
+}
+
+/// Elements without content and closing tags.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// The lowercase tag name at the start of `tag` - e.g. `kbd` from `kbd class="a">`.
+fn tag_name(tag: &str) -> String {
+    tag.chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Lowercase alphanumeric words joined by `-`.
+fn slug(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn is_internal_url(url: &str) -> bool {
+    let scheme_end = url.find(&[':', '/', '?', '#'][..]);
+    let has_scheme = matches!(scheme_end, Some(index) if url[index..].starts_with(':'));
+    !has_scheme && !url.starts_with("//")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::virtual_dom::tests::el;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn render_markdown() {
+        let nodes = Markdown::<()>::new()
+            .heading_anchors(true)
+            .base_path("/docs")
+            .render("# Hello *World*\n\n[Intro](intro) and `code`\n\n```rust\nfn main() {}\n```\n");
+        assert_eq!(nodes.len(), 3);
+
+        let heading = el(&nodes[0]);
+        assert_eq!(heading.tag, Tag::H1);
+        assert_eq!(
+            heading.attrs.vals.get(&At::Id),
+            Some(&AtValue::Some("hello-world".to_owned()))
+        );
+
+        let link = el(&el(&nodes[1]).children[0]);
+        assert_eq!(
+            link.attrs.vals.get(&At::Href),
+            Some(&AtValue::Some("/docs/intro".to_owned()))
+        );
+
+        let code = el(&el(&nodes[2]).children[0]);
+        assert_eq!(
+            code.attrs.vals.get(&At::Class),
+            Some(&AtValue::Some("language-rust".to_owned()))
+        );
+        assert!(matches!(&code.children[0], Node::Text(text) if text.text == "fn main() {}\n"));
+    }
+
+    #[wasm_bindgen_test]
+    fn custom_renderers() {
+        let nodes = Markdown::<()>::new()
+            .options(Options::empty())
+            .heading(|heading| div![C!["heading"], heading.text])
+            .code_block(|code| pre![code.language, code.code])
+            .link(|link| span![link.is_internal().to_string()])
+            .render("## A\n\n[x](https://example.com)\n\n    indented\n\n| a |\n|---|\n");
+
+        assert_eq!(el(&nodes[0]).tag, Tag::Div);
+        let link = el(&el(&nodes[1]).children[0]);
+        assert!(matches!(&link.children[0], Node::Text(text) if text.text == "false"));
+        assert_eq!(el(&nodes[2]).tag, Tag::Pre);
+        // Tables are disabled.
+        assert_eq!(el(&nodes[3]).tag, Tag::P);
+    }
+
+    #[wasm_bindgen_test]
+    fn render_inline_html() {
+        let nodes = Markdown::<()>::new()
+            .render("Press <kbd>Ctrl</kbd> now, <span class=\"a\">*b*<br>c</span> <b>d\n\n<div>\nblock\n</div>");
+        assert_eq!(nodes.len(), 2);
+
+        let p = el(&nodes[0]);
+        assert!(matches!(&p.children[0], Node::Text(text) if text.text == "Press "));
+        let kbd = el(&p.children[1]);
+        assert_eq!(kbd.tag, Tag::Kbd);
+        assert!(matches!(&kbd.children[..], [Node::Text(text)] if text.text == "Ctrl"));
+        assert!(matches!(&p.children[2], Node::Text(text) if text.text == " now, "));
+
+        let span = el(&p.children[3]);
+        assert_eq!(
+            span.attrs.vals.get(&At::Class),
+            Some(&AtValue::Some("a".to_owned()))
+        );
+        assert_eq!(el(&span.children[0]).tag, Tag::Em);
+        assert_eq!(el(&span.children[1]).tag, Tag::Br);
+        assert!(matches!(&span.children[2], Node::Text(text) if text.text == "c"));
+
+        // Unclosed inline elements are closed with the paragraph.
+        let b = el(&p.children[5]);
+        assert!(matches!(&b.children[..], [Node::Text(text)] if text.text == "d"));
+
+        assert_eq!(el(&nodes[1]).tag, Tag::Div);
+    }
+}
@@ -8,7 +8,7 @@ pub mod el;
 pub mod into_nodes;
 pub mod text;
 
-pub use el::{el_key, El, ElKey};
+pub use el::{animate_moves, el_key, AnimateMoves, El, ElKey};
 pub use into_nodes::IntoNodes;
 pub use text::Text;
 
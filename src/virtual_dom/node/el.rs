@@ -3,10 +3,9 @@ use super::super::{
     Tag, Text,
 };
 use crate::app::MessageMapper;
-use crate::browser::{
-    dom::{virtual_dom_bridge, Namespace},
-    util,
-};
+use crate::browser::dom::Namespace;
+#[cfg(not(feature = "native-html-parser"))]
+use crate::browser::{dom::virtual_dom_bridge, util};
 use std::borrow::Cow;
 use std::fmt;
 
@@ -25,6 +24,64 @@ pub fn el_key(key: &impl ToString) -> ElKey {
     ElKey(key.to_string())
 }
 
+// ------ AnimateMoves ------
+
+/// Configuration of move animations for keyed children.
+///
+/// See `animate_moves` for more info.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimateMoves {
+    /// Animation duration in milliseconds.
+    pub duration: u32,
+    /// CSS [easing function](https://developer.mozilla.org/en-US/docs/Web/CSS/easing-function).
+    pub easing: Cow<'static, str>,
+}
+
+impl Default for AnimateMoves {
+    fn default() -> Self {
+        Self {
+            duration: 250,
+            easing: Cow::Borrowed("ease"),
+        }
+    }
+}
+
+impl AnimateMoves {
+    /// Set animation duration in milliseconds.
+    pub const fn duration(mut self, duration: u32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set CSS easing function - e.g. `"ease-in-out"` or `"cubic-bezier(0.2, 0, 0, 1)"`.
+    pub fn easing(mut self, easing: impl Into<Cow<'static, str>>) -> Self {
+        self.easing = easing.into();
+        self
+    }
+}
+
+/// Animate moves of the `El`'s children with `el_key`s.
+///
+/// When the keyed children are reordered, they don't jump to their new positions, but they
+/// slide there using the [FLIP](https://aerotwist.com/blog/flip-your-animations/) technique:
+/// children positions are measured before the patch (_First_), then the DOM is updated (_Last_),
+/// the children are moved back by inverse transforms (_Invert_) and finally the transforms
+/// are transitioned away (_Play_).
+///
+/// _Note:_ Only `HtmlElement` children are animated.
+///
+/// # Example
+///
+/// ```rust,no_run
+///ul![
+///    animate_moves().duration(300),
+///    model.items.iter().map(|item| li![el_key(&item.id), &item.title]),
+///]
+/// ```
+pub fn animate_moves() -> AnimateMoves {
+    AnimateMoves::default()
+}
+
 // ------ El ------
 
 /// A component in our virtual DOM.
@@ -48,6 +105,8 @@ pub struct El<Ms> {
     pub node_ws: Option<web_sys::Node>,
     pub refs: Vec<SharedNodeWs>,
     pub key: Option<ElKey>,
+    /// Animate moves of keyed children. See `animate_moves`.
+    pub animate_moves: Option<AnimateMoves>,
 }
 
 // @TODO remove custom impl once https://github.com/rust-lang/rust/issues/26925 is fixed
@@ -63,6 +122,7 @@ impl<Ms> Clone for El<Ms> {
             node_ws: self.node_ws.clone(),
             refs: self.refs.clone(),
             key: self.key.clone(),
+            animate_moves: self.animate_moves.clone(),
         }
     }
 }
@@ -132,6 +192,7 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for El<Ms> {
             event_handler_manager: self.event_handler_manager.map_msg(f),
             refs: self.refs,
             key: self.key,
+            animate_moves: self.animate_moves,
         }
     }
 }
@@ -143,6 +204,15 @@ impl<Ms: 'static, OtherMs: 'static> MessageMapper<Ms, OtherMs> for Vec<El<Ms>> {
     }
 }
 
+/// Convert markdown to HTML with all [extensions](https://docs.rs/pulldown-cmark/latest/pulldown_cmark/struct.Options.html).
+#[cfg(feature = "markdown")]
+pub(crate) fn markdown_to_html(markdown: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::all());
+    let mut html_text = String::new();
+    pulldown_cmark::html::push_html(&mut html_text, parser);
+    html_text
+}
+
 impl<Ms> El<Ms> {
     /// Create an empty element, specifying only the tag
     pub fn empty(tag: Tag) -> Self {
@@ -156,6 +226,7 @@ impl<Ms> El<Ms> {
             node_ws: None,
             refs: Vec::new(),
             key: None,
+            animate_moves: None,
         }
     }
 
@@ -171,16 +242,14 @@ impl<Ms> El<Ms> {
     /// _Note:_ Requires the `markdown` feature. All additional markdown [extensions](https://docs.rs/pulldown-cmark/latest/pulldown_cmark/struct.Options.html) enabled.
     #[cfg(feature = "markdown")]
     pub fn from_markdown(markdown: &str) -> Vec<Node<Ms>> {
-        let options = pulldown_cmark::Options::all();
-
-        let parser = pulldown_cmark::Parser::new_ext(markdown, options);
-        let mut html_text = String::new();
-        pulldown_cmark::html::push_html(&mut html_text, parser);
-
-        Self::from_html(None, &html_text)
+        Self::from_html(None, &markdown_to_html(markdown))
     }
 
     /// Create elements from an HTML string.
+    ///
+    /// _Note:_ The HTML is parsed by the browser. Enable the feature `native-html-parser`
+    /// to parse it in Rust - it works also outside of the browser (e.g. in native tests).
+    #[cfg(not(feature = "native-html-parser"))]
     pub fn from_html(namespace: Option<&Namespace>, html: &str) -> Vec<Node<Ms>> {
         // Create a web_sys::Element, with our HTML wrapped in a (arbitrary) span tag.
         // We allow web_sys to parse into a DOM tree, then analyze the tree to create our vdom
@@ -204,6 +273,41 @@ impl<Ms> El<Ms> {
         result
     }
 
+    /// Create elements from an HTML string.
+    ///
+    /// _Note:_ The HTML is parsed in Rust (the feature `native-html-parser`).
+    #[cfg(feature = "native-html-parser")]
+    pub fn from_html(namespace: Option<&Namespace>, html: &str) -> Vec<Node<Ms>> {
+        crate::virtual_dom::html_parser::parse(namespace, html)
+    }
+
+    /// Create elements from an untrusted HTML string.
+    ///
+    /// The HTML is parsed into the content of a `<template>` - it's inert, so scripts
+    /// and event handlers (e.g. `<img src=x onerror=..>`) aren't run and resources aren't loaded.
+    #[cfg(not(feature = "native-html-parser"))]
+    pub(crate) fn from_html_inert(html: &str) -> Vec<Node<Ms>> {
+        let template: web_sys::HtmlTemplateElement = wasm_bindgen::JsCast::unchecked_into(
+            util::document()
+                .create_element("template")
+                .expect("Problem creating web-sys element"),
+        );
+        template.set_inner_html(html);
+
+        let children = template.content().child_nodes();
+        (0..children.length())
+            .filter_map(|i| children.get(i))
+            .filter_map(|child| virtual_dom_bridge::node_from_ws(&child))
+            .collect()
+    }
+
+    /// Create elements from an untrusted HTML string - the HTML is parsed in Rust
+    /// (the feature `native-html-parser`), so nothing is run or loaded.
+    #[cfg(feature = "native-html-parser")]
+    pub(crate) fn from_html_inert(html: &str) -> Vec<Node<Ms>> {
+        crate::virtual_dom::html_parser::parse(None, html)
+    }
+
     /// Add a new child to the element
     pub fn add_child(&mut self, element: Node<Ms>) -> &mut Self {
         self.children.push(element);
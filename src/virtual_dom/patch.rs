@@ -6,7 +6,11 @@ use crate::app::App;
 use crate::browser::dom::virtual_dom_bridge;
 use web_sys::Document;
 
+mod flip;
 mod patch_gen;
+#[cfg(test)]
+mod queue_patch_gen;
+pub(crate) mod stats;
 use patch_gen::{PatchCommand, PatchGen};
 
 // We assume that when we run this, the new vdom doesn't have assigned `web_sys::Node`s -
@@ -88,6 +92,12 @@ fn patch_el<'a, Ms, Mdl, INodes>(
         .as_ref()
         .expect("missing old el_ws when patching non-empty el")
         .clone();
+
+    let first_positions = new
+        .animate_moves
+        .as_ref()
+        .map(|_| flip::measure(&old.children));
+
     virtual_dom_bridge::patch_el_details(&mut old, new, &old_el_ws, mailbox);
 
     for ref_ in &mut new.refs {
@@ -105,6 +115,10 @@ fn patch_el<'a, Ms, Mdl, INodes>(
         old_children_iter,
         new_children_iter,
     );
+
+    if let (Some(animate_moves), Some(first_positions)) = (&new.animate_moves, first_positions) {
+        flip::play(&new.children, &first_positions, animate_moves);
+    }
     new.node_ws = Some(old_el_ws);
 }
 
@@ -167,6 +181,7 @@ fn replace_el_by_el<'a, Ms>(
         .node_ws
         .take()
         .expect("old el_ws missing when replacing element with new element");
+    stats::record(|stats| stats.removed += 1);
     replace_by_el(document, &old_node, new, parent, mailbox);
 }
 
@@ -180,6 +195,7 @@ fn replace_el_by_text<'a, Ms>(
         .node_ws
         .take()
         .expect("old el_ws missing when replacing element with text node");
+    stats::record(|stats| stats.removed += 1);
     replace_by_text(document, &old_node, new, parent);
 }
 
@@ -198,6 +214,7 @@ fn replace_text_by_el<'a, Ms>(
 }
 
 fn remove_el<Ms>(mut old: El<Ms>, parent: &web_sys::Node) {
+    stats::record(|stats| stats.removed += 1);
     let old_node = old.node_ws.take().expect("Missing child node_ws");
     virtual_dom_bridge::remove_node(&old_node, parent);
     old.node_ws.replace(old_node);
@@ -221,7 +238,23 @@ pub(crate) fn patch_els<'a, Ms, Mdl, INodes, OI, NI>(
     OI: Iterator<Item = Node<Ms>>,
     NI: Iterator<Item = &'a mut Node<Ms>>,
 {
-    for command in PatchGen::new(old_children_iter, new_children_iter) {
+    run_patch_commands(
+        document,
+        mailbox,
+        app,
+        old_el_ws,
+        PatchGen::new(old_children_iter, new_children_iter),
+    );
+}
+
+fn run_patch_commands<'a, Ms: 'static, Mdl, INodes: IntoNodes<Ms>>(
+    document: &Document,
+    mailbox: &Mailbox<Ms>,
+    app: &App<Ms, Mdl, INodes>,
+    old_el_ws: &web_sys::Node,
+    commands: impl Iterator<Item = PatchCommand<'a, Ms>>,
+) {
+    for command in commands {
         match command {
             PatchCommand::AppendEl { el_new } => append_el(document, el_new, old_el_ws, mailbox),
             PatchCommand::AppendText { text_new } => append_text(document, text_new, old_el_ws),
@@ -247,6 +280,10 @@ pub(crate) fn patch_els<'a, Ms, Mdl, INodes, OI, NI>(
             }
             PatchCommand::RemoveEl { el_old } => remove_el(el_old, old_el_ws),
             PatchCommand::RemoveText { text_old } => remove_text(text_old, old_el_ws),
+            PatchCommand::MoveNode { node_ws, next_node } => {
+                stats::record(|stats| stats.moved += 1);
+                virtual_dom_bridge::insert_node(&node_ws, old_el_ws, next_node)
+            }
         };
     }
 }
@@ -329,3 +366,229 @@ pub(crate) fn patch<'a, Ms, Mdl, INodes: IntoNodes<Ms>>(
     };
     new.node_ws()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::queue_patch_gen::QueuePatchGen;
+    use super::*;
+    use crate as seed;
+    use crate::browser::util;
+    use crate::prelude::*;
+    use std::collections::BTreeMap;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    const ROWS: usize = 1000;
+    const ITERATIONS: usize = 10;
+
+    #[derive(Clone, Debug)]
+    enum Msg {}
+
+    struct TestModel;
+
+    fn create_app() -> App<Msg, TestModel, Node<Msg>> {
+        App::start("output", |_, _| TestModel, |_, _, _| (), |_| seed::empty())
+    }
+
+    fn rows(order: &[usize]) -> Vec<Node<Msg>> {
+        order
+            .iter()
+            .map(|index| li![el_key(index), index.to_string()])
+            .collect()
+    }
+
+    /// Keyed children reconciliation compared by benchmarks.
+    #[derive(Copy, Clone)]
+    enum Reconciliation {
+        /// `PatchGen` - key index + longest increasing subsequence.
+        Lis,
+        /// The original queue-based algorithm - `QueuePatchGen`.
+        Queue,
+    }
+
+    struct BenchResult {
+        /// How many nodes with a key in both the old and the new list have been preserved.
+        reused: usize,
+        /// The average patch duration in milliseconds.
+        duration: f64,
+    }
+
+    /// Patch the list rendered in `old_order` to `new_order` and measure it.
+    fn bench(
+        reconciliation: Reconciliation,
+        old_order: &[usize],
+        new_order: &[usize],
+    ) -> BenchResult {
+        let app = create_app();
+        let mailbox = Mailbox::new(|_msg: Option<Msg>| {});
+        let document = util::document();
+        let performance = util::window().performance().expect("get `Performance`");
+
+        let mut reused = 0;
+        let mut duration = 0.;
+        for _ in 0..ITERATIONS {
+            let parent: web_sys::Node = document.create_element("ul").unwrap().into();
+
+            let mut old_children = rows(old_order);
+            patch_els(
+                &document,
+                &mailbox,
+                &app,
+                &parent,
+                std::iter::empty(),
+                old_children.iter_mut(),
+            );
+            let old_nodes = old_order
+                .iter()
+                .zip(old_children.iter())
+                .map(|(index, child)| (*index, child.node_ws().unwrap().clone()))
+                .collect::<BTreeMap<_, _>>();
+
+            let mut new_children = rows(new_order);
+            let start = performance.now();
+            match reconciliation {
+                Reconciliation::Lis => patch_els(
+                    &document,
+                    &mailbox,
+                    &app,
+                    &parent,
+                    old_children.into_iter(),
+                    new_children.iter_mut(),
+                ),
+                Reconciliation::Queue => run_patch_commands(
+                    &document,
+                    &mailbox,
+                    &app,
+                    &parent,
+                    QueuePatchGen::new(old_children.into_iter(), new_children.iter_mut()),
+                ),
+            }
+            duration += performance.now() - start;
+
+            let child_nodes = parent.child_nodes();
+            assert_eq!(child_nodes.length() as usize, new_order.len());
+            reused = 0;
+            for (position, index) in new_order.iter().enumerate() {
+                #[allow(clippy::cast_possible_truncation)]
+                let node = child_nodes.item(position as u32).unwrap();
+                assert_eq!(node.text_content().unwrap(), index.to_string());
+                if old_nodes
+                    .get(index)
+                    .into_iter()
+                    .any(|old_node| old_node.is_same_node(Some(&node)))
+                {
+                    reused += 1;
+                }
+            }
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let duration = duration / ITERATIONS as f64;
+        BenchResult { reused, duration }
+    }
+
+    /// Benchmark `PatchGen` against the original queue-based algorithm.
+    ///
+    /// The results are the benchmark output - they're logged side by side into the browser
+    /// console (e.g. `wasm-pack test --chrome` without `--headless`).
+    fn compare(name: &str, old_order: &[usize], new_order: &[usize]) {
+        let kept = new_order
+            .iter()
+            .filter(|index| old_order.contains(index))
+            .count();
+
+        let lis = bench(Reconciliation::Lis, old_order, new_order);
+        let queue = bench(Reconciliation::Queue, old_order, new_order);
+        log!(format!(
+            "{}: LIS - {} reused nodes, {:.2} ms; Queue - {} reused nodes, {:.2} ms",
+            name, lis.reused, lis.duration, queue.reused, queue.duration
+        ));
+
+        assert_eq!(lis.reused, kept);
+        assert!(lis.reused >= queue.reused);
+    }
+
+    #[wasm_bindgen_test]
+    fn patch_stats() {
+        let app = create_app();
+        let mailbox = Mailbox::new(|_msg: Option<Msg>| {});
+        let document = util::document();
+        let parent: web_sys::Node = document.create_element("ul").unwrap().into();
+
+        let mut old_children = rows(&[0, 1, 2, 3]);
+        patch_els(
+            &document,
+            &mailbox,
+            &app,
+            &parent,
+            std::iter::empty(),
+            old_children.iter_mut(),
+        );
+        stats::take();
+
+        let mut new_children = rows(&[3, 0, 1, 4]);
+        if let Node::Element(el) = &mut new_children[1] {
+            el.add_class("moved")
+                .add_event_handler(ev(Ev::Click, |_| ()));
+        }
+        patch_els(
+            &document,
+            &mailbox,
+            &app,
+            &parent,
+            old_children.into_iter(),
+            new_children.iter_mut(),
+        );
+
+        assert_eq!(
+            stats::take(),
+            PatchStats {
+                created: 1,
+                removed: 1,
+                moved: 1,
+                updated: 1,
+                attrs_changed: 1,
+                listeners_attached: 1,
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn bench_keyed_move_last_to_front() {
+        let old_order = (0..ROWS).collect::<Vec<_>>();
+        let mut new_order = old_order.clone();
+        new_order.rotate_right(1);
+        compare("move last to front", &old_order, &new_order);
+    }
+
+    #[wasm_bindgen_test]
+    fn bench_keyed_swap_first_and_last() {
+        let old_order = (0..ROWS).collect::<Vec<_>>();
+        let mut new_order = old_order.clone();
+        new_order.swap(0, ROWS - 1);
+        compare("swap first and last", &old_order, &new_order);
+    }
+
+    #[wasm_bindgen_test]
+    fn bench_keyed_reverse() {
+        let old_order = (0..ROWS).collect::<Vec<_>>();
+        let new_order = old_order.iter().rev().copied().collect::<Vec<_>>();
+        compare("reverse", &old_order, &new_order);
+    }
+
+    #[wasm_bindgen_test]
+    fn bench_keyed_remove_and_insert() {
+        let old_order = (0..ROWS).collect::<Vec<_>>();
+        let new_order = old_order
+            .iter()
+            .map(|index| {
+                if index % 10 == 0 {
+                    index + ROWS
+                } else {
+                    *index
+                }
+            })
+            .collect::<Vec<_>>();
+        compare("replace every tenth", &old_order, &new_order);
+    }
+}
@@ -0,0 +1,165 @@
+//! [FLIP](https://aerotwist.com/blog/flip-your-animations/) animations of keyed children moves.
+//!
+//! See `virtual_dom::animate_moves` for more info.
+
+use crate::browser::util;
+use crate::virtual_dom::{AnimateMoves, ElKey, Node};
+use std::collections::BTreeMap;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+/// The original inline `transition` value stored on the animated element.
+const ORIGINAL_TRANSITION_KEY: &str = "__seedFlipTransition";
+/// Animation counter stored on the animated element to detect interrupted animations.
+const GENERATION_KEY: &str = "__seedFlipGeneration";
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Position {
+    left: f64,
+    top: f64,
+}
+
+impl Position {
+    fn of(el_ws: &web_sys::Element) -> Self {
+        let rect = el_ws.get_bounding_client_rect();
+        Self {
+            left: rect.left(),
+            top: rect.top(),
+        }
+    }
+}
+
+/// _First_ - measure positions of all keyed children before patching.
+pub(crate) fn measure<Ms>(children: &[Node<Ms>]) -> BTreeMap<ElKey, Position> {
+    children
+        .iter()
+        .filter_map(|child| {
+            let el = match child {
+                Node::Element(el) => el,
+                _ => return None,
+            };
+            let key = el.key.clone()?;
+            let el_ws = el.node_ws.as_ref()?.dyn_ref::<web_sys::Element>()?;
+            Some((key, Position::of(el_ws)))
+        })
+        .collect()
+}
+
+/// _Last_, _Invert_ and _Play_ - compare the new positions of the patched children with
+/// the `first_positions` and animate the children that have been moved.
+pub(crate) fn play<Ms>(
+    children: &[Node<Ms>],
+    first_positions: &BTreeMap<ElKey, Position>,
+    config: &AnimateMoves,
+) {
+    if first_positions.is_empty() {
+        return;
+    }
+    // Measure all children before we start to touch styles to prevent layout thrashing.
+    let moves = children
+        .iter()
+        .filter_map(|child| {
+            let el = match child {
+                Node::Element(el) => el,
+                _ => return None,
+            };
+            let first = first_positions.get(el.key.as_ref()?)?;
+            let el_ws = el.node_ws.as_ref()?.dyn_ref::<web_sys::HtmlElement>()?;
+            let last = Position::of(el_ws);
+            let (dx, dy) = (first.left - last.left, first.top - last.top);
+            if dx.abs() < 0.5 && dy.abs() < 0.5 {
+                return None;
+            }
+            Some((el_ws.clone(), dx, dy))
+        })
+        .collect::<Vec<_>>();
+
+    for (el_ws, dx, dy) in moves {
+        animate(&el_ws, dx, dy, config);
+    }
+}
+
+fn animate(el_ws: &web_sys::HtmlElement, dx: f64, dy: f64, config: &AnimateMoves) {
+    let style = el_ws.style();
+    let original_transition = original_transition(el_ws, &style);
+    // Inline `transform` isn't changed by running animations - it's always the user's one.
+    let transform = style.get_property_value("transform").unwrap_or_default();
+
+    // Invert.
+    set_style_property(&style, "transition", "none");
+    set_style_property(
+        &style,
+        "transform",
+        &format!("translate({}px, {}px) {}", dx, dy, transform),
+    );
+    // Force reflow so the browser registers the inverted position before the transition starts.
+    let _ = el_ws.offset_width();
+
+    // Play.
+    set_style_property(
+        &style,
+        "transition",
+        &format!("transform {}ms {}", config.duration, config.easing),
+    );
+    set_style_property(&style, "transform", &transform);
+
+    let generation = next_generation(el_ws);
+    let el_ws = el_ws.clone();
+    let cleanup = Closure::once_into_js(move || {
+        // Another animation has been started in the meantime - it'll clean up after itself.
+        if generation_of(&el_ws) != generation {
+            return;
+        }
+        set_style_property(&el_ws.style(), "transition", &original_transition);
+        let _ = js_sys::Reflect::delete_property(&el_ws, &ORIGINAL_TRANSITION_KEY.into());
+        let _ = js_sys::Reflect::delete_property(&el_ws, &GENERATION_KEY.into());
+    });
+    #[allow(clippy::cast_possible_wrap)]
+    util::window()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            cleanup.unchecked_ref(),
+            config.duration as i32,
+        )
+        .expect("set timeout for FLIP animation cleanup");
+}
+
+/// Get the inline `transition` set by the user - i.e. not the one set by a running animation.
+fn original_transition(
+    el_ws: &web_sys::HtmlElement,
+    style: &web_sys::CssStyleDeclaration,
+) -> String {
+    if let Some(transition) = js_sys::Reflect::get(el_ws, &ORIGINAL_TRANSITION_KEY.into())
+        .ok()
+        .and_then(|transition| transition.as_string())
+    {
+        return transition;
+    }
+    let transition = style.get_property_value("transition").unwrap_or_default();
+    js_sys::Reflect::set(
+        el_ws,
+        &ORIGINAL_TRANSITION_KEY.into(),
+        &JsValue::from_str(&transition),
+    )
+    .expect("store original transition");
+    transition
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn generation_of(el_ws: &web_sys::HtmlElement) -> u32 {
+    js_sys::Reflect::get(el_ws, &GENERATION_KEY.into())
+        .ok()
+        .and_then(|generation| generation.as_f64())
+        .map_or(0, |generation| generation as u32)
+}
+
+fn next_generation(el_ws: &web_sys::HtmlElement) -> u32 {
+    let generation = generation_of(el_ws).wrapping_add(1);
+    js_sys::Reflect::set(el_ws, &GENERATION_KEY.into(), &generation.into())
+        .expect("store FLIP animation generation");
+    generation
+}
+
+fn set_style_property(style: &web_sys::CssStyleDeclaration, property: &str, value: &str) {
+    style
+        .set_property(property, value)
+        .expect("set style property for FLIP animation");
+}
@@ -22,58 +22,37 @@
 //! new: [a] [d] [e] [b] [c] [x] [f] [y]
 //! ```
 //!
-//! The algorithm starts by taking nodes from the source iterators one by one in turn (new and old)
-//! and putting them in the corresponding queues:
+//! The algorithm collects all remaining old and new children and pairs them by their `PatchKey`
+//! (namespace + tag + `el_key`, or just "text" for text nodes) using an index of the old
+//! children. Nodes with the same `PatchKey` (e.g. elements without keys) are paired in order.
 //! ```text
-//! old: [a]
-//! new: [a]
+//! old index: [a]=0 [b]=1 [c]=2 [d]=3 [e]=4 [f]=5 [g]=6 [h]=7
+//! new:       [a]   [d]   [e]   [b]   [c]   [x]   [f]   [y]
+//! sources:    0     3     4     1     2     -     5     -
 //! ```
 //!
-//! Now we have the matching key in queues. The algorithm takes nodes from the queues and yields
-//! `patch [a] by [a]` command.
+//! Old nodes without a pair (`[g]`, `[h]`) are removed first.
 //!
-//! But then nodes become diverse:
-//! ```text
-//! old: [b] [c]
-//! new: [d] [e] [b]
-//! ```
+//! Then the algorithm computes the [longest increasing subsequence](https://en.wikipedia.org/wiki/Longest_increasing_subsequence)
+//! of `sources` - `0 1 2 5` (`[a] [b] [c] [f]`). These nodes are already in the right order
+//! so they are only patched. The other paired nodes (`[d]`, `[e]`) are moved - it's the minimal
+//! number of moves.
 //!
-//! As soon as the algorithm finds the matching key `[b]` it yields three commands:
-//! `insert [d] before old [b]`, `insert [e] before old [b]` and then `patch [b] by [b]`.
-//! Old node `[c]` remains in queue.
-//!
-//! The algorithm continues to fill queues and stops with matching nodes `[c]`. Then it issues `patch`
-//! command:
-//! ```text
-//! old: [c]
-//! new: [c]
-//! ```
+//! Paired nodes are processed from the end so each moved node can be inserted before its
+//! already positioned successor: `patch [f] by [f]`, `patch [c] by [c]`, `patch [b] by [b]`,
+//! `move [e] before [b]` + `patch [e] by [e]`, `move [d] before [e]` + `patch [d] by [d]`, ...
 //!
-//! Then nodes again become diverse:
-//! ```text
-//! old: [d] [e] [f]
-//! new: [x] [f] [y]
-//! ```
+//! Finally, new nodes without a pair (`[x]`, `[y]`) are inserted before the next paired node
+//! (`insert [x] before [f]`) or appended (`append [y]`).
 //!
-//! The algorithm stops when finds the matching key `[f]` and yields three commands:
-//! `replace [d] by [x]`, `remove [e]`, `patch [f] by [f]`.
-//!
-//! At this point the source iterator for the new nodes has been exhausted and the algorithm
-//! continues to take only old nodes.
-//! ```text
-//! old: [g] [h]
-//! new: [y]
-//! ```
-//!
-//! At this point both source iterators are exhausted and the algorithm yields:
-//! `replace [g] by [y]` and `remove [h]`.
-//! The append command means append as a last children.
+//! Matched nodes are never recreated.
 //!
 
 use crate::browser::dom::Namespace;
 use crate::virtual_dom::{El, ElKey, Node, Tag, Text};
 use std::borrow::Borrow;
-use std::collections::{BTreeSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
 use std::iter::Peekable;
 
 #[allow(clippy::large_enum_variant)]
@@ -118,6 +97,11 @@ pub enum PatchCommand<'a, Ms: 'static> {
     RemoveText {
         text_old: Text,
     },
+    /// Move the node before the `next_node` or to the end if `next_node` is `None`.
+    MoveNode {
+        node_ws: web_sys::Node,
+        next_node: Option<web_sys::Node>,
+    },
 }
 
 /// `PatchKey` used to compare nodes during patching.
@@ -158,11 +142,8 @@ where
     old_children_iter: Peekable<OI>,
     new_children_iter: Peekable<NI>,
     old_children: VecDeque<Node<Ms>>,
-    new_children: VecDeque<&'a mut Node<Ms>>,
-    matching_child_old: Option<OI::Item>,
-    matching_child_new: Option<NI::Item>,
-    matching_key: Option<PatchKey>,
     keyed_mode: bool,
+    keyed_commands: VecDeque<PatchCommand<'a, Ms>>,
 }
 
 impl<'a, Ms, OI, NI> PatchGen<'a, Ms, OI, NI>
@@ -177,11 +158,8 @@ where
             old_children_iter: old_children_iter.peekable(),
             new_children_iter: new_children_iter.peekable(),
             old_children: VecDeque::new(),
-            new_children: VecDeque::new(),
-            matching_child_old: None,
-            matching_child_new: None,
-            matching_key: None,
             keyed_mode: false,
+            keyed_commands: VecDeque::new(),
         }
     }
 
@@ -190,29 +168,13 @@ where
         if !self.keyed_mode {
             return self.yield_keyless();
         }
-        // Matching `PatchKey` has been already found.
-        if self.matching_key.is_some() {
-            return self.yield_keyed();
-        }
-        // Try to find matching key if at least one iterator has some nodes.
-        if self.old_children_iter.peek().is_some() || self.new_children_iter.peek().is_some() {
-            self.matching_key = find_matching(
-                &mut self.old_children_iter,
-                &mut self.old_children,
-                &mut self.new_children_iter,
-                &mut self.new_children,
-            );
-            // Matching key has been found.
-            if self.matching_key.is_some() {
-                return self.yield_keyed();
-            }
-        }
-        self.yield_remaining()
+        self.keyed_commands.pop_front()
     }
 
     /// Takes a pair of old and new children from source iterators and decides how to update the
     /// old child by the new one.
-    /// Sets `keyed_mode` to true and calls `yield_keyed` as soon as any child has an element key.
+    /// Sets `keyed_mode` to true and computes the keyed commands as soon as any child
+    /// has an element key.
     fn yield_keyless(&mut self) -> Option<PatchCommand<'a, Ms>> {
         // Take a pair of old/new children but skip if both are `Some(Node::Empty)`.
         let (child_old, child_new) = loop {
@@ -240,17 +202,17 @@ where
                 // Permanent switch to keyed mode.
                 self.keyed_mode = true;
 
-                let key_old = PatchKey::new(&child_old);
-                let key_new = PatchKey::new(child_new);
-                if key_old == key_new {
-                    self.matching_key = key_new;
-                }
-                if !child_old.is_empty() {
-                    self.old_children.push_back(child_old);
-                }
-                if !child_new.is_empty() {
-                    self.new_children.push_back(child_new);
+                let mut old_children = vec![child_old];
+                // The queue is filled from the front.
+                while let Some(child_old) = self.old_children.pop_back() {
+                    old_children.push(child_old);
                 }
+                old_children.extend(&mut self.old_children_iter);
+
+                let mut new_children = vec![child_new];
+                new_children.extend(&mut self.new_children_iter);
+
+                self.keyed_commands = lis_commands(old_children, new_children);
                 self.next_command()
             }
             (None, Some(child_new)) => self.append(child_new),
@@ -259,101 +221,6 @@ where
         }
     }
 
-    /// Produces commands from children stored in the `old_children` and the `new_children` queues
-    /// until the child key is equal to `matching_key`, then returns the `PatchEl` or `ReplaceElByEl`
-    /// command.
-    ///
-    /// `self.matching_key` has to be set before calling this method.
-    fn yield_keyed(&mut self) -> Option<PatchCommand<'a, Ms>> {
-        // `self.matching_child_old` and `self.matching_child_new` are set only in this method.
-        // Therefore the first matching arm is always `(None, None)`.
-        match (
-            self.matching_child_old.as_ref(),
-            self.matching_child_new.as_ref(),
-        ) {
-            // No nodes with the matching key have been found.
-            (None, None) => {
-                // If the matching key is set then both the old and the new children queues
-                // have a node with this key.
-                let child_old = self
-                    .old_children
-                    .pop_back()
-                    .expect("old child from the queue");
-                let child_new = self
-                    .new_children
-                    .pop_back()
-                    .expect("new child from the queue");
-
-                let key_old = PatchKey::new(&child_old);
-                let key_new = PatchKey::new(child_new);
-
-                if key_old == self.matching_key && key_new == self.matching_key {
-                    self.matching_child_old = Some(child_old);
-                    self.matching_child_new = Some(child_new);
-                    return self.yield_keyed();
-                }
-                if key_old == self.matching_key {
-                    let next_node = child_old.node_ws().unwrap().clone();
-                    self.matching_child_old = Some(child_old);
-                    return self.insert(child_new, next_node);
-                }
-                if key_new == self.matching_key {
-                    self.matching_child_new = Some(child_new);
-                    return self.remove(child_old);
-                }
-                self.patch_or_replace(child_old, child_new)
-            }
-            // An old node with the matching key has been found in the queue.
-            (Some(child_old), None) => {
-                let child_new = self
-                    .new_children
-                    .pop_back()
-                    .expect("node with a matching key");
-
-                if PatchKey::new(child_new) == self.matching_key {
-                    self.matching_child_new = Some(child_new);
-                    return self.yield_keyed();
-                }
-                let next_node = child_old
-                    .node_ws()
-                    .expect("old node connected to web_sys node")
-                    .clone();
-                self.insert(child_new, next_node)
-            }
-            // A new node with the matching key has been found in the queue.
-            (None, Some(_)) => {
-                let child_old = self
-                    .old_children
-                    .pop_back()
-                    .expect("node with a matching key");
-
-                if PatchKey::new(&child_old) == self.matching_key {
-                    self.matching_child_old = Some(child_old);
-                    return self.yield_keyed();
-                }
-                self.remove(child_old)
-            }
-            // An old and a new node with the matching key have been found in queues.
-            (Some(_), Some(_)) => {
-                // We have found the matching node pair, we no longer need the key.
-                self.matching_key = None;
-                let child_old = self.matching_child_old.take().unwrap();
-                let child_new = self.matching_child_new.take().unwrap();
-                self.patch_or_replace(child_old, child_new)
-            }
-        }
-    }
-
-    /// Takes a pair of the remaining children stored in the queues and returns the command.
-    fn yield_remaining(&mut self) -> Option<PatchCommand<'a, Ms>> {
-        match (self.old_children.pop_back(), self.new_children.pop_back()) {
-            (Some(child_old), Some(child_new)) => self.patch_or_replace(child_old, child_new),
-            (Some(child_old), None) => self.remove(child_old),
-            (None, Some(child_new)) => self.append(child_new),
-            (None, None) => None,
-        }
-    }
-
     fn append(&mut self, child_new: &'a mut Node<Ms>) -> Option<PatchCommand<'a, Ms>> {
         Some(match child_new {
             Node::Element(el_new) => PatchCommand::AppendEl { el_new },
@@ -362,21 +229,6 @@ where
         })
     }
 
-    fn insert(
-        &mut self,
-        child_new: &'a mut Node<Ms>,
-        next_node: web_sys::Node,
-    ) -> Option<PatchCommand<'a, Ms>> {
-        Some(match child_new {
-            Node::Element(el_new) => PatchCommand::InsertEl { el_new, next_node },
-            Node::Text(text_new) => PatchCommand::InsertText {
-                text_new,
-                next_node,
-            },
-            Node::Empty | Node::NoChange => return self.next_command(),
-        })
-    }
-
     #[allow(clippy::option_if_let_else)]
     fn patch_or_replace(
         &mut self,
@@ -462,68 +314,201 @@ where
     }
 }
 
-/// Checks whether the old element can be updated with a new one.
-pub fn el_can_be_patched<Ms>(el_old: &El<Ms>, el_new: &El<Ms>) -> bool {
-    el_old.namespace == el_new.namespace && el_old.tag == el_new.tag && el_old.key == el_new.key
-}
-
-/// Takes children from source iterators (new and old) and puts them in the
-/// corresponding queues.
+/// Pairs old and new children by `PatchKey`s and creates commands that remove unpaired old
+/// children, move the minimal number of paired children, patch all paired children
+/// and insert unpaired new children.
 ///
-/// Stops when:
-/// - The key of the new child matches to any key of the previously seen old children.
-/// - The key of the old child matches to any key of the previously seen new children.
-fn find_matching<OI, NI, ON, NN, Ms>(
-    old_children_iter: &mut Peekable<OI>,
-    old_children: &mut VecDeque<ON>,
-    new_children_iter: &mut Peekable<NI>,
-    new_children: &mut VecDeque<NN>,
-) -> Option<PatchKey>
-where
-    OI: Iterator<Item = ON>,
-    NI: Iterator<Item = NN>,
-    ON: Borrow<Node<Ms>>,
-    NN: Borrow<Node<Ms>>,
-    Ms: 'static,
-{
-    // First store all seen keys to the sets.
-    // One for the old children.
-    let mut seen_old_keys: BTreeSet<_> = old_children
+/// See the module documentation for more info.
+fn lis_commands<'a, Ms: 'static>(
+    old_children: Vec<Node<Ms>>,
+    new_children: Vec<&'a mut Node<Ms>>,
+) -> VecDeque<PatchCommand<'a, Ms>> {
+    let mut old_children = old_children
+        .into_iter()
+        .filter(|child| !child.is_empty())
+        .map(Some)
+        .collect::<Vec<_>>();
+
+    let mut old_indices_by_key = BTreeMap::<PatchKey, VecDeque<usize>>::new();
+    for (index, child) in old_children.iter().enumerate() {
+        if let Some(key) = child.as_ref().and_then(PatchKey::new) {
+            old_indices_by_key.entry(key).or_default().push_back(index);
+        }
+    }
+
+    // `sources[new index] == Some(old index)`
+    let mut sources = new_children
         .iter()
-        .filter_map(|node| PatchKey::new(node.borrow()))
-        .collect();
-    // And one for the new children.
-    let mut seen_new_keys: BTreeSet<_> = new_children
+        .map(|child| {
+            PatchKey::new(child)
+                .and_then(|key| old_indices_by_key.get_mut(&key))
+                .and_then(VecDeque::pop_front)
+        })
+        .collect::<Vec<_>>();
+
+    // `Node::NoChange` doesn't have a key - pair it with any unpaired old child.
+    let mut paired_old = vec![false; old_children.len()];
+    for old_index in sources.iter().flatten() {
+        paired_old[*old_index] = true;
+    }
+    let mut unpaired_old = (0..old_children.len()).filter(|index| !paired_old[*index]);
+    for (source, child) in sources.iter_mut().zip(&new_children) {
+        if let Node::NoChange = child {
+            *source = unpaired_old.next();
+        }
+    }
+    for old_index in sources.iter().flatten() {
+        paired_old[*old_index] = true;
+    }
+
+    let mut commands = VecDeque::new();
+
+    for (child_old, paired) in old_children.iter_mut().zip(paired_old) {
+        if !paired {
+            commands.extend(remove_command(
+                child_old.take().expect("unpaired old child"),
+            ));
+        }
+    }
+
+    let paired_new_indices = sources
         .iter()
-        .filter_map(|node| PatchKey::new(node.borrow()))
-        .collect();
-
-    while old_children_iter.peek().is_some() || new_children_iter.peek().is_some() {
-        // Fill the old/new children queues and keep the same queue lengths.
-        let should_pick_old_child = old_children_iter.peek().is_some()
-            && (new_children_iter.peek().is_none() || new_children.len() > old_children.len());
-
-        if should_pick_old_child {
-            if let Some(key) = fetch_next_item(old_children_iter, old_children)
-                .and_then(|child| PatchKey::new(child.borrow()))
-            {
-                if seen_new_keys.contains(&key) {
-                    return Some(key);
+        .enumerate()
+        .filter_map(|(new_index, source)| source.map(|_| new_index))
+        .collect::<Vec<_>>();
+    let paired_sources = sources.iter().flatten().copied().collect::<Vec<_>>();
+    let mut stable = vec![false; new_children.len()];
+    for index in longest_increasing_subsequence(&paired_sources) {
+        stable[paired_new_indices[index]] = true;
+    }
+
+    let mut new_children = new_children.into_iter().map(Some).collect::<Vec<_>>();
+    let mut inserts = Vec::new();
+    let mut next_node: Option<web_sys::Node> = None;
+
+    for new_index in (0..new_children.len()).rev() {
+        let child_new = new_children[new_index].take().expect("new child");
+        let old_index = match sources[new_index] {
+            Some(old_index) => old_index,
+            None => {
+                match child_new {
+                    // An orphaned `NoChange` has nothing to keep.
+                    Node::NoChange => *child_new = Node::Empty,
+                    Node::Empty => (),
+                    _ => inserts.push((child_new, next_node.clone())),
                 }
-                seen_old_keys.insert(key);
+                continue;
             }
-        } else if new_children_iter.peek().is_some() {
-            if let Some(key) = fetch_next_item(new_children_iter, new_children)
-                .and_then(|child| PatchKey::new(child.borrow()))
-            {
-                if seen_old_keys.contains(&key) {
-                    return Some(key);
+        };
+        let child_old = old_children[old_index].take().expect("paired old child");
+        let node_ws = child_old
+            .node_ws()
+            .expect("old node connected to web_sys node")
+            .clone();
+
+        if !stable[new_index] {
+            commands.push_back(PatchCommand::MoveNode {
+                node_ws: node_ws.clone(),
+                next_node: next_node.clone(),
+            });
+        }
+        commands.extend(patch_command(child_old, child_new));
+        next_node = Some(node_ws);
+    }
+
+    // Insert unpaired new children in their order before their paired successors.
+    for (child_new, next_node) in inserts.into_iter().rev() {
+        commands.extend(insert_command(child_new, next_node));
+    }
+    commands
+}
+
+/// Creates a command for a pair of old and new children with the same `PatchKey`.
+fn patch_command<'a, Ms>(
+    child_old: Node<Ms>,
+    child_new: &'a mut Node<Ms>,
+) -> Option<PatchCommand<'a, Ms>> {
+    match (child_old, child_new) {
+        (Node::Element(el_old), Node::Element(el_new)) => {
+            Some(PatchCommand::PatchEl { el_old, el_new })
+        }
+        (Node::Text(text_old), Node::Text(text_new)) => {
+            Some(PatchCommand::PatchText { text_old, text_new })
+        }
+        (child_old, child_new @ Node::NoChange) => {
+            *child_new = child_old;
+            None
+        }
+        _ => unreachable!("patched children have the same `PatchKey`"),
+    }
+}
+
+fn insert_command<Ms>(
+    child_new: &mut Node<Ms>,
+    next_node: Option<web_sys::Node>,
+) -> Option<PatchCommand<'_, Ms>> {
+    Some(match (child_new, next_node) {
+        (Node::Element(el_new), Some(next_node)) => PatchCommand::InsertEl { el_new, next_node },
+        (Node::Element(el_new), None) => PatchCommand::AppendEl { el_new },
+        (Node::Text(text_new), Some(next_node)) => PatchCommand::InsertText {
+            text_new,
+            next_node,
+        },
+        (Node::Text(text_new), None) => PatchCommand::AppendText { text_new },
+        (Node::Empty, _) | (Node::NoChange, _) => return None,
+    })
+}
+
+fn remove_command<'a, Ms>(child_old: Node<Ms>) -> Option<PatchCommand<'a, Ms>> {
+    Some(match child_old {
+        Node::Element(el_old) => PatchCommand::RemoveEl { el_old },
+        Node::Text(text_old) => PatchCommand::RemoveText { text_old },
+        Node::Empty | Node::NoChange => return None,
+    })
+}
+
+/// Returns indices of the items that form the longest strictly increasing subsequence.
+///
+/// O(n log n) - patience sorting with predecessor links.
+fn longest_increasing_subsequence(sequence: &[usize]) -> Vec<usize> {
+    // `tails[length - 1]` is the index of the smallest tail of all increasing
+    // subsequences with the given `length`.
+    let mut tails = Vec::<usize>::new();
+    let mut predecessors = vec![None; sequence.len()];
+
+    for (index, value) in sequence.iter().enumerate() {
+        let position = tails
+            .binary_search_by(|tail| {
+                if sequence[*tail] < *value {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
                 }
-                seen_new_keys.insert(key);
-            }
+            })
+            .unwrap_or_else(|position| position);
+        if position > 0 {
+            predecessors[index] = Some(tails[position - 1]);
+        }
+        if position == tails.len() {
+            tails.push(index);
+        } else {
+            tails[position] = index;
         }
     }
-    None
+
+    let mut subsequence = Vec::with_capacity(tails.len());
+    let mut index = tails.last().copied();
+    while let Some(current) = index {
+        subsequence.push(current);
+        index = predecessors[current];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+/// Checks whether the old element can be updated with a new one.
+pub fn el_can_be_patched<Ms>(el_old: &El<Ms>, el_new: &El<Ms>) -> bool {
+    el_old.namespace == el_new.namespace && el_old.tag == el_new.tag && el_old.key == el_new.key
 }
 
 /// Searches for the next node with set `web_sys::Node` and returns a clone of that
@@ -561,3 +546,46 @@ where
         queue.front()
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn lis_empty() {
+        assert!(longest_increasing_subsequence(&[]).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn lis_sorted() {
+        assert_eq!(
+            longest_increasing_subsequence(&[0, 1, 2, 3]),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn lis_reversed() {
+        assert_eq!(longest_increasing_subsequence(&[3, 2, 1, 0]).len(), 1);
+    }
+
+    #[wasm_bindgen_test]
+    fn lis_last_moved_to_front() {
+        assert_eq!(
+            longest_increasing_subsequence(&[4, 0, 1, 2, 3]),
+            vec![1, 2, 3, 4]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn lis_mixed() {
+        // Sources from the module documentation: `[a] [d] [e] [b] [c] [f]`.
+        assert_eq!(
+            longest_increasing_subsequence(&[0, 3, 4, 1, 2, 5]),
+            vec![0, 3, 4, 5]
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Collects `PatchStats` during the VDOM patch.
+
+use crate::app::render_info::PatchStats;
+use std::cell::Cell;
+
+thread_local! {
+    static PATCH_STATS: Cell<PatchStats> = Cell::new(PatchStats::default());
+}
+
+/// Update the collected stats.
+pub(crate) fn record(f: impl FnOnce(&mut PatchStats)) {
+    PATCH_STATS.with(|patch_stats| {
+        let mut stats = patch_stats.get();
+        f(&mut stats);
+        patch_stats.set(stats);
+    });
+}
+
+/// Return the collected stats and reset the collector.
+pub(crate) fn take() -> PatchStats {
+    PATCH_STATS.with(Cell::take)
+}
@@ -0,0 +1,376 @@
+//! Sanitizer for nodes created from untrusted HTML or markdown - see `raw_safe!` and `md_safe!`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//!use seed::{prelude::*, *};
+//!
+//!fn view(model: &Model) -> Vec<Node<Msg>> {
+//!    // Safe defaults.
+//!    let comment = md_safe!(&model.comment);
+//!    // Custom allowlist.
+//!    let sanitizer = Sanitizer::default()
+//!        .allow_tag_attributes("span", &["class"])
+//!        .allow_url_schemes(&["tel"]);
+//!    let bio = raw_safe!(&model.bio_html, &sanitizer);
+//!    nodes![comment, bio]
+//!}
+//! ```
+
+#[cfg(feature = "markdown")]
+use super::node::el::markdown_to_html;
+use super::{El, Node};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tags allowed by default - text formatting, lists, tables, links and images.
+const DEFAULT_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Attributes allowed by default on all tags.
+const DEFAULT_ATTRIBUTES: &[&str] = &["title", "lang", "dir"];
+
+/// Attributes allowed by default on specific tags.
+const DEFAULT_TAG_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href"]),
+    ("blockquote", &["cite"]),
+    ("code", &["class"]),
+    ("del", &["cite"]),
+    ("details", &["open"]),
+    ("img", &["src", "alt", "width", "height"]),
+    // Task lists in markdown.
+    ("input", &["type", "checked", "disabled"]),
+    ("ins", &["cite"]),
+    ("li", &["value"]),
+    ("ol", &["start", "reversed"]),
+    ("q", &["cite"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan"]),
+];
+
+/// URL schemes allowed by default. URLs without a scheme (relative URLs) are always allowed.
+const DEFAULT_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Attributes with URL values.
+const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "formaction",
+    "href",
+    "longdesc",
+    "poster",
+    "src",
+    "xlink:href",
+];
+
+/// Disallowed elements are replaced by their children - except these ones, they are removed
+/// with their content.
+const CONTENT_REMOVING_TAGS: &[&str] = &[
+    "applet", "embed", "frame", "frameset", "iframe", "math", "noembed", "noframes", "noscript",
+    "object", "script", "select", "style", "svg", "template", "textarea", "title", "xmp",
+];
+
+// ------ Sanitizer ------
+
+/// Allowlist of tags, attributes and URL schemes.
+///
+/// - Disallowed elements are replaced by their children (`script`, `style`, `iframe`, `svg`
+///   and other elements with non-text content are removed with their content).
+/// - Disallowed attributes and URLs with disallowed schemes (e.g. `javascript:`) are removed.
+/// - Event handler attributes (`onclick`, ..) are always removed.
+///
+/// `Sanitizer::default()` allows text formatting, lists, tables, links and images
+/// with `http`, `https` and `mailto` URLs.
+#[derive(Debug, Clone)]
+pub struct Sanitizer {
+    tags: BTreeSet<String>,
+    attributes: BTreeSet<String>,
+    tag_attributes: BTreeMap<String, BTreeSet<String>>,
+    url_schemes: BTreeSet<String>,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            tags: to_set(DEFAULT_TAGS),
+            attributes: to_set(DEFAULT_ATTRIBUTES),
+            tag_attributes: DEFAULT_TAG_ATTRIBUTES
+                .iter()
+                .map(|(tag, attributes)| ((*tag).to_owned(), to_set(attributes)))
+                .collect(),
+            url_schemes: to_set(DEFAULT_URL_SCHEMES),
+        }
+    }
+}
+
+impl Sanitizer {
+    /// Sanitizer that allows only text.
+    pub fn empty() -> Self {
+        Self {
+            tags: BTreeSet::new(),
+            attributes: BTreeSet::new(),
+            tag_attributes: BTreeMap::new(),
+            url_schemes: BTreeSet::new(),
+        }
+    }
+
+    pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+        self.tags.extend(to_set(tags));
+        self
+    }
+
+    pub fn deny_tags(mut self, tags: &[&str]) -> Self {
+        for tag in tags {
+            self.tags.remove(&tag.to_ascii_lowercase());
+        }
+        self
+    }
+
+    /// Allow attributes on all allowed tags.
+    pub fn allow_attributes(mut self, attributes: &[&str]) -> Self {
+        self.attributes.extend(to_set(attributes));
+        self
+    }
+
+    /// Allow attributes on the given tag.
+    pub fn allow_tag_attributes(mut self, tag: &str, attributes: &[&str]) -> Self {
+        self.tag_attributes
+            .entry(tag.to_ascii_lowercase())
+            .or_default()
+            .extend(to_set(attributes));
+        self
+    }
+
+    /// Deny attributes on all tags.
+    pub fn deny_attributes(mut self, attributes: &[&str]) -> Self {
+        for attribute in attributes {
+            let attribute = attribute.to_ascii_lowercase();
+            self.attributes.remove(&attribute);
+            for tag_attributes in self.tag_attributes.values_mut() {
+                tag_attributes.remove(&attribute);
+            }
+        }
+        self
+    }
+
+    /// Allow URL schemes (without `:`) - e.g. `&["tel", "ftp"]`.
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.url_schemes.extend(to_set(schemes));
+        self
+    }
+
+    pub fn deny_url_schemes(mut self, schemes: &[&str]) -> Self {
+        for scheme in schemes {
+            self.url_schemes.remove(&scheme.to_ascii_lowercase());
+        }
+        self
+    }
+
+    /// Sanitize nodes - e.g. created by `El::from_html` or `El::from_markdown`.
+    pub fn sanitize<Ms>(&self, nodes: Vec<Node<Ms>>) -> Vec<Node<Ms>> {
+        let mut sanitized = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            self.sanitize_node(node, &mut sanitized);
+        }
+        sanitized
+    }
+
+    /// Create sanitized nodes from an HTML string.
+    ///
+    /// The HTML is parsed in an inert document - scripts and event handlers aren't run
+    /// before the nodes are sanitized.
+    pub fn sanitize_html<Ms>(&self, html: &str) -> Vec<Node<Ms>> {
+        self.sanitize(El::from_html_inert(html))
+    }
+
+    /// Create sanitized nodes from a markdown string.
+    /// _Note:_ Requires the `markdown` feature.
+    #[cfg(feature = "markdown")]
+    pub fn sanitize_markdown<Ms>(&self, markdown: &str) -> Vec<Node<Ms>> {
+        self.sanitize(El::from_html_inert(&markdown_to_html(markdown)))
+    }
+
+    fn sanitize_node<Ms>(&self, node: Node<Ms>, sanitized: &mut Vec<Node<Ms>>) {
+        let mut el = match node {
+            Node::Element(el) => el,
+            node => return sanitized.push(node),
+        };
+        let tag = el.tag.as_str().to_ascii_lowercase();
+        let children = self.sanitize(std::mem::take(&mut el.children));
+
+        if !self.tags.contains(&tag) {
+            if !CONTENT_REMOVING_TAGS.contains(&tag.as_str()) {
+                sanitized.extend(children);
+            }
+            return;
+        }
+
+        let tag_attributes = self.tag_attributes.get(&tag);
+        el.attrs.vals.retain(|at, value| {
+            let attribute = at.as_str().to_ascii_lowercase();
+            let allowed = !attribute.starts_with("on")
+                && (self.attributes.contains(&attribute)
+                    || tag_attributes
+                        .iter()
+                        .any(|attributes| attributes.contains(&attribute)));
+            if !allowed {
+                return false;
+            }
+            if URL_ATTRIBUTES.contains(&attribute.as_str()) {
+                if let super::AtValue::Some(url) = value {
+                    return self.is_url_allowed(url);
+                }
+            }
+            true
+        });
+        // Styles can't be allowed through attributes.
+        el.style = super::Style::empty();
+        el.children = children;
+        sanitized.push(Node::Element(el));
+    }
+
+    fn is_url_allowed(&self, url: &str) -> bool {
+        // Browsers ignore whitespace and control characters in URL schemes.
+        let url = url
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .collect::<String>();
+        let scheme_end = url.find(&[':', '/', '?', '#'][..]);
+        match scheme_end {
+            Some(index) if url[index..].starts_with(':') => self
+                .url_schemes
+                .contains(&url[..index].to_ascii_lowercase()),
+            // Relative URL.
+            _ => true,
+        }
+    }
+}
+
+fn to_set(names: &[&str]) -> BTreeSet<String> {
+    names.iter().map(|name| name.to_ascii_lowercase()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::virtual_dom::tests::el;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn sanitize_nodes() {
+        let nodes: Vec<Node<()>> = nodes![
+            p![
+                attrs! {At::Title => "ok", At::Id => "removed", "onclick" => "alert(1)"},
+                "text",
+                custom![Tag::from("script"), "alert(1)"],
+                custom![Tag::from("blink"), "unwrapped"],
+            ],
+            a![attrs! {At::Href => " Java\tScript:alert(1)"}],
+            a![attrs! {At::Href => "/relative?x=a:b"}],
+            img![attrs! {At::Src => "https://example.com/a.png"}],
+            iframe![p!["removed"]],
+        ];
+        let nodes = Sanitizer::default().sanitize(nodes);
+        assert_eq!(nodes.len(), 4);
+
+        let p = el(&nodes[0]);
+        assert_eq!(p.attrs.vals.len(), 1);
+        assert!(p.attrs.vals.contains_key(&At::Title));
+        assert_eq!(p.children.len(), 2);
+        assert!(matches!(&p.children[1], Node::Text(text) if text.text == "unwrapped"));
+
+        assert!(el(&nodes[1]).attrs.vals.is_empty());
+        assert!(el(&nodes[2]).attrs.vals.contains_key(&At::Href));
+        assert!(el(&nodes[3]).attrs.vals.contains_key(&At::Src));
+    }
+
+    #[wasm_bindgen_test]
+    async fn raw_safe_runs_no_handlers() {
+        let flag = "seedSanitizerXss";
+        let nodes: Vec<Node<()>> = raw_safe!(&format!(
+            r#"<img src="x" onerror="window.{0} = true"><svg><script>window.{0} = true</script></svg>"#,
+            flag
+        ));
+        assert!(
+            matches!(&nodes[0], Node::Element(img) if img.attrs.vals.get(&At::from("onerror")).is_none())
+        );
+
+        // Give the browser time to load the image and fail.
+        gloo_timers::future::TimeoutFuture::new(200).await;
+        let value = js_sys::Reflect::get(&crate::browser::util::window(), &flag.into()).unwrap();
+        assert!(value.is_undefined());
+    }
+
+    #[wasm_bindgen_test]
+    fn custom_allowlist() {
+        let sanitizer = Sanitizer::empty()
+            .allow_tags(&["a", "span"])
+            .allow_tag_attributes("a", &["href", "onclick"])
+            .allow_url_schemes(&["tel"]);
+        let nodes: Vec<Node<()>> = nodes![
+            a![attrs! {At::Href => "tel:123", "onclick" => "alert(1)"}],
+            a![attrs! {At::Href => "https://example.com"}],
+            div![span!["text"]],
+        ];
+        let nodes = sanitizer.sanitize(nodes);
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(el(&nodes[0]).attrs.vals.len(), 1);
+        assert!(el(&nodes[1]).attrs.vals.is_empty());
+        assert_eq!(el(&nodes[2]).tag, Tag::Span);
+    }
+}
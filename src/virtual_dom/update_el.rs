@@ -1,4 +1,4 @@
-use super::{Attrs, El, ElKey, ElRef, EventHandler, Node, Style, Tag, Text};
+use super::{AnimateMoves, Attrs, El, ElKey, ElRef, EventHandler, Node, Style, Tag, Text};
 
 // ------ Traits ------
 
@@ -73,6 +73,12 @@ impl<Ms> UpdateEl<Ms> for ElKey {
     }
 }
 
+impl<Ms> UpdateEl<Ms> for AnimateMoves {
+    fn update_el(self, el: &mut El<Ms>) {
+        el.animate_moves = Some(self);
+    }
+}
+
 // --- Texts ---
 
 impl<Ms> UpdateEl<Ms> for String {
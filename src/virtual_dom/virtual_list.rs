@@ -0,0 +1,371 @@
+//! Virtualized list - renders only the visible rows of huge collections.
+
+use crate::app::{streams, Orders, StreamHandle};
+use crate::virtual_dom::{el_key, El, ElRef, Ev, EventHandler, Node, St, Tag, UpdateEl};
+use std::cell::Cell;
+use wasm_bindgen::JsCast;
+
+/// The number of rows rendered above and below the visible rows by default.
+const DEFAULT_OVERSCAN: usize = 5;
+
+// ------ RowHeight ------
+
+/// Height of `VirtualList` rows.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RowHeight {
+    /// All rows have the same height in pixels.
+    Fixed(f64),
+    /// Rows are measured once they're rendered; `estimate` (in pixels) is used for the others.
+    Measured { estimate: f64 },
+}
+
+// ------ Msg ------
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Msg {
+    /// The list container has been scrolled.
+    Scrolled {
+        scroll_top: f64,
+        viewport_height: f64,
+    },
+    /// The window has been resized.
+    Resized,
+    /// Measure the rendered rows. Send it after your items have been changed
+    /// when you use `RowHeight::Measured`.
+    Measure,
+}
+
+// ------ VirtualList ------
+
+/// `VirtualList` renders only the visible rows plus `overscan` rows above and below them.
+/// The space occupied by the other rows is filled by two spacer elements so the scrollbar
+/// behaves like all rows were rendered.
+///
+/// The list container fills its parent (`height: 100%`) - wrap it in an element with a height.
+///
+/// _Notes:_
+/// - Rows have to be elements - text nodes or empty nodes would break the measurement.
+/// - Rows without `el_key` get their index as a key to keep them stable during scrolling.
+///
+/// # Example
+///
+/// ```rust,no_run
+///// init
+///virtual_list: VirtualList::new(RowHeight::Fixed(30.), &mut orders.proxy(Msg::VirtualList)),
+///
+///// update
+///Msg::VirtualList(msg) => {
+///    model.virtual_list.update(msg, &mut orders.proxy(Msg::VirtualList))
+///}
+///
+///// view
+///div![
+///    style!{St::Height => px(500)},
+///    model.virtual_list.view(
+///        model.rows.len(),
+///        |index| div![el_key(&model.rows[index].id), &model.rows[index].title],
+///        Msg::VirtualList,
+///    ),
+///]
+/// ```
+#[derive(Debug)]
+pub struct VirtualList {
+    row_height: RowHeight,
+    overscan: usize,
+    spacer_tag: Tag,
+    scroll_top: f64,
+    viewport_height: f64,
+    measured_heights: Vec<Option<f64>>,
+    /// Indices of the rendered rows (`start..end`), set by `view`.
+    rendered_rows: Cell<(usize, usize)>,
+    /// The number of items passed to the last `view` call.
+    item_count: Cell<usize>,
+    container: ElRef<web_sys::Element>,
+    _resize_stream: StreamHandle,
+}
+
+impl VirtualList {
+    pub fn new(row_height: RowHeight, orders: &mut impl Orders<Msg>) -> Self {
+        orders.after_next_render(|_| Msg::Resized);
+        Self {
+            row_height,
+            overscan: DEFAULT_OVERSCAN,
+            spacer_tag: Tag::Div,
+            scroll_top: 0.,
+            viewport_height: 0.,
+            measured_heights: Vec::new(),
+            rendered_rows: Cell::new((0, 0)),
+            item_count: Cell::new(0),
+            container: ElRef::default(),
+            _resize_stream: orders
+                .stream_with_handle(streams::window_event(Ev::Resize, |_| Msg::Resized)),
+        }
+    }
+
+    /// Set the number of rows rendered above and below the visible rows. Default is `5`.
+    pub const fn overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// Set the tag of spacer elements - e.g. `Tag::Tr` when rows are table rows. Default is `Tag::Div`.
+    pub fn spacer_tag(mut self, spacer_tag: Tag) -> Self {
+        self.spacer_tag = spacer_tag;
+        self
+    }
+
+    /// Current scroll position of the list container in pixels.
+    pub const fn scroll_top(&self) -> f64 {
+        self.scroll_top
+    }
+
+    pub fn update(&mut self, msg: Msg, orders: &mut impl Orders<Msg>) {
+        match msg {
+            Msg::Scrolled {
+                scroll_top,
+                viewport_height,
+            } => {
+                // Measured windows are computed row by row - compare only the cheap fixed ones.
+                let old_window = match self.row_height {
+                    RowHeight::Fixed(_) => Some(self.window(self.item_count.get())),
+                    RowHeight::Measured { .. } => None,
+                };
+                self.scroll_top = scroll_top;
+                self.viewport_height = viewport_height;
+                if old_window == Some(self.window(self.item_count.get())) {
+                    orders.skip();
+                }
+            }
+            Msg::Resized => {
+                if let Some(container) = self.container.get() {
+                    self.scroll_top = f64::from(container.scroll_top());
+                    self.viewport_height = f64::from(container.client_height());
+                }
+            }
+            Msg::Measure => {
+                if !self.measure() {
+                    orders.skip();
+                }
+                return;
+            }
+        }
+        if let RowHeight::Measured { .. } = self.row_height {
+            orders.after_next_render(|_| Msg::Measure);
+        }
+    }
+
+    pub fn view<Ms: 'static>(
+        &self,
+        item_count: usize,
+        item_view: impl Fn(usize) -> Node<Ms>,
+        to_msg: impl FnOnce(Msg) -> Ms + Clone + 'static,
+    ) -> Node<Ms> {
+        let window = self.window(item_count);
+        self.rendered_rows.set((window.start, window.end));
+        self.item_count.set(item_count);
+
+        let mut container = El::empty(Tag::Div);
+        container
+            .add_style(St::Height, "100%")
+            .add_style(St::OverflowY, "auto")
+            .add_event_handler(
+                EventHandler::new(Ev::Scroll, move |event| {
+                    let container = event.target()?.dyn_into::<web_sys::Element>().ok()?;
+                    Some(to_msg.clone()(Msg::Scrolled {
+                        scroll_top: f64::from(container.scroll_top()),
+                        viewport_height: f64::from(container.client_height()),
+                    }))
+                })
+                .passive(),
+            );
+        self.container.clone().update_el(&mut container);
+
+        container.add_child(self.spacer("seed-virtual-list-top-spacer", window.top_space));
+        for index in window.start..window.end {
+            let mut row = item_view(index);
+            if let Node::Element(el) = &mut row {
+                if el.key.is_none() {
+                    el.key = Some(el_key(&index));
+                }
+            }
+            container.add_child(row);
+        }
+        container.add_child(self.spacer("seed-virtual-list-bottom-spacer", window.bottom_space));
+        Node::Element(container)
+    }
+
+    fn spacer<Ms>(&self, key: &str, height: f64) -> Node<Ms> {
+        let mut spacer = El::empty(self.spacer_tag.clone());
+        spacer.key = Some(el_key(&key));
+        spacer.add_style(St::Height, format!("{}px", height));
+        Node::Element(spacer)
+    }
+
+    fn row_height(&self, index: usize) -> f64 {
+        match self.row_height {
+            RowHeight::Fixed(height) => height,
+            RowHeight::Measured { estimate } => self
+                .measured_heights
+                .get(index)
+                .copied()
+                .flatten()
+                .unwrap_or(estimate),
+        }
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    fn window(&self, item_count: usize) -> Window {
+        let viewport_bottom = self.scroll_top + self.viewport_height;
+
+        if let RowHeight::Fixed(height) = self.row_height {
+            let first_visible =
+                ((self.scroll_top / height).floor().max(0.) as usize).min(item_count);
+            let last_visible = ((viewport_bottom / height).ceil().max(0.) as usize).min(item_count);
+            let start = first_visible.saturating_sub(self.overscan);
+            let end = last_visible.saturating_add(self.overscan).min(item_count);
+            return Window {
+                start,
+                end,
+                top_space: start as f64 * height,
+                bottom_space: (item_count - end) as f64 * height,
+            };
+        }
+
+        let mut offset = 0.;
+        let mut first_visible = item_count;
+        let mut last_visible = item_count;
+        for index in 0..item_count {
+            let height = self.row_height(index);
+            if first_visible == item_count && offset + height > self.scroll_top {
+                first_visible = index;
+            }
+            if offset >= viewport_bottom {
+                last_visible = index;
+                break;
+            }
+            offset += height;
+        }
+        let start = first_visible.saturating_sub(self.overscan);
+        let end = last_visible.saturating_add(self.overscan).min(item_count);
+        Window {
+            start,
+            end,
+            top_space: (0..start).map(|index| self.row_height(index)).sum(),
+            bottom_space: (end..item_count).map(|index| self.row_height(index)).sum(),
+        }
+    }
+
+    /// Measure rendered rows and return `true` if any height has been changed.
+    fn measure(&mut self) -> bool {
+        let container = match self.container.get() {
+            Some(container) => container,
+            None => return false,
+        };
+        let (start, end) = self.rendered_rows.get();
+        if self.measured_heights.len() < end {
+            self.measured_heights.resize(end, None);
+        }
+        let rows = container.children();
+        let mut changed = false;
+        for (index, row_index) in (start..end).enumerate() {
+            // `+ 1` to skip the top spacer.
+            #[allow(clippy::cast_possible_truncation)]
+            let row = match rows.item(index as u32 + 1) {
+                Some(row) => row,
+                None => break,
+            };
+            let height = row.get_bounding_client_rect().height();
+            let measured_height = &mut self.measured_heights[row_index];
+            match measured_height {
+                Some(old_height) if (*old_height - height).abs() <= 0.5 => (),
+                _ => {
+                    *measured_height = Some(height);
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+}
+
+// ------ Window ------
+
+/// Rendered rows (`start..end`) and heights of spacers.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Window {
+    start: usize,
+    end: usize,
+    top_space: f64,
+    bottom_space: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::stream_manager::StreamManager;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn virtual_list(row_height: RowHeight, scroll_top: f64, viewport_height: f64) -> VirtualList {
+        VirtualList {
+            row_height,
+            overscan: 2,
+            spacer_tag: Tag::Div,
+            scroll_top,
+            viewport_height,
+            measured_heights: Vec::new(),
+            rendered_rows: Cell::new((0, 0)),
+            item_count: Cell::new(0),
+            container: ElRef::default(),
+            _resize_stream: StreamManager::stream_with_handle(futures::stream::empty()),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn fixed_window() {
+        let list = virtual_list(RowHeight::Fixed(10.), 100., 50.);
+        assert_eq!(
+            list.window(1000),
+            Window {
+                start: 8,
+                end: 17,
+                top_space: 80.,
+                bottom_space: 9830.,
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn fixed_window_at_the_end() {
+        let list = virtual_list(RowHeight::Fixed(10.), 95., 50.);
+        assert_eq!(
+            list.window(12),
+            Window {
+                start: 7,
+                end: 12,
+                top_space: 70.,
+                bottom_space: 0.,
+            }
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn measured_window() {
+        let mut list = virtual_list(RowHeight::Measured { estimate: 10. }, 100., 50.);
+        list.measured_heights = vec![Some(50.), Some(50.)];
+        assert_eq!(
+            list.window(100),
+            Window {
+                start: 0,
+                end: 9,
+                top_space: 0.,
+                bottom_space: 910.,
+            }
+        );
+    }
+}